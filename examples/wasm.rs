@@ -0,0 +1,35 @@
+//! Minimal extraction that builds for `wasm32-unknown-unknown`.
+//!
+//! The crate does no file, network, thread or clock access, so parsing works
+//! the same in the browser. This example only uses an in-memory string:
+//!
+//! ```bash
+//! rustup target add wasm32-unknown-unknown
+//! cargo build --example wasm --target wasm32-unknown-unknown
+//! ```
+//!
+//! To call it from JavaScript, wrap a function like [`extract`] with
+//! `wasm-bindgen` in your own crate.
+
+use readabilityrs::{Readability, ReadabilityOptions};
+
+/// Extract the article title and text from `html`.
+pub fn extract(html: &str, url: Option<&str>) -> Option<(String, String)> {
+    let options = ReadabilityOptions::builder().char_threshold(100).build();
+    let article = Readability::new(html, url, Some(options)).ok()?.parse()?;
+    Some((
+        article.title.unwrap_or_default(),
+        article.text_content.unwrap_or_default(),
+    ))
+}
+
+fn main() {
+    let html = r#"<html><head><title>Garden notes</title></head><body><article>
+<h1>Garden notes</h1>
+<p>Spring has finally arrived, and with it the first wave of seedlings. We spent the week transplanting tomatoes, peppers, and a few experimental squash varieties.</p>
+</article></body></html>"#;
+
+    if let Some((title, text)) = extract(html, Some("https://example.com/garden")) {
+        println!("{title}\n\n{text}");
+    }
+}
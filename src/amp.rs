@@ -0,0 +1,252 @@
+//! Conversion of article HTML to an AMP-safe subset.
+//!
+//! [`html_to_amp`] rewrites cleaned article content so it can be embedded in
+//! an AMP page body: elements outside a conservative allowlist are unwrapped
+//! or dropped, attributes are reduced to those AMP accepts, and `<img>`
+//! becomes `<amp-img>` with the `width` and `height` AMP requires.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use readabilityrs::amp::html_to_amp;
+//!
+//! let html = r#"<p style="color: red">Hi <img src="a.jpg" width="640" height="480"></p>"#;
+//! assert_eq!(
+//!     html_to_amp(html),
+//!     r#"<p>Hi <amp-img src="a.jpg" width="640" height="480" layout="responsive"></amp-img></p>"#
+//! );
+//! ```
+
+use scraper::node::Node;
+use scraper::{ElementRef, Html};
+
+/// Elements kept as-is, with their allowed attributes.
+const ALLOWED_TAGS: &[&str] = &[
+    "a",
+    "abbr",
+    "article",
+    "aside",
+    "b",
+    "blockquote",
+    "br",
+    "caption",
+    "cite",
+    "code",
+    "col",
+    "colgroup",
+    "dd",
+    "del",
+    "details",
+    "dfn",
+    "div",
+    "dl",
+    "dt",
+    "em",
+    "figcaption",
+    "figure",
+    "footer",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "i",
+    "ins",
+    "kbd",
+    "li",
+    "mark",
+    "ol",
+    "p",
+    "pre",
+    "q",
+    "s",
+    "samp",
+    "section",
+    "small",
+    "span",
+    "strong",
+    "sub",
+    "summary",
+    "sup",
+    "table",
+    "tbody",
+    "td",
+    "tfoot",
+    "th",
+    "thead",
+    "time",
+    "tr",
+    "u",
+    "ul",
+    "var",
+];
+
+/// Elements removed with their content.
+const DROPPED_TAGS: &[&str] = &[
+    "applet", "audio", "base", "button", "canvas", "embed", "form", "frame", "frameset", "iframe",
+    "input", "link", "math", "meta", "noscript", "object", "param", "script", "select", "source",
+    "style", "svg", "template", "textarea", "track", "video",
+];
+
+/// Elements serialized without an end tag.
+const VOID_TAGS: &[&str] = &["br", "col", "hr"];
+
+/// Attributes kept on every allowed element.
+const GLOBAL_ATTRS: &[&str] = &["class", "dir", "id", "lang", "title"];
+
+/// Convert article HTML to markup that is valid inside an AMP page body.
+///
+/// - `<img>` becomes `<amp-img layout="responsive">` keeping `src`, `srcset`,
+///   `sizes` and `alt`. AMP needs the image's dimensions, so images without
+///   numeric `width` and `height` attributes are dropped.
+/// - Scripts, styles, forms, iframes, embeds and media players are removed
+///   with their content; other elements outside the allowlist, such as
+///   `<font>` or `<picture>`, are unwrapped.
+/// - `style`, event handler and other attributes AMP rejects are removed,
+///   as are `javascript:` links.
+pub fn html_to_amp(html: &str) -> String {
+    let doc = Html::parse_fragment(html);
+    let mut out = String::with_capacity(html.len());
+    push_amp_children(doc.root_element(), &mut out);
+    out
+}
+
+fn push_amp_children(parent: ElementRef, out: &mut String) {
+    for child in parent.children() {
+        match child.value() {
+            Node::Text(text) => out.push_str(&escape_text(&text.text)),
+            Node::Element(_) => {
+                if let Some(element) = ElementRef::wrap(child) {
+                    push_amp_element(element, out);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn push_amp_element(element: ElementRef, out: &mut String) {
+    let name = element.value().name();
+    if name == "img" {
+        push_amp_img(element, out);
+        return;
+    }
+    if DROPPED_TAGS.contains(&name) {
+        return;
+    }
+    if !ALLOWED_TAGS.contains(&name) {
+        push_amp_children(element, out);
+        return;
+    }
+
+    out.push('<');
+    out.push_str(name);
+    for (attr, value) in element.value().attrs() {
+        if is_allowed_attr(name, attr, value) {
+            push_attr(out, attr, value);
+        }
+    }
+    out.push('>');
+    if VOID_TAGS.contains(&name) {
+        return;
+    }
+    push_amp_children(element, out);
+    out.push_str("</");
+    out.push_str(name);
+    out.push('>');
+}
+
+fn push_amp_img(img: ElementRef, out: &mut String) {
+    let element = img.value();
+    let (Some(src), Some(width), Some(height)) = (
+        element.attr("src").filter(|src| !src.trim().is_empty()),
+        element.attr("width").and_then(parse_dimension),
+        element.attr("height").and_then(parse_dimension),
+    ) else {
+        return;
+    };
+
+    out.push_str("<amp-img");
+    push_attr(out, "src", src);
+    for attr in ["srcset", "sizes", "alt"] {
+        if let Some(value) = element.attr(attr) {
+            push_attr(out, attr, value);
+        }
+    }
+    push_attr(out, "width", &width.to_string());
+    push_attr(out, "height", &height.to_string());
+    push_attr(out, "layout", "responsive");
+    out.push_str("></amp-img>");
+}
+
+/// Parse a `width` or `height` attribute such as `640` or `640px`.
+fn parse_dimension(value: &str) -> Option<u32> {
+    value
+        .trim()
+        .trim_end_matches("px")
+        .parse::<u32>()
+        .ok()
+        .filter(|size| *size > 0)
+}
+
+fn is_allowed_attr(tag: &str, attr: &str, value: &str) -> bool {
+    if GLOBAL_ATTRS.contains(&attr) {
+        return true;
+    }
+    match (tag, attr) {
+        ("a", "href") => !value.trim_start().to_lowercase().starts_with("javascript:"),
+        ("a", "rel" | "target" | "hreflang")
+        | ("blockquote" | "q" | "del" | "ins", "cite")
+        | ("td" | "th", "colspan" | "rowspan" | "headers")
+        | ("th", "scope")
+        | ("ol", "start" | "reversed" | "type")
+        | ("li", "value")
+        | ("col" | "colgroup", "span")
+        | ("time" | "del" | "ins", "datetime")
+        | ("details", "open") => true,
+        _ => false,
+    }
+}
+
+fn push_attr(out: &mut String, name: &str, value: &str) {
+    out.push(' ');
+    out.push_str(name);
+    out.push_str("=\"");
+    out.push_str(
+        &value
+            .replace('&', "&amp;")
+            .replace('"', "&quot;")
+            .replace('\u{a0}', "&nbsp;"),
+    );
+    out.push('"');
+}
+
+/// Escape a text node the way scraper serializes it.
+fn escape_text(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('\u{a0}', "&nbsp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_html_to_amp() {
+        let html = r#"<div class="body" style="margin: 0" onclick="track()"><p>Text <font color="red">with</font> <a href="https://example.com" rel="nofollow" data-id="1">a link</a> and <a href="javascript:void(0)">a script link</a>.</p>
+<figure><picture><source srcset="a.webp"><img src="a.jpg" alt="Heron &amp; chick" width="640px" height="480"></picture><figcaption>Heron</figcaption></figure>
+<img src="no-size.jpg"><script>track()</script><iframe src="https://example.com/embed"></iframe><br><table><tr><td colspan="2" width="50%">Cell</td></tr></table></div>"#;
+
+        assert_eq!(
+            html_to_amp(html),
+            r#"<div class="body"><p>Text with <a href="https://example.com" rel="nofollow">a link</a> and <a>a script link</a>.</p>
+<figure><amp-img src="a.jpg" alt="Heron &amp; chick" width="640" height="480" layout="responsive"></amp-img><figcaption>Heron</figcaption></figure>
+<br><table><tbody><tr><td colspan="2">Cell</td></tr></tbody></table></div>"#
+        );
+    }
+}
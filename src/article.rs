@@ -29,7 +29,23 @@
 //! }
 //! ```
 
+use crate::blocks::Block;
+use crate::markdown::MarkdownOptions;
+use ego_tree::iter::Edge;
+use scraper::{ElementRef, Html};
 use serde::{Deserialize, Serialize};
+use std::io::{self, Write};
+use v_htmlescape::escape;
+
+/// Stylesheet [`Article::to_document`] inlines: a centered, narrow column of
+/// readable text with images scaled to fit.
+pub const DOCUMENT_STYLESHEET: &str = "body { margin: 0 auto; max-width: 42em; padding: 1em; font: 1.125em/1.6 Georgia, serif; color: #222; }
+header { margin-bottom: 2em; }
+h1, h2, h3, h4, h5, h6 { line-height: 1.25; }
+.byline, time { color: #666; font-size: 0.9em; }
+img, video, iframe { max-width: 100%; height: auto; }
+pre { overflow-x: auto; padding: 1em; background: #f5f5f5; }
+blockquote { margin-left: 0; padding-left: 1em; border-left: 3px solid #ddd; color: #555; }";
 
 /// Represents a successfully parsed article with extracted content and metadata.
 ///
@@ -89,6 +105,37 @@ pub struct Article {
     /// useful for reading time estimation or content validation.
     pub length: usize,
 
+    /// Number of words in the plain text content.
+    ///
+    /// Space-delimited languages count runs of letters and digits. Chinese,
+    /// Japanese, Thai, Lao, Khmer and Burmese are written without spaces, so
+    /// each character of those scripts counts as one word; divide by a
+    /// per-language reading speed to estimate reading time.
+    #[serde(default)]
+    pub word_count: usize,
+
+    /// Confidence that the extracted content is the page's article, from `0.0` to `1.0`.
+    ///
+    /// Derived from the selected candidate's content score, the text length
+    /// and the link density of the content. Roughly, above `0.7` is a full
+    /// article, `0.4` to `0.7` a short article or a partial extraction, and
+    /// below `0.3` a thin or link-heavy page worth reviewing.
+    #[serde(default)]
+    pub score: f64,
+
+    /// XPath-like path to the selected content container, such as
+    /// `/html/body/div[2]/article`.
+    ///
+    /// Identifies the candidate in the preprocessed document, not the raw
+    /// HTML. Pass it to [`Readability::parse_excluding`](crate::Readability::parse_excluding)
+    /// to reject this block and get the next-best extraction.
+    ///
+    /// `None` when the content is not a candidate of the page itself: a clean
+    /// fragment taken whole, or HTML found in script data or an iframe
+    /// `srcdoc`.
+    #[serde(default)]
+    pub candidate_path: Option<String>,
+
     /// Article description or short excerpt.
     ///
     /// The excerpt is extracted from JSON-LD description if available, otherwise from
@@ -132,6 +179,52 @@ pub struct Article {
     /// `Content-Language` meta tag.
     pub lang: Option<String>,
 
+    /// Alternate-language versions of the page as `(lang, url)` pairs.
+    ///
+    /// Collected from `<link rel="alternate" hreflang="...">` entries in the
+    /// document head. URLs are resolved against the base URL when one was
+    /// provided to [`Readability::new`](crate::Readability::new).
+    #[serde(default)]
+    pub alternates: Vec<(String, String)>,
+
+    /// Site icon URL.
+    ///
+    /// The highest-resolution `<link rel="icon">` or `<link rel="apple-touch-icon">`,
+    /// resolved to an absolute URL when a base URL was provided. Falls back to
+    /// the `og:image` when the page declares no icon.
+    #[serde(default)]
+    pub icon: Option<String>,
+
+    /// Structured recipe or how-to steps.
+    ///
+    /// Populated when the page declares a schema.org `Recipe` or `HowTo` in
+    /// JSON-LD. Lists in the body that carry these ingredients or steps are
+    /// also protected from conditional cleaning.
+    #[serde(default)]
+    pub recipe: Option<Recipe>,
+
+    /// Category path from the page's breadcrumb trail, outermost first.
+    ///
+    /// Read from schema.org `BreadcrumbList` JSON-LD or microdata, or from
+    /// breadcrumb navigation markup, before navigation is removed from the
+    /// content. Only populated when `ReadabilityOptions::extract_breadcrumbs`
+    /// is `true`.
+    #[serde(default)]
+    pub breadcrumbs: Vec<String>,
+
+    /// Section or category of the article, such as `Politics` or `Recipes`.
+    ///
+    /// Extracted from the JSON-LD `articleSection` field or the OpenGraph
+    /// `article:section` meta tag.
+    #[serde(default)]
+    pub section: Option<String>,
+
+    /// Images and videos in the cleaned content, in document order.
+    ///
+    /// Only populated when `ReadabilityOptions::extract_media` is `true`.
+    #[serde(default)]
+    pub media: Vec<MediaItem>,
+
     /// Publication or modification timestamp.
     ///
     /// The publication time is extracted from the JSON-LD `datePublished` field or the
@@ -139,22 +232,351 @@ pub struct Article {
     /// is typically ISO 8601.
     pub published_time: Option<String>,
 
+    /// Last modification timestamp.
+    ///
+    /// Extracted from the JSON-LD `dateModified` field, the
+    /// `article:modified_time` meta tag or `og:updated_time`, as written by
+    /// the page.
+    #[serde(default)]
+    pub modified_time: Option<String>,
+
+    /// Publication time parsed into a timestamp.
+    ///
+    /// Parsed from `published_time`, or from a `<time datetime>` element when
+    /// the page has no publish date metadata. Values without an offset are
+    /// read as UTC. `None` when no date parses; `published_time` still holds
+    /// the raw string in that case. Requires the `dates` feature.
+    #[cfg(feature = "dates")]
+    #[serde(default)]
+    pub published: Option<chrono::DateTime<chrono::FixedOffset>>,
+
+    /// Modification time parsed into a timestamp.
+    ///
+    /// Parsed from `modified_time`, or from a `<time datetime>` element marked
+    /// as the modified date. Requires the `dates` feature.
+    #[cfg(feature = "dates")]
+    #[serde(default)]
+    pub modified: Option<chrono::DateTime<chrono::FixedOffset>>,
+
+    /// The matched schema.org article object from the page's JSON-LD.
+    ///
+    /// This is the object `title`, `byline`, `published_time` and the other
+    /// JSON-LD sourced fields were read from, after picking it out of an array
+    /// or `@graph`. Read fields the typed metadata does not model, such as
+    /// `keywords` or `wordCount`, from here instead of parsing the scripts
+    /// again. `None` when the page has no article JSON-LD or
+    /// `ReadabilityOptions::disable_json_ld` is set.
+    #[serde(default)]
+    pub json_ld: Option<serde_json::Value>,
+
     /// Raw HTML content before final post-processing.
     ///
     /// This is the extracted content before the final cleaning steps,
     /// useful for debugging or custom post-processing.
     pub raw_content: Option<String>,
 
+    /// Article HTML after only the light cleaning passes.
+    ///
+    /// Navigation sections are removed but conditional cleaning, style
+    /// stripping and empty-element removal are skipped. Compare with `content`
+    /// to diagnose over-cleaning, or run your own sanitizer on it. Only
+    /// populated when `ReadabilityOptions::include_light_content` is `true`.
+    #[serde(default)]
+    pub light_content: Option<String>,
+
     /// Markdown content of the article.
     ///
     /// Only populated when `ReadabilityOptions::output_markdown` is `true`.
     /// Contains the article content converted to Markdown format after
     /// the HTML has been cleaned and standardized.
     pub markdown_content: Option<String>,
+
+    /// Article content restricted to markup valid in an AMP page body.
+    ///
+    /// Only populated when `ReadabilityOptions::output_amp` is `true`. Images
+    /// are converted to `<amp-img>`; those without declared dimensions are
+    /// left out.
+    #[serde(default)]
+    pub amp_content: Option<String>,
+
+    /// Article content as typed blocks: paragraphs, headings, lists, images,
+    /// code and quotes.
+    ///
+    /// Only populated when `ReadabilityOptions::output_blocks` is `true`.
+    /// Built from the standardized content, like `markdown_content`.
+    #[serde(default)]
+    pub blocks: Option<Vec<Block>>,
+
+    /// Article content as plain text, one line per block.
+    ///
+    /// Only populated when `ReadabilityOptions::output_plain_text` is `true`.
+    /// Paragraphs, headings, list items and other blocks each take their own
+    /// line, with whitespace collapsed and zero-width characters dropped.
+    #[serde(default)]
+    pub plain_text: Option<String>,
 }
 
 impl Article {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Write the cleaned HTML content to `writer`.
+    ///
+    /// Streams `content` to a file, socket or any other [`std::io::Write`]
+    /// without cloning it. Writes nothing when there is no content.
+    ///
+    /// ```rust,no_run
+    /// use readabilityrs::Readability;
+    /// # let html = "<html></html>";
+    ///
+    /// let readability = Readability::new(html, None, None).unwrap();
+    /// if let Some(article) = readability.parse() {
+    ///     let mut file = std::fs::File::create("article.html").unwrap();
+    ///     article.write_content(&mut file).unwrap();
+    /// }
+    /// ```
+    pub fn write_content<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        match &self.content {
+            Some(content) => writer.write_all(content.as_bytes()),
+            None => Ok(()),
+        }
+    }
+
+    /// Convert the cleaned content to Markdown.
+    ///
+    /// Returns `markdown_content` when the parse already produced it, and
+    /// otherwise converts `content` with the default [`MarkdownOptions`],
+    /// except that emphasis is written as `_text_`.
+    /// Links and images keep the URLs of `content`, which are absolute when a
+    /// base URL was given. Returns an empty string when there is no content.
+    ///
+    /// ```rust
+    /// use readabilityrs::{Readability, ReadabilityOptions};
+    ///
+    /// let html = r#"<html><body><article>
+    ///     <p>Grey herons hunt <strong>at dawn</strong>, standing perfectly still in the shallows.</p>
+    ///     <p>They strike at fish and frogs with a sudden thrust of the neck and beak.</p>
+    /// </article></body></html>"#;
+    /// let options = ReadabilityOptions::builder().char_threshold(50).build();
+    /// let article = Readability::new(html, None, Some(options))
+    ///     .unwrap()
+    ///     .parse()
+    ///     .unwrap();
+    /// assert!(article.to_markdown().contains("Grey herons hunt **at dawn**"));
+    /// ```
+    pub fn to_markdown(&self) -> String {
+        if let Some(markdown) = &self.markdown_content {
+            return markdown.clone();
+        }
+        let options = MarkdownOptions {
+            emphasis_delimiter: '_',
+            lang: self.lang.clone(),
+            ..MarkdownOptions::default()
+        };
+        self.to_markdown_with(&options)
+    }
+
+    /// Convert the cleaned content to Markdown with the given options.
+    ///
+    /// Always converts `content`, even when `markdown_content` is set.
+    pub fn to_markdown_with(&self, options: &MarkdownOptions) -> String {
+        let Some(content) = &self.content else {
+            return String::new();
+        };
+        let standardized = crate::elements::standardize_all(content, self.title.as_deref());
+        crate::markdown::html_to_markdown(&standardized, options)
+    }
+
+    /// Wrap the cleaned content in a standalone HTML document.
+    ///
+    /// The document has a `<!DOCTYPE html>`, a UTF-8 `<meta charset>`, the
+    /// title in `<title>`, the article's `lang` and `dir` on `<html>`, a
+    /// header with the title, byline and publication time, and
+    /// [`DOCUMENT_STYLESHEET`] inlined for readable typography. The header
+    /// leaves out the title when the content already opens with it as a
+    /// heading.
+    ///
+    /// ```rust
+    /// use readabilityrs::Article;
+    ///
+    /// let article = Article {
+    ///     title: Some("Herons".to_string()),
+    ///     content: Some("<p>Grey herons hunt at dawn.</p>".to_string()),
+    ///     ..Article::new()
+    /// };
+    /// let html = article.to_document();
+    /// assert!(html.starts_with("<!DOCTYPE html>"));
+    /// assert!(html.contains("<title>Herons</title>"));
+    /// ```
+    pub fn to_document(&self) -> String {
+        self.to_document_with(Some(DOCUMENT_STYLESHEET))
+    }
+
+    /// Wrap the cleaned content in a standalone HTML document with the given
+    /// stylesheet, or none.
+    ///
+    /// See [`Article::to_document`].
+    pub fn to_document_with(&self, stylesheet: Option<&str>) -> String {
+        let title = self.title.as_deref().unwrap_or_default();
+
+        let mut html = String::from("<!DOCTYPE html>\n<html");
+        for (name, value) in [("lang", &self.lang), ("dir", &self.dir)] {
+            if let Some(value) = value.as_deref().filter(|value| !value.is_empty()) {
+                html.push_str(&format!(" {name}=\"{}\"", escape(value)));
+            }
+        }
+        html.push_str(">\n<head>\n<meta charset=\"utf-8\">\n");
+        html.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n");
+        html.push_str(&format!("<title>{}</title>\n", escape(title)));
+        if let Some(stylesheet) = stylesheet {
+            html.push_str(&format!("<style>\n{stylesheet}\n</style>\n"));
+        }
+        html.push_str("</head>\n<body>\n<article>\n<header>\n");
+        let content = self.content.as_deref().unwrap_or_default();
+        if !title.is_empty() && !starts_with_title_heading(content, title) {
+            html.push_str(&format!("<h1>{}</h1>\n", escape(title)));
+        }
+        if let Some(byline) = self.byline.as_deref().filter(|byline| !byline.is_empty()) {
+            html.push_str(&format!("<p class=\"byline\">{}</p>\n", escape(byline)));
+        }
+        if let Some(time) = self
+            .published_time
+            .as_deref()
+            .filter(|time| !time.is_empty())
+        {
+            let time = escape(time);
+            html.push_str(&format!("<p><time datetime=\"{time}\">{time}</time></p>\n"));
+        }
+        html.push_str("</header>\n");
+        html.push_str(content);
+        html.push_str("\n</article>\n</body>\n</html>\n");
+        html
+    }
+}
+
+/// Ingredients and steps from a schema.org `Recipe` or `HowTo`.
+///
+/// For `HowTo` documents, `ingredients` holds the declared supplies.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct Recipe {
+    /// Ingredient lines from `recipeIngredient` (or `supply` for `HowTo`).
+    pub ingredients: Vec<String>,
+    /// Step texts from `recipeInstructions` (or `step` for `HowTo`), in order.
+    pub instructions: Vec<String>,
+}
+
+/// An image or video found in the article content.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MediaItem {
+    /// Whether this is an image or a video.
+    pub kind: MediaKind,
+    /// Source URL, resolved against the base URL when one was provided.
+    pub src: String,
+    /// The `alt` attribute. `Some("")` marks an image declared decorative,
+    /// while `None` means the attribute is missing.
+    pub alt: Option<String>,
+    /// Text of the enclosing `<figure>`'s `<figcaption>`.
+    pub caption: Option<String>,
+    /// Declared `width` attribute in pixels.
+    pub width: Option<u32>,
+    /// Declared `height` attribute in pixels.
+    pub height: Option<u32>,
+}
+
+/// Kind of a [`MediaItem`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MediaKind {
+    /// An `<img>`.
+    Image,
+    /// A `<video>` or an embedded player `<iframe>`.
+    Video,
+}
+
+/// Check whether the first text in `content` is an `<h1>` or `<h2>` reading `title`.
+fn starts_with_title_heading(content: &str, title: &str) -> bool {
+    let words = |text: &str| {
+        text.split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase()
+    };
+    let fragment = Html::parse_fragment(content);
+    for edge in fragment.root_element().traverse() {
+        let Edge::Open(node) = edge else {
+            continue;
+        };
+        if node
+            .value()
+            .as_text()
+            .is_some_and(|text| !text.trim().is_empty())
+        {
+            return false;
+        }
+        if let Some(element) = ElementRef::wrap(node) {
+            if matches!(element.value().name(), "h1" | "h2") {
+                return words(&element.text().collect::<String>()) == words(title);
+            }
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scraper::{Html, Selector};
+
+    #[test]
+    fn test_to_document() {
+        let article = Article {
+            title: Some("Herons & egrets".to_string()),
+            byline: Some("Jane Marsh".to_string()),
+            published_time: Some("2024-05-01".to_string()),
+            lang: Some("en".to_string()),
+            dir: Some("ltr".to_string()),
+            content: Some("<div><p>Grey herons hunt <em>at dawn</em>.</p></div>".to_string()),
+            ..Article::new()
+        };
+
+        let html = article.to_document();
+        assert!(html.starts_with("<!DOCTYPE html>\n<html lang=\"en\" dir=\"ltr\">"));
+        let document = Html::parse_document(&html);
+        assert!(document.errors.is_empty(), "{:?}", document.errors);
+        let text = |selector: &str| {
+            let selector = Selector::parse(selector).unwrap();
+            document
+                .select(&selector)
+                .next()
+                .map(|element| element.text().collect::<String>())
+        };
+        assert_eq!(text("title").as_deref(), Some("Herons & egrets"));
+        assert_eq!(
+            text("article header h1").as_deref(),
+            Some("Herons & egrets")
+        );
+        assert_eq!(text("header .byline").as_deref(), Some("Jane Marsh"));
+        assert_eq!(text("header time").as_deref(), Some("2024-05-01"));
+        assert_eq!(
+            text("article > div > p").as_deref(),
+            Some("Grey herons hunt at dawn.")
+        );
+        assert!(text("style").is_some());
+
+        let bare = article.to_document_with(None);
+        assert!(!bare.contains("<style>"));
+        assert!(Html::parse_document(&bare).errors.is_empty());
+
+        // A title heading already leading the content is not repeated.
+        let headed = Article {
+            content: Some(
+                "<div><h1>Herons &amp;  Egrets</h1><p>Grey herons.</p></div>".to_string(),
+            ),
+            ..article
+        };
+        let html = headed.to_document_with(None);
+        assert_eq!(html.matches("<h1>").count(), 1);
+        assert!(html.contains("<header>\n<p class=\"byline\">"));
+    }
 }
@@ -0,0 +1,305 @@
+//! Conversion of article HTML to a list of typed content blocks.
+//!
+//! [`html_to_blocks`] turns cleaned article content into [`Block`]s such as
+//! paragraphs, headings, lists and images, a portable representation for
+//! editors and native renderers that would rather not interpret HTML. Blocks
+//! serialize to JSON objects tagged by `type`.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use readabilityrs::blocks::{html_to_blocks, Block};
+//!
+//! let html = "<h2>Herons</h2><p>They hunt <em>at dawn</em>.</p>";
+//! assert_eq!(
+//!     html_to_blocks(html),
+//!     vec![
+//!         Block::Heading { level: 2, text: "Herons".to_string() },
+//!         Block::Paragraph { text: "They hunt at dawn.".to_string() },
+//!     ]
+//! );
+//! ```
+
+use crate::{dom_utils, utils};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use scraper::node::Node;
+use scraper::{ElementRef, Html, Selector};
+use serde::{Deserialize, Serialize};
+
+/// A block of article content.
+///
+/// Text is plain: inline markup such as links and emphasis is flattened and
+/// whitespace collapsed. Serialized with a `type` tag, e.g.
+/// `{"type":"heading","level":2,"text":"Herons"}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum Block {
+    /// A paragraph, or a run of text between other blocks.
+    Paragraph { text: String },
+    /// An `<h1>` to `<h6>` heading.
+    Heading { level: u8, text: String },
+    /// An `<ul>` or `<ol>` with the text of each item.
+    List { ordered: bool, items: Vec<String> },
+    /// An image, with the `<figcaption>` of its `<figure>` if any.
+    Image {
+        src: String,
+        alt: Option<String>,
+        caption: Option<String>,
+    },
+    /// A `<pre>` block, with its language from a `language-*` class.
+    Code {
+        language: Option<String>,
+        code: String,
+    },
+    /// A `<blockquote>`, its paragraphs separated by blank lines.
+    Quote { text: String },
+}
+
+impl Block {
+    /// The block's text, for blocks that are a single piece of text.
+    fn text(&self) -> Option<&str> {
+        match self {
+            Block::Paragraph { text } | Block::Heading { text, .. } | Block::Quote { text } => {
+                Some(text)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Convert article HTML to a list of blocks.
+///
+/// - `<p>`s, headings, lists, `<pre>`s and `<blockquote>`s map to their
+///   block type; text directly inside containers such as `<div>` or table
+///   cells becomes a paragraph.
+/// - `<img>`s become image blocks, taking the caption of their `<figure>`.
+///   Images without a `src` are left out.
+/// - Other containers are descended into.
+pub fn html_to_blocks(html: &str) -> Vec<Block> {
+    let doc = Html::parse_fragment(html);
+    let mut blocks = Vec::new();
+    let mut pending = String::new();
+    push_blocks(doc.root_element(), &mut blocks, &mut pending);
+    flush_paragraph(&mut pending, &mut blocks);
+    blocks
+}
+
+fn push_blocks(parent: ElementRef, blocks: &mut Vec<Block>, pending: &mut String) {
+    for child in parent.children() {
+        match child.value() {
+            Node::Text(text) => pending.push_str(&text.text),
+            Node::Element(_) => {
+                if let Some(element) = ElementRef::wrap(child) {
+                    push_element(element, blocks, pending);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn push_element(element: ElementRef, blocks: &mut Vec<Block>, pending: &mut String) {
+    let name = element.value().name();
+    match name {
+        "br" => pending.push(' '),
+        "img" => {
+            flush_paragraph(pending, blocks);
+            blocks.extend(image_block(element, None));
+        }
+        _ if dom_utils::is_phrasing_content(element) => push_blocks(element, blocks, pending),
+        _ => {
+            flush_paragraph(pending, blocks);
+            push_block(element, blocks);
+            flush_paragraph(pending, blocks);
+        }
+    }
+}
+
+fn push_block(element: ElementRef, blocks: &mut Vec<Block>) {
+    static IMG_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("img").unwrap());
+    static FIGCAPTION_SELECTOR: Lazy<Selector> =
+        Lazy::new(|| Selector::parse("figcaption").unwrap());
+
+    let name = element.value().name();
+    match name {
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+            let text = element_text(element);
+            if !text.is_empty() {
+                let level = name[1..].parse().unwrap_or(1);
+                blocks.push(Block::Heading { level, text });
+            }
+        }
+        "ul" | "ol" => {
+            let items: Vec<String> = element
+                .children()
+                .filter_map(ElementRef::wrap)
+                .filter(|child| child.value().name() == "li")
+                .map(element_text)
+                .filter(|item| !item.is_empty())
+                .collect();
+            if !items.is_empty() {
+                blocks.push(Block::List {
+                    ordered: name == "ol",
+                    items,
+                });
+            }
+        }
+        "pre" => {
+            let code = element.text().collect::<String>();
+            if !code.trim().is_empty() {
+                blocks.push(Block::Code {
+                    language: code_language(element),
+                    code: code.trim_matches('\n').to_string(),
+                });
+            }
+        }
+        "blockquote" => {
+            let text = html_to_blocks(&element.inner_html())
+                .iter()
+                .filter_map(Block::text)
+                .collect::<Vec<_>>()
+                .join("\n\n");
+            if !text.is_empty() {
+                blocks.push(Block::Quote { text });
+            }
+        }
+        "figure" => match element.select(&IMG_SELECTOR).next() {
+            Some(img) => {
+                let caption = element
+                    .select(&FIGCAPTION_SELECTOR)
+                    .next()
+                    .map(element_text)
+                    .filter(|caption| !caption.is_empty());
+                blocks.extend(image_block(img, caption));
+            }
+            None => push_container(element, blocks),
+        },
+        "script" | "style" | "template" | "noscript" => {}
+        _ => push_container(element, blocks),
+    }
+}
+
+/// Push the blocks inside a container, with its loose text as paragraphs.
+fn push_container(element: ElementRef, blocks: &mut Vec<Block>) {
+    let mut pending = String::new();
+    push_blocks(element, blocks, &mut pending);
+    flush_paragraph(&mut pending, blocks);
+}
+
+fn flush_paragraph(pending: &mut String, blocks: &mut Vec<Block>) {
+    let text = collapse_whitespace(pending);
+    if !text.is_empty() {
+        blocks.push(Block::Paragraph { text });
+    }
+    pending.clear();
+}
+
+fn image_block(img: ElementRef, caption: Option<String>) -> Option<Block> {
+    let src = img.value().attr("src")?.trim();
+    if src.is_empty() {
+        return None;
+    }
+    Some(Block::Image {
+        src: src.to_string(),
+        alt: img.value().attr("alt").map(str::to_string),
+        caption,
+    })
+}
+
+/// Language of a code block from a `language-*` or `lang-*` class on the
+/// `<pre>` or its `<code>`.
+fn code_language(pre: ElementRef) -> Option<String> {
+    static LANGUAGE_CLASS_REGEX: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"(?i)^(?:language|lang)-(.+)$").unwrap());
+
+    let code = pre
+        .children()
+        .filter_map(ElementRef::wrap)
+        .find(|child| child.value().name() == "code");
+    [Some(pre), code]
+        .into_iter()
+        .flatten()
+        .flat_map(|element| element.value().classes())
+        .find_map(|class| LANGUAGE_CLASS_REGEX.captures(class))
+        .map(|captures| captures[1].to_lowercase())
+}
+
+fn element_text(element: ElementRef) -> String {
+    collapse_whitespace(&element.text().collect::<String>())
+}
+
+/// Join the words of `text` with single spaces, as HTML renders them.
+fn collapse_whitespace(text: &str) -> String {
+    utils::replace_nbsp(text)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_html_to_blocks() {
+        let html = r#"<div class="page"><h1>Herons</h1>
+<p>Grey herons hunt <a href="/dawn">at dawn</a>,
+standing still.</p>
+<figure><img src="heron.jpg" alt="A heron"><figcaption>A heron at dawn.</figcaption></figure>
+<ol><li>Wait</li><li>Strike</li></ol>
+<div>Loose text <img src="nest.jpg"> after an image.</div>
+<blockquote><p>Patience.</p><p>Then speed.</p></blockquote>
+<pre><code class="language-rust">fn main() {}
+</code></pre></div>"#;
+
+        assert_eq!(
+            html_to_blocks(html),
+            vec![
+                Block::Heading {
+                    level: 1,
+                    text: "Herons".to_string()
+                },
+                Block::Paragraph {
+                    text: "Grey herons hunt at dawn, standing still.".to_string()
+                },
+                Block::Image {
+                    src: "heron.jpg".to_string(),
+                    alt: Some("A heron".to_string()),
+                    caption: Some("A heron at dawn.".to_string())
+                },
+                Block::List {
+                    ordered: true,
+                    items: vec!["Wait".to_string(), "Strike".to_string()]
+                },
+                Block::Paragraph {
+                    text: "Loose text".to_string()
+                },
+                Block::Image {
+                    src: "nest.jpg".to_string(),
+                    alt: None,
+                    caption: None
+                },
+                Block::Paragraph {
+                    text: "after an image.".to_string()
+                },
+                Block::Quote {
+                    text: "Patience.\n\nThen speed.".to_string()
+                },
+                Block::Code {
+                    language: Some("rust".to_string()),
+                    code: "fn main() {}".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_blocks_serialize_with_type_tag() {
+        let blocks = html_to_blocks("<h2>Nesting</h2><ul><li>Trees</li></ul>");
+        assert_eq!(
+            serde_json::to_string(&blocks).unwrap(),
+            r#"[{"type":"heading","level":2,"text":"Nesting"},{"type":"list","ordered":false,"items":["Trees"]}]"#
+        );
+    }
+}
@@ -1,12 +1,20 @@
 //! Content cleaning and post-processing functions.
 
-use crate::constants::REGEXPS;
+use crate::article::Recipe;
+use crate::constants::{PHRASING_ELEMS, REGEXPS};
+use crate::dom_utils;
 use crate::error::Result;
+use ego_tree::iter::Edge;
 use ego_tree::NodeId;
 use once_cell::sync::Lazy;
 use regex::{Captures, Regex};
+use scraper::node::Attributes;
 use scraper::{ElementRef, Html, Node as ScraperNode, Selector};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+
+/// Attribute carrying the original source line of an element when
+/// `ReadabilityOptions::annotate_source` is enabled.
+pub const SOURCE_LINE_ATTR: &str = "data-src-line";
 
 /// Clean and post-process extracted article content (light version)
 ///
@@ -32,17 +40,121 @@ pub fn clean_article_content_light(html: &str, base_url: Option<&str>) -> Result
 /// - Fixes relative URLs to absolute
 /// - Cleans up empty elements
 /// - Normalizes whitespace
-pub fn clean_article_content(html: &str, base_url: Option<&str>) -> Result<String> {
+///
+/// Embeds whose attributes match `video_regex` are treated as allowed videos
+/// and protect their container from conditional cleaning, as do lists holding
+/// the ingredients or steps of `recipe` and elements whose class or id is
+/// listed in `content_containers`. Without `clean_conditionally`, conditional
+/// cleaning is skipped altogether.
+pub fn clean_article_content(
+    html: &str,
+    base_url: Option<&str>,
+    video_regex: &Regex,
+    recipe: Option<&Recipe>,
+    content_containers: &[String],
+    clean_conditionally: bool,
+) -> Result<String> {
     let mut result = clean_article_content_light(html, base_url)?;
-    result = remove_conditionally(&result);
+    if clean_conditionally {
+        result = remove_conditionally(&result, video_regex, recipe, content_containers);
+    }
+    result = strip_internal_attributes(&result);
     Ok(result)
 }
 
-/// Fix relative URLs in HTML string using regex
-fn fix_relative_urls_in_html(html: &str, _base_url: &str) -> String {
-    // For now, just return as-is
-    // TODO: Implement proper URL fixing without re-parsing the entire tree
-    html.to_string()
+/// Remove `data-readability` hints and `data-readability-*` marker attributes
+/// from serialized HTML.
+///
+/// Data tables are tracked by node id during cleaning, but pages saved from
+/// other Readability implementations can still carry markers such as
+/// `data-readability-datatable`. These and the extraction hints are
+/// bookkeeping, never part of the article, so they are dropped whatever the
+/// attribute settings.
+fn strip_internal_attributes(html: &str) -> String {
+    static MARKER_ATTR_REGEX: Lazy<Regex> = Lazy::new(|| {
+        Regex::new(r#"(?i)(<[a-z][a-z0-9-]*\b[^>]*?)\s+data-readability(?:-[a-z0-9-]+)?(?:\s*=\s*(?:"[^"]*"|'[^']*'|[^\s>]+))?"#)
+            .unwrap()
+    });
+
+    let mut result = html.to_string();
+    while MARKER_ATTR_REGEX.is_match(&result) {
+        result = MARKER_ATTR_REGEX.replace_all(&result, "$1").to_string();
+    }
+    result
+}
+
+/// Resolve relative URLs in `href`, `src`, `poster` and `srcset` attributes
+///
+/// Root-relative (`/x`), path-relative (`../x`) and protocol-relative
+/// (`//cdn...`) URLs are resolved against `base_url`. Absolute URLs, in-page
+/// `#fragment` links and `data:`, `mailto:` or `javascript:` URLs are left
+/// alone, as is everything when `base_url` is not a valid URL.
+fn fix_relative_urls_in_html(html: &str, base_url: &str) -> String {
+    let Ok(base) = url::Url::parse(base_url) else {
+        return html.to_string();
+    };
+
+    let mut doc = Html::parse_fragment(html);
+    let elements: Vec<NodeId> = doc
+        .root_element()
+        .descendants()
+        .filter(|node| node.value().is_element())
+        .map(|node| node.id())
+        .collect();
+    let mut changed = false;
+    for id in elements {
+        let Some(mut node) = doc.tree.get_mut(id) else {
+            continue;
+        };
+        let ScraperNode::Element(element) = node.value() else {
+            continue;
+        };
+        for (name, value) in element.attrs.iter_mut() {
+            let resolved = match name.local.as_ref() {
+                "href" | "src" | "poster" => resolve_relative_url(&base, value),
+                "srcset" => resolve_srcset(&base, value),
+                _ => None,
+            };
+            if let Some(resolved) = resolved {
+                *value = resolved.as_str().into();
+                changed = true;
+            }
+        }
+    }
+    if !changed {
+        return html.to_string();
+    }
+    doc.root_element().inner_html()
+}
+
+/// Resolve `url` against `base`, or `None` when it should stay as written.
+fn resolve_relative_url(base: &url::Url, url: &str) -> Option<String> {
+    let url = url.trim();
+    // A URL that parses on its own has a scheme: absolute, `data:`, `mailto:`...
+    if url.is_empty() || url.starts_with('#') || url::Url::parse(url).is_ok() {
+        return None;
+    }
+    base.join(url).ok().map(String::from)
+}
+
+/// Resolve the URL of every candidate in a `srcset`, keeping descriptors.
+fn resolve_srcset(base: &url::Url, srcset: &str) -> Option<String> {
+    static SRCSET_CANDIDATE_REGEX: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"(\S+)(\s+[\d.]+[xw])?(\s*(?:,|$))").unwrap());
+
+    let mut changed = false;
+    let resolved = SRCSET_CANDIDATE_REGEX.replace_all(srcset, |caps: &Captures| {
+        let url = match resolve_relative_url(base, &caps[1]) {
+            Some(url) => {
+                changed = true;
+                url
+            }
+            None => caps[1].to_string(),
+        };
+        let descriptor = caps.get(2).map_or("", |descriptor| descriptor.as_str());
+        format!("{url}{descriptor}{}", &caps[3])
+    });
+    changed.then(|| resolved.into_owned())
 }
 
 /// Remove nav-like sections using lightweight regex patterns.
@@ -78,11 +190,22 @@ fn remove_nav_like_sections(html: &str) -> String {
     result
 }
 
-fn remove_conditionally(html: &str) -> String {
-    remove_conditionally_dom(html).unwrap_or_else(|| remove_conditionally_regex(html))
+fn remove_conditionally(
+    html: &str,
+    video_regex: &Regex,
+    recipe: Option<&Recipe>,
+    content_containers: &[String],
+) -> String {
+    remove_conditionally_dom(html, video_regex, recipe, content_containers)
+        .unwrap_or_else(|| remove_conditionally_regex(html))
 }
 
-fn remove_conditionally_dom(html: &str) -> Option<String> {
+fn remove_conditionally_dom(
+    html: &str,
+    video_regex: &Regex,
+    recipe: Option<&Recipe>,
+    content_containers: &[String],
+) -> Option<String> {
     let mut doc = Html::parse_document(html);
 
     let body_id = doc.select(&BODY_SELECTOR).next().map(|e| e.id());
@@ -90,9 +213,13 @@ fn remove_conditionally_dom(html: &str) -> Option<String> {
 
     let root_el = ElementRef::wrap(doc.tree.get(root_id)?)?;
     let marks = mark_data_tables(root_el);
+    let targets = find_link_targets(root_el);
 
     for tag in ["form", "fieldset", "table", "ul", "ol", "div", "section"] {
-        clean_conditionally_tag(&mut doc, root_id, tag, &marks);
+        clean_conditionally_tag(&mut doc, root_id, tag, |el| {
+            !is_content_container(el, content_containers)
+                && should_remove_dom_node(el, tag, &marks, &targets, video_regex, recipe)
+        });
     }
 
     let serialized = if body_id.is_some() {
@@ -293,7 +420,9 @@ fn compute_fragment_stats(fragment: &str) -> FragmentStats {
     let mut link_length = 0;
     if let Some(node) = wrapper.as_ref() {
         for link in node.select(&LINK_SELECTOR) {
-            link_length += link.text().collect::<String>().len();
+            if !dom_utils::is_nested_link(link, *node) && !dom_utils::is_block_wrapping_link(link) {
+                link_length += link.text().collect::<String>().len();
+            }
         }
     }
 
@@ -324,7 +453,7 @@ fn compute_fragment_stats(fragment: &str) -> FragmentStats {
         link_density: if text_len == 0 {
             1.0
         } else {
-            link_length as f64 / text_len as f64
+            (link_length as f64 / text_len as f64).min(1.0)
         },
         counts,
         comma_count,
@@ -365,78 +494,88 @@ fn extract_class_and_id(fragment: &str) -> String {
     format!("{class} {id}").trim().to_string()
 }
 
-/// Replace consecutive BR tags with paragraph tags
-///
-/// This converts content like:
-/// ```html
-/// <div>Text line 1<br><br>Text line 2</div>
-/// ```
-/// Into:
-/// ```html
-/// <div><p>Text line 1</p><p>Text line 2</p></div>
-/// ```
-///
-/// This matches Mozilla's Readability _replaceBrs function
-pub fn replace_brs(html: &str) -> String {
-    let trimmed = html.trim();
-
-    if trimmed.starts_with('<') && trimmed.ends_with('>') {
-        if let Some((tag_name, attributes, inner_content, closing_tag)) = parse_element(trimmed) {
-            if closing_tag == tag_name {
-                let processed_inner = replace_brs_in_content(inner_content);
-                if attributes.is_empty() {
-                    return format!("<{tag_name}>{processed_inner}</{tag_name}>");
-                } else {
-                    return format!("<{tag_name}{attributes}>{processed_inner}</{tag_name}>");
-                }
-            }
-        }
-    }
+/// Elements whose content the parser reads as raw text up to the closing tag.
+const RAW_TEXT_TAGS: [&str; 8] = [
+    "title", "textarea", "style", "script", "xmp", "iframe", "noembed", "noframes",
+];
 
-    replace_brs_in_content(trimmed)
-}
-
-/// Parse an HTML element into (tag_name, attributes, inner_content, closing_tag)
-fn parse_element(html: &str) -> Option<(&str, &str, &str, &str)> {
-    let opening_end = html.find('>')?;
-    let opening_tag = &html[1..opening_end];
-
-    let (tag_name, attributes) = if let Some(space_pos) = opening_tag.find(char::is_whitespace) {
-        let tag = &opening_tag[..space_pos];
-        let attrs = &opening_tag[space_pos..];
-        (tag, attrs)
-    } else {
-        (opening_tag, "")
+/// Check whether a parsed document has any content in its body
+///
+/// Text inside raw-text elements such as an unclosed `<textarea>` or `<title>`
+/// does not count, since that is where malformed markup ends up.
+pub fn has_body_content(document: &Html) -> bool {
+    let Some(body) = document.select(&BODY_SELECTOR).next() else {
+        return false;
     };
 
-    let closing_tag_pattern = format!("</{tag_name}>");
-    let closing_start = html.rfind(&closing_tag_pattern)?;
-    let inner_content = &html[opening_end + 1..closing_start];
-    let closing_tag_name = tag_name;
-
-    Some((tag_name, attributes, inner_content, closing_tag_name))
+    body.descendants().skip(1).any(|node| match node.value() {
+        ScraperNode::Element(element) => !RAW_TEXT_TAGS.contains(&element.name()),
+        ScraperNode::Text(text) => {
+            !text.trim().is_empty()
+                && node
+                    .parent()
+                    .and_then(ElementRef::wrap)
+                    .is_none_or(|parent| !RAW_TEXT_TAGS.contains(&parent.value().name()))
+        }
+        _ => false,
+    })
 }
 
-/// Replace BRs in text/content (no wrapping element)
-fn replace_brs_in_content(content: &str) -> String {
-    let br_regex = regex::Regex::new(r"(?i)(<br\s*/?>(\s|&nbsp;?)*){2,}").unwrap();
-    if !br_regex.is_match(content) {
-        return content.to_string();
+/// Check whether raw markup has any text once its tags are removed
+///
+/// Comments and the contents of properly closed `<head>`, `<template>`,
+/// `<noscript>` and raw-text elements are not counted, so a page whose body
+/// holds only scripts or a title is not mistaken for swallowed content.
+pub fn has_text_outside_tags(html: &str) -> bool {
+    static TAG_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"<[^>]*>").unwrap());
+    static NON_CONTENT_REGEXES: Lazy<Vec<Regex>> = Lazy::new(|| {
+        let mut regexes = vec![Regex::new(r"(?s)<!--.*?-->").unwrap()];
+        regexes.extend(
+            ["head", "template", "noscript"]
+                .iter()
+                .chain(RAW_TEXT_TAGS.iter())
+                .map(|tag| Regex::new(&format!(r"(?is)<{tag}\b[^>]*>.*?</{tag}\s*>")).unwrap()),
+        );
+        regexes
+    });
+
+    let mut html = html.to_string();
+    for regex in NON_CONTENT_REGEXES.iter() {
+        html = regex.replace_all(&html, "").into_owned();
     }
+    TAG_REGEX.split(&html).any(|text| !text.trim().is_empty())
+}
 
-    let parts: Vec<&str> = br_regex.split(content).collect();
-    let paragraphs: Vec<String> = parts
-        .iter()
-        .map(|p| p.trim())
-        .filter(|p| !p.is_empty())
-        .map(|p| format!("<p>{p}</p>"))
-        .collect();
+/// Repair markup that makes the parser swallow the rest of the document
+///
+/// An unterminated `<!--` turns everything after it into a comment, and an
+/// unclosed raw-text element (`<title>`, `<textarea>`, `<script>`, ...) turns
+/// it into that element's text, leaving an empty body. Such openers are
+/// dropped so the markup after them parses as elements again.
+pub fn sanitize_malformed_html(html: &str) -> String {
+    let mut html = html.to_string();
 
-    if paragraphs.is_empty() {
-        String::new()
-    } else {
-        paragraphs.join("\n    ")
+    while let Some(start) = html.rfind("<!--") {
+        if html[start..].contains("-->") {
+            break;
+        }
+        html.replace_range(start..start + 4, "");
+    }
+
+    for tag in RAW_TEXT_TAGS {
+        let opener = Regex::new(&format!(r"(?i)<{tag}\b[^>]*>")).unwrap();
+        let last_closer = html.to_ascii_lowercase().rfind(&format!("</{tag}"));
+        let unclosed: Vec<(usize, usize)> = opener
+            .find_iter(&html)
+            .filter(|open| last_closer.is_none_or(|close| close < open.end()))
+            .map(|open| (open.start(), open.end()))
+            .collect();
+        for (start, end) in unclosed.into_iter().rev() {
+            html.replace_range(start..end, "");
+        }
     }
+
+    html
 }
 
 /// Prepare document for readability processing
@@ -444,6 +583,9 @@ fn replace_brs_in_content(content: &str) -> String {
 /// This function implements Mozilla's _prepDocument functionality:
 /// - Remove script and style elements
 /// - Replace font tags with span
+/// - Drop elements marked `data-readability="remove"`
+/// - Map deprecated center/tt/strike/big tags to modern equivalents
+/// - Turn line-numbered code tables into `<pre><code>` blocks
 /// - Unwrap noscript tags to reveal lazy-loaded images
 /// - Remove form elements
 ///
@@ -463,6 +605,18 @@ pub fn prep_document(html: &str) -> String {
     let font_close_regex = regex::Regex::new(r"</font>").unwrap();
     html = font_close_regex.replace_all(&html, "</span>").to_string();
 
+    html = normalize_br_tags(&html);
+
+    html = remove_hinted_nodes(&html);
+
+    html = flatten_wrapper_chains(&html);
+
+    html = reveal_hidden_tab_panels(&html);
+
+    html = replace_deprecated_tags(&html);
+
+    html = convert_code_listing_tables(&html);
+
     let noscript_regex = regex::Regex::new(r"(?is)<noscript\b[^>]*>(.*?)</noscript>").unwrap();
     html = noscript_regex
         .replace_all(&html, |caps: &regex::Captures| {
@@ -481,6 +635,726 @@ pub fn prep_document(html: &str) -> String {
     html
 }
 
+/// Promote the content cell of a table-based (email/newsletter) layout
+///
+/// Newsletter HTML nests the whole page in layout tables: a preheader, a logo
+/// row, the body cell, and an unsubscribe footer. When nearly all body text
+/// lives in such tables, this descends from the outermost cells into whichever
+/// cell holds most of the text, stopping once a neighbouring cell carries
+/// headings or paragraphs of its own. That cell's content replaces the body, with its own layout tables
+/// turned into `<div>`s so they score like ordinary blocks.
+///
+/// Pages with semantic `<article>`/`<main>` markup or data tables at the top
+/// level are returned unchanged.
+pub fn promote_layout_table_content(html: &str) -> String {
+    const MIN_BODY_TEXT: usize = 200;
+    const TABLE_TEXT_SHARE: f64 = 0.9;
+    const DOMINANT_CELL_SHARE: f64 = 0.75;
+
+    static PROSE_SELECTOR: Lazy<Selector> =
+        Lazy::new(|| Selector::parse("p, h1, h2, h3, h4, h5, h6").unwrap());
+    static SEMANTIC_SELECTOR: Lazy<Selector> =
+        Lazy::new(|| Selector::parse("article, main, [role='main']").unwrap());
+    static HEAD_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("head").unwrap());
+    static TABLE_TAG_REGEX: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"(?i)<(/?)(?:table|tbody|thead|tfoot|tr|td|th)\b").unwrap());
+
+    let doc = Html::parse_document(html);
+    let Some(body) = doc.select(&BODY_SELECTOR).next() else {
+        return html.to_string();
+    };
+    if body.select(&SEMANTIC_SELECTOR).next().is_some() {
+        return html.to_string();
+    }
+
+    let text_len = |el: ElementRef| dom_inner_text(el).trim().len();
+    let body_len = text_len(body);
+    if body_len < MIN_BODY_TEXT {
+        return html.to_string();
+    }
+
+    let top_tables: Vec<ElementRef> = body
+        .select(&TABLE_SELECTOR)
+        .filter(|t| !has_ancestor(*t, is_table))
+        .collect();
+    if top_tables.is_empty() || top_tables.iter().any(|t| detect_data_table(*t)) {
+        return html.to_string();
+    }
+    let table_len: usize = top_tables.iter().map(|t| text_len(*t)).sum();
+    if (table_len as f64) < body_len as f64 * TABLE_TEXT_SHARE {
+        return html.to_string();
+    }
+
+    let mut dominant: Option<ElementRef> = None;
+    let mut current = body;
+    let mut current_len = body_len;
+    loop {
+        let cells = direct_cells(current);
+        let Some(cell) = cells
+            .iter()
+            .copied()
+            .find(|td| text_len(*td) as f64 >= current_len as f64 * DOMINANT_CELL_SHARE)
+        else {
+            break;
+        };
+        // Only chrome (links, logos, legal text) may sit beside the content
+        // cell; a sibling with its own headings or paragraphs is more content.
+        let sibling_has_prose = cells
+            .iter()
+            .filter(|td| td.id() != cell.id())
+            .any(|td| td.select(&PROSE_SELECTOR).next().is_some());
+        if sibling_has_prose {
+            break;
+        }
+        dominant = Some(cell);
+        current = cell;
+        current_len = text_len(cell);
+    }
+    let Some(cell) = dominant else {
+        return html.to_string();
+    };
+
+    let mut content = cell.inner_html();
+    if !cell.select(&TABLE_SELECTOR).any(detect_data_table) {
+        content = TABLE_TAG_REGEX.replace_all(&content, "<${1}div").to_string();
+    }
+
+    let head = doc
+        .select(&HEAD_SELECTOR)
+        .next()
+        .map(|h| h.html())
+        .unwrap_or_default();
+    // Keep every attribute of the original root so `lang`, `dir` and any
+    // data attributes survive the rebuild.
+    let html_attrs: String = doc
+        .root_element()
+        .value()
+        .attrs()
+        .map(|(name, value)| {
+            format!(
+                r#" {}="{}""#,
+                name,
+                value.replace('&', "&amp;").replace('"', "&quot;")
+            )
+        })
+        .collect();
+
+    format!("<html{html_attrs}>{head}<body><div>{content}</div></body></html>")
+}
+
+/// Cells whose nearest enclosing cell is `parent` (or that have none when
+/// `parent` is not itself a cell).
+fn direct_cells(parent: ElementRef) -> Vec<ElementRef> {
+    static TD_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("td").unwrap());
+
+    parent
+        .select(&TD_SELECTOR)
+        .filter(|td| {
+            let mut current = td.parent();
+            while let Some(node) = current {
+                if node.id() == parent.id() {
+                    return true;
+                }
+                if ElementRef::wrap(node).is_some_and(|el| node_has_tag(el, "td")) {
+                    return false;
+                }
+                current = node.parent();
+            }
+            false
+        })
+        .collect()
+}
+
+/// Remove elements the page marks as noise with `data-readability="remove"`
+///
+/// Extraction hints give page authors (or a pre-processor) a deterministic way
+/// to correct the heuristics. Marked nodes are detached before any scoring; the
+/// matching `data-readability="content"` hint is honored by `grab_article`.
+fn remove_hinted_nodes(html: &str) -> String {
+    static REMOVE_HINT_SELECTOR: Lazy<Selector> =
+        Lazy::new(|| Selector::parse(r#"[data-readability="remove"]"#).unwrap());
+
+    if !html.contains("data-readability") {
+        return html.to_string();
+    }
+
+    let mut doc = Html::parse_document(html);
+    let to_detach: Vec<NodeId> = doc.select(&REMOVE_HINT_SELECTOR).map(|el| el.id()).collect();
+    if to_detach.is_empty() {
+        return html.to_string();
+    }
+
+    for id in to_detach {
+        if let Some(mut node) = doc.tree.get_mut(id) {
+            node.detach();
+        }
+    }
+    doc.html()
+}
+
+/// Remove the recirculation, newsletter and advertising blocks of news pages
+///
+/// News templates interleave the story with "related" and "most read" lists,
+/// newsletter signups and ad slots, which split the body into several
+/// sections and pull scoring toward link lists. Elements are matched by class
+/// or id, by `data-ad-slot`/`data-ad-unit` attributes, and "Read more:" style
+/// paragraphs that are mostly a link. Elements holding more prose than such a
+/// block would are kept, as are `<main>`, `<article>` and their ancestors.
+pub fn remove_news_clutter(html: &str) -> String {
+    static NEWS_CLUTTER_REGEX: Lazy<Regex> = Lazy::new(|| {
+        Regex::new(
+            r"(?i)(?:^|[\s_-])(?:related|recirc(?:ulation)?|more-?(?:stories|news|from)|read-?(?:more|next)|recommended|most-?(?:read|popular|viewed)|trending|newsletter|sign-?up|subscribe|ad|ads|advert(?:isement)?s?|adsbygoogle|gpt-ad|dfp|taboola|outbrain|sponsored|promo)(?:$|[\s_-])",
+        )
+        .unwrap()
+    });
+    static RECIRCULATION_LEAD_REGEX: Lazy<Regex> = Lazy::new(|| {
+        Regex::new(r"(?i)^(?:read (?:more|next|also)|also read|related|more from|recommended)\s*:")
+            .unwrap()
+    });
+
+    let mut doc = Html::parse_document(html);
+    let clutter: Vec<NodeId> = doc
+        .root_element()
+        .descendants()
+        .filter_map(ElementRef::wrap)
+        .filter(|el| {
+            let elem = el.value();
+            if matches!(elem.name(), "html" | "body" | "main" | "article") {
+                return false;
+            }
+            let is_clutter = NEWS_CLUTTER_REGEX.is_match(elem.attr("class").unwrap_or(""))
+                || NEWS_CLUTTER_REGEX.is_match(elem.id().unwrap_or(""))
+                || elem.attr("data-ad-slot").is_some()
+                || elem.attr("data-ad-unit").is_some()
+                || (elem.name() == "p"
+                    && RECIRCULATION_LEAD_REGEX.is_match(&dom_utils::get_inner_text(*el, true))
+                    && dom_utils::get_link_density(*el) > 0.5);
+            is_clutter
+                && !crate::post_processor::holds_article_text(*el)
+                && !el
+                    .descendants()
+                    .filter_map(ElementRef::wrap)
+                    .any(|descendant| matches!(descendant.value().name(), "main" | "article"))
+        })
+        .map(|el| el.id())
+        .collect();
+    if clutter.is_empty() {
+        return html.to_string();
+    }
+
+    for id in clutter {
+        if let Some(mut node) = doc.tree.get_mut(id) {
+            node.detach();
+        }
+    }
+    doc.html()
+}
+
+/// Longest text, in characters, a block can hold to be taken for a consent banner.
+const CONSENT_BANNER_MAX_LENGTH: usize = 600;
+
+/// How much page text may precede or follow a consent banner.
+const CONSENT_BANNER_MAX_OFFSET: usize = 1000;
+
+/// Remove cookie-consent and GDPR banners found by their content
+///
+/// A banner is the innermost block of at most `CONSENT_BANNER_MAX_LENGTH`
+/// characters that mentions cookies or consent (`REGEXPS.consent_words`) and
+/// holds both an accept control and a reject or settings control, so the
+/// wrappers around it and anything else they hold are left alone. Only
+/// buttons count as controls: `<button>`, `role="button"` and button inputs,
+/// never plain links, which articles about privacy routinely contain. It
+/// must sit near the top of the page, or at its very end where overlay
+/// banners are usually appended, within `CONSENT_BANNER_MAX_OFFSET`
+/// characters of text. Class names and ids are not consulted, so banners
+/// without any markup hints are caught too.
+pub fn remove_consent_banners(html: &str) -> String {
+    static ACCEPT_REGEX: Lazy<Regex> = Lazy::new(|| {
+        Regex::new(r"(?i)^(?:accept|allow|agree|i agree|got it|ok(?:ay)?)\b").unwrap()
+    });
+    static REJECT_OR_SETTINGS_REGEX: Lazy<Regex> = Lazy::new(|| {
+        Regex::new(
+            r"(?i)^(?:reject|decline|deny|refuse|continue without|manage|customi[sz]e|(?:cookie )?settings|preferences)\b",
+        )
+        .unwrap()
+    });
+
+    let text_length = |text: &str| text.chars().filter(|c| !c.is_whitespace()).count();
+    let button_label = |el: ElementRef| {
+        let elem = el.value();
+        let label = match elem.name() {
+            "input" => match elem.attr("type") {
+                Some("button" | "submit") => elem.attr("value").unwrap_or("").to_string(),
+                _ => return None,
+            },
+            "button" => el.text().collect(),
+            _ if elem.attr("role") == Some("button") => el.text().collect(),
+            _ => return None,
+        };
+        let label = label.trim().to_string();
+        (label.len() < 40).then_some(label)
+    };
+    let has_consent_controls = |el: ElementRef| {
+        let labels: Vec<String> = el
+            .descendants()
+            .filter_map(ElementRef::wrap)
+            .filter_map(button_label)
+            .collect();
+        labels.iter().any(|label| ACCEPT_REGEX.is_match(label))
+            && labels
+                .iter()
+                .any(|label| REJECT_OR_SETTINGS_REGEX.is_match(label))
+    };
+
+    let mut doc = Html::parse_document(html);
+    let total_length = text_length(&doc.root_element().text().collect::<String>());
+    let mut preceding_length = 0;
+    let mut banners: Vec<NodeId> = Vec::new();
+    for edge in doc.root_element().traverse() {
+        let node = match edge {
+            Edge::Open(node) => node,
+            Edge::Close(_) => continue,
+        };
+        if let ScraperNode::Text(text) = node.value() {
+            preceding_length += text_length(text);
+            continue;
+        }
+        let Some(el) = ElementRef::wrap(node) else {
+            continue;
+        };
+        if matches!(el.value().name(), "html" | "body" | "main" | "article") {
+            continue;
+        }
+        let text: String = el.text().collect();
+        let length = text_length(&text);
+        let following_length = total_length.saturating_sub(preceding_length + length);
+        if length <= CONSENT_BANNER_MAX_LENGTH
+            && preceding_length.min(following_length) <= CONSENT_BANNER_MAX_OFFSET
+            && REGEXPS.consent_words.is_match(&text)
+            && has_consent_controls(el)
+            && !el
+                .descendants()
+                .filter_map(ElementRef::wrap)
+                .any(|descendant| matches!(descendant.value().name(), "main" | "article"))
+        {
+            banners.retain(|&banner| !el.ancestors().any(|ancestor| ancestor.id() == banner));
+            banners.push(el.id());
+        }
+    }
+    if banners.is_empty() {
+        return html.to_string();
+    }
+
+    for id in banners {
+        if let Some(mut node) = doc.tree.get_mut(id) {
+            node.detach();
+        }
+    }
+    doc.html()
+}
+
+/// Wrapper chains longer than this are treated as unclosed-tag recovery.
+const MAX_WRAPPER_CHAIN: usize = 5;
+
+/// Collapse abnormally deep chains of single-child `<div>` wrappers
+///
+/// When hand-written HTML leaves `<div>`s unclosed, html5ever nests everything
+/// after them, burying the article under a long chain of wrappers that hold
+/// nothing but the next wrapper. Such chains push the article's container past
+/// the reach of ancestor score propagation and end up wrapping the extracted
+/// content. Only documents the parser had to recover from unclosed elements
+/// are touched; there, chains of more than `MAX_WRAPPER_CHAIN` wrappers are
+/// collapsed into their outermost `<div>`, which takes the classes and first
+/// `id` of the wrappers removed. A wrapper with any other attribute, such as
+/// `itemprop`, ends the chain. Shorter layout chains are left alone.
+fn flatten_wrapper_chains(html: &str) -> String {
+    static OPEN_DIV_RUN_REGEX: Lazy<Regex> = Lazy::new(|| {
+        Regex::new(&format!(
+            r"(?i)(?:<div\b[^>]*>\s*){{{}}}",
+            MAX_WRAPPER_CHAIN + 1
+        ))
+        .unwrap()
+    });
+    const UNCLOSED_ELEMENTS_ERROR: &str = "Unexpected open tag at end of body";
+
+    // A chain shows up in the source as a run of opening tags.
+    if !OPEN_DIV_RUN_REGEX.is_match(html) {
+        return html.to_string();
+    }
+
+    let mut doc = Html::parse_document(html);
+    let recovered = doc
+        .errors
+        .iter()
+        .any(|error| error == UNCLOSED_ELEMENTS_ERROR);
+    if !recovered {
+        return html.to_string();
+    }
+
+    let collapsible = |element: ElementRef| {
+        element
+            .value()
+            .attrs()
+            .all(|(name, _)| matches!(name, "class" | "id"))
+    };
+    let mut chains: Vec<(NodeId, Vec<NodeId>)> = Vec::new();
+    for div in doc.select(&DIV_SELECTOR) {
+        if wrapped_div(div).is_none()
+            || div
+                .parent()
+                .and_then(ElementRef::wrap)
+                .and_then(wrapped_div)
+                .is_some()
+        {
+            // Not a wrapper, or part of a chain that starts further up.
+            continue;
+        }
+
+        let mut removed = Vec::new();
+        let mut last_wrapper = div;
+        while let Some(child) = wrapped_div(last_wrapper)
+            .filter(|child| wrapped_div(*child).is_some() && collapsible(*child))
+        {
+            last_wrapper = child;
+            removed.push(child.id());
+        }
+        if removed.len() >= MAX_WRAPPER_CHAIN {
+            chains.push((div.id(), removed));
+        }
+    }
+
+    if chains.is_empty() {
+        return html.to_string();
+    }
+
+    for (outer, removed) in chains {
+        let removed_attrs: Attributes = removed
+            .iter()
+            .filter_map(|id| doc.tree.get(*id))
+            .filter_map(|node| node.value().as_element())
+            .flat_map(|element| element.attrs.clone())
+            .collect();
+        let (Some(&second), Some(&last_wrapper)) = (removed.first(), removed.last()) else {
+            continue;
+        };
+        if let Some(mut node) = doc.tree.get_mut(outer) {
+            if let ScraperNode::Element(element) = node.value() {
+                crate::post_processor::merge_wrapper_attrs(&mut element.attrs, removed_attrs);
+            }
+            node.reparent_from_id_append(last_wrapper);
+        }
+        if let Some(mut node) = doc.tree.get_mut(second) {
+            node.detach();
+        }
+    }
+    doc.html()
+}
+
+/// The only child of a transparent `<div>` wrapper, if that child is a `<div>`.
+///
+/// Transparent wrappers hold a single element child and no text of their own.
+pub(crate) fn wrapped_div(element: ElementRef) -> Option<ElementRef> {
+    if !node_has_tag(element, "div") || element.value().attr("data-readability").is_some() {
+        return None;
+    }
+
+    let mut only_child = None;
+    for child in element.children() {
+        match child.value() {
+            ScraperNode::Element(_) => {
+                if only_child.is_some() {
+                    return None;
+                }
+                only_child = ElementRef::wrap(child);
+            }
+            ScraperNode::Text(text) if !text.trim().is_empty() => return None,
+            _ => {}
+        }
+    }
+    only_child.filter(|child| node_has_tag(*child, "div"))
+}
+
+/// Unhide the fullest panel of a tab group whose panels are all `hidden`
+///
+/// Tabbed layouts mark inactive panels with `hidden` and let a script reveal
+/// the active one, so server-rendered markup can arrive with every panel
+/// hidden and nothing left to extract. When all panels sharing a parent are
+/// hidden, the one with the most text is treated as the active panel. Groups
+/// with a visible panel already are left alone. Pages that never mention a
+/// tab panel are returned without being parsed.
+fn reveal_hidden_tab_panels(html: &str) -> String {
+    static TAB_PANEL_SELECTOR: Lazy<Selector> = Lazy::new(|| {
+        Selector::parse(r#"[role="tabpanel"], .tab-pane, .tab-panel, .tabpanel"#).unwrap()
+    });
+    static TAB_PANEL_MARKER_REGEX: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"(?i)\btab-?panel?\b").unwrap());
+
+    if !html.contains("hidden") || !TAB_PANEL_MARKER_REGEX.is_match(html) {
+        return html.to_string();
+    }
+
+    let mut doc = Html::parse_document(html);
+    let mut groups: Vec<(NodeId, Vec<ElementRef>)> = Vec::new();
+    for panel in doc.select(&TAB_PANEL_SELECTOR) {
+        let Some(parent) = panel.parent() else {
+            continue;
+        };
+        match groups.iter_mut().find(|(id, _)| *id == parent.id()) {
+            Some((_, panels)) => panels.push(panel),
+            None => groups.push((parent.id(), vec![panel])),
+        }
+    }
+
+    let to_reveal: Vec<NodeId> = groups
+        .iter()
+        .filter(|(_, panels)| {
+            panels.len() > 1
+                && panels
+                    .iter()
+                    .all(|panel| panel.value().attr("hidden").is_some())
+        })
+        .filter_map(|(_, panels)| {
+            panels
+                .iter()
+                .max_by_key(|panel| dom_utils::get_inner_text(**panel, true).len())
+                .map(|panel| panel.id())
+        })
+        .collect();
+    if to_reveal.is_empty() {
+        return html.to_string();
+    }
+
+    for id in to_reveal {
+        if let Some(mut node) = doc.tree.get_mut(id) {
+            if let ScraperNode::Element(element) = node.value() {
+                element
+                    .attrs
+                    .retain(|(name, _)| name.local.as_ref() != "hidden");
+            }
+        }
+    }
+    doc.html()
+}
+
+/// Rewrite every line break tag as a plain `<br>`
+///
+/// Source HTML spells line breaks as `<BR>`, `<br/>`, `<br >`, `</br>` or with
+/// presentational attributes like `clear="all"`. Canonicalizing them up front
+/// means the `<br>` patterns in the cleaning passes only have to match one form.
+fn normalize_br_tags(html: &str) -> String {
+    static BR_TAG_REGEX: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"(?i)<\s*/?\s*br(?:[\s/][^>]*)?>").unwrap());
+
+    BR_TAG_REGEX.replace_all(html, "<br>").to_string()
+}
+
+/// Map deprecated presentational tags to their modern equivalents
+///
+/// `<center>` becomes `<div>`, `<tt>` becomes `<code>`, `<strike>` becomes `<s>`
+/// and `<big>` becomes `<span>`, so they are scored and cleaned like the tags
+/// they stand for. Attributes are kept as-is.
+fn replace_deprecated_tags(html: &str) -> String {
+    static DEPRECATED_TAG_REGEX: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"(?i)<(/?)(center|tt|strike|big)([\s/>])").unwrap());
+
+    DEPRECATED_TAG_REGEX
+        .replace_all(html, |caps: &Captures| {
+            let replacement = match caps[2].to_ascii_lowercase().as_str() {
+                "center" => "div",
+                "tt" => "code",
+                "strike" => "s",
+                _ => "span",
+            };
+            format!("<{}{}{}", &caps[1], replacement, &caps[3])
+        })
+        .to_string()
+}
+
+/// Convert line-numbered code listing tables into `<pre><code>` blocks
+///
+/// Pygments (`highlighttable`), Pandoc (`sourceCode`), codehilite and GitHub
+/// (`highlight`) render numbered listings as a table with a line-number column
+/// next to the code. Such tables would otherwise be kept as data tables, with
+/// the numbers mixed into copied code. Only tables whose class matches one of
+/// these highlighters and that have a line-number column are converted; the
+/// code column is kept, one table row per line (or the whole listing when a
+/// single row holds a `<pre>`).
+fn convert_code_listing_tables(html: &str) -> String {
+    static TABLE_REGEX: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"(?is)<table\b[^>]*>.*?</table>").unwrap());
+    static HIGHLIGHT_CLASS_REGEX: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"(?i)highlight|sourcecode|codehilite").unwrap());
+    static TABLE_ONLY_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("table").unwrap());
+
+    TABLE_REGEX
+        .replace_all(html, |caps: &Captures| {
+            let fragment = Html::parse_fragment(&caps[0]);
+            let Some(table) = fragment.select(&TABLE_ONLY_SELECTOR).next() else {
+                return caps[0].to_string();
+            };
+            let class = table.value().attr("class").unwrap_or("");
+            if !HIGHLIGHT_CLASS_REGEX.is_match(class) {
+                return caps[0].to_string();
+            }
+            code_listing_block(table).unwrap_or_else(|| caps[0].to_string())
+        })
+        .to_string()
+}
+
+/// Build the `<pre><code>` replacement for a code listing table, if it has a
+/// line-number column.
+fn code_listing_block(table: ElementRef) -> Option<String> {
+    static LANGUAGE_CLASS_REGEX: Lazy<Regex> = Lazy::new(|| {
+        Regex::new(r"(?i)^(?:language|lang|highlight-source)-([a-z0-9_+#-]+)$").unwrap()
+    });
+
+    let mut has_line_numbers = false;
+    let mut lines = Vec::new();
+    for row in table.select(&TR_SELECTOR) {
+        let mut code = String::new();
+        for cell in direct_cells(row) {
+            if is_line_number_cell(cell) {
+                has_line_numbers = true;
+            } else {
+                code.push_str(&cell.text().collect::<String>());
+            }
+        }
+        lines.push(code.strip_suffix('\n').unwrap_or(&code).to_string());
+    }
+    if !has_line_numbers {
+        return None;
+    }
+
+    let code = lines.join("\n");
+    let code = code.trim_matches('\n');
+    if code.trim().is_empty() {
+        return None;
+    }
+
+    let language = std::iter::once(table)
+        .chain(table.descendants().filter_map(ElementRef::wrap))
+        .find_map(|el| {
+            let classes: Vec<&str> = el.value().classes().collect();
+            classes
+                .iter()
+                .find_map(|class| LANGUAGE_CLASS_REGEX.captures(class).map(|c| c[1].to_lowercase()))
+                .or_else(|| {
+                    // Pandoc marks code as `class="sourceCode python"`.
+                    if el.value().name() != "code" || !classes.contains(&"sourceCode") {
+                        return None;
+                    }
+                    classes
+                        .iter()
+                        .find(|class| !class.eq_ignore_ascii_case("sourceCode"))
+                        .map(|class| class.to_lowercase())
+                })
+        });
+
+    let class_attr = language
+        .map(|lang| format!(" class=\"language-{lang}\""))
+        .unwrap_or_default();
+    Some(format!(
+        "<pre><code{class_attr}>{}</code></pre>",
+        v_htmlescape::escape(code)
+    ))
+}
+
+/// Check if a table cell is the line-number column of a code listing.
+fn is_line_number_cell(cell: ElementRef) -> bool {
+    static LINE_NUMBER_CLASS_REGEX: Lazy<Regex> = Lazy::new(|| {
+        Regex::new(r"(?i)linenos|lineno|line-?numbers?|blob-num|gutter").unwrap()
+    });
+
+    cell.value().attr("data-line-number").is_some()
+        || LINE_NUMBER_CLASS_REGEX.is_match(cell.value().attr("class").unwrap_or(""))
+}
+
+/// Tag every start tag in the raw HTML with the line it appears on
+///
+/// Inserts a `data-src-line="N"` attribute (1-based) right after the tag name so
+/// the source position survives parsing, scoring and serialization. This must run
+/// on the original HTML, before [`prep_document`] removes scripts and shifts lines.
+///
+/// Comments, quoted attribute values and the bodies of raw-text elements such
+/// as `<script>` and `<style>` are skipped, so a `<b ` inside them is left alone.
+pub fn annotate_source_lines(html: &str) -> String {
+    static MARKUP_REGEX: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"<!--|<([a-zA-Z][a-zA-Z0-9-]*)[\s/>]").unwrap());
+
+    let mut result = String::with_capacity(html.len() + html.len() / 4);
+    let mut line = 1;
+    let mut copied = 0;
+    let mut pos = 0;
+
+    while let Some(caps) = MARKUP_REGEX.captures_at(html, pos) {
+        let whole = caps.get(0).unwrap();
+        let Some(name) = caps.get(1) else {
+            pos = html[whole.end()..]
+                .find("-->")
+                .map_or(html.len(), |end| whole.end() + end + 3);
+            continue;
+        };
+
+        let preceding = &html[copied..name.end()];
+        line += preceding.matches('\n').count();
+        result.push_str(preceding);
+        result.push_str(&format!(" {SOURCE_LINE_ATTR}=\"{line}\""));
+        copied = name.end();
+
+        pos = start_tag_end(html, name.end());
+        let tag = name.as_str().to_ascii_lowercase();
+        if RAW_TEXT_TAGS.contains(&tag.as_str()) || tag == "noscript" {
+            pos = html[pos..]
+                .to_ascii_lowercase()
+                .find(&format!("</{tag}"))
+                .map_or(html.len(), |end| pos + end);
+        }
+    }
+
+    result.push_str(&html[copied..]);
+    result
+}
+
+/// Byte offset just past the `>` closing the start tag that begins before
+/// `from`, ignoring any `>` inside quoted attribute values.
+fn start_tag_end(html: &str, from: usize) -> usize {
+    let mut quote = None;
+    let mut after_equals = false;
+    for (i, byte) in html.bytes().enumerate().skip(from) {
+        match quote {
+            Some(open) if byte == open => quote = None,
+            Some(_) => {}
+            None if byte == b'>' => return i + 1,
+            None if after_equals && (byte == b'"' || byte == b'\'') => quote = Some(byte),
+            None => {}
+        }
+        after_equals = byte == b'=' || (after_equals && byte.is_ascii_whitespace());
+    }
+    html.len()
+}
+
+/// Drop source line annotations from phrasing elements
+///
+/// Only block-level elements keep their `data-src-line` attribute in the final
+/// output; inline markup such as links and emphasis is left untouched.
+pub fn retain_block_source_lines(html: &str) -> String {
+    static ANNOTATED_TAG_REGEX: Lazy<Regex> = Lazy::new(|| {
+        Regex::new(r#"<([a-zA-Z][a-zA-Z0-9-]*)([^>]*?)\s+data-src-line="\d+""#).unwrap()
+    });
+
+    ANNOTATED_TAG_REGEX
+        .replace_all(html, |caps: &Captures| {
+            let tag = caps[1].to_uppercase();
+            if PHRASING_ELEMS.contains(&tag.as_str()) || matches!(tag.as_str(), "A" | "DEL" | "INS")
+            {
+                format!("<{}{}", &caps[1], &caps[2])
+            } else {
+                caps[0].to_string()
+            }
+        })
+        .to_string()
+}
+
 fn node_has_tag(element: ElementRef, tag: &str) -> bool {
     element.value().name().eq_ignore_ascii_case(tag)
 }
@@ -534,9 +1408,11 @@ fn dom_link_density(element: ElementRef, text_len: usize) -> f64 {
     }
     let mut link_length = 0usize;
     for link in element.select(&LINK_SELECTOR) {
-        link_length += link.text().collect::<String>().len();
+        if !dom_utils::is_nested_link(link, element) && !dom_utils::is_block_wrapping_link(link) {
+            link_length += link.text().collect::<String>().len();
+        }
     }
-    link_length as f64 / text_len as f64
+    (link_length as f64 / text_len as f64).min(1.0)
 }
 
 fn get_text_density(element: ElementRef, selector: &Selector) -> f64 {
@@ -551,20 +1427,65 @@ fn get_text_density(element: ElementRef, selector: &Selector) -> f64 {
     child_text / total_text
 }
 
-fn node_has_allowed_video(element: ElementRef) -> bool {
+fn node_has_allowed_video(element: ElementRef, video_regex: &Regex) -> bool {
     for (_, value) in element.value().attrs() {
-        if REGEXPS.videos.is_match(value) {
+        if video_regex.is_match(value) {
             return true;
         }
     }
     if node_has_tag(element, "object")
-        && REGEXPS.videos.is_match(&element.text().collect::<String>())
+        && video_regex.is_match(&element.text().collect::<String>())
     {
         return true;
     }
     false
 }
 
+/// Check whether the element is, or contains, a list whose items are mostly
+/// recipe ingredients or steps.
+fn node_has_recipe_list(element: ElementRef, recipe: &Recipe) -> bool {
+    let known: Vec<String> = recipe
+        .ingredients
+        .iter()
+        .chain(recipe.instructions.iter())
+        .map(|item| normalize_recipe_text(item))
+        .filter(|item| !item.is_empty())
+        .collect();
+    if known.is_empty() {
+        return false;
+    }
+
+    let is_list = node_has_tag(element, "ul") || node_has_tag(element, "ol");
+    let mut lists = element.select(&UL_OL_SELECTOR).collect::<Vec<_>>();
+    if is_list {
+        lists.push(element);
+    }
+
+    lists.into_iter().any(|list| {
+        let items: Vec<String> = list
+            .select(&LI_SELECTOR)
+            .map(|li| normalize_recipe_text(&dom_inner_text(li)))
+            .filter(|text| !text.is_empty())
+            .collect();
+        let matched = items
+            .iter()
+            .filter(|text| {
+                known
+                    .iter()
+                    .any(|item| text.contains(item.as_str()) || item.contains(text.as_str()))
+            })
+            .count();
+        matched > 0 && matched * 2 >= items.len()
+    })
+}
+
+fn normalize_recipe_text(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
 fn detect_data_table(table: ElementRef) -> bool {
     if let Some(role) = table.value().attr("role") {
         if role == "presentation" {
@@ -605,12 +1526,10 @@ fn detect_data_table(table: ElementRef) -> bool {
     rows * columns > 10
 }
 
-fn clean_conditionally_tag(
-    doc: &mut Html,
-    root_id: NodeId,
-    tag: &str,
-    marks: &HashSet<NodeId>,
-) {
+fn clean_conditionally_tag<F>(doc: &mut Html, root_id: NodeId, tag: &str, should_remove: F)
+where
+    F: Fn(ElementRef) -> bool,
+{
     let Some(selector) = cleanup_tag_selector(tag) else {
         return;
     };
@@ -625,7 +1544,7 @@ fn clean_conditionally_tag(
         };
         root_el
             .select(selector)
-            .filter(|el| should_remove_dom_node(*el, tag, marks))
+            .filter(|el| should_remove(*el))
             .map(|el| el.id())
             .collect()
     };
@@ -640,12 +1559,45 @@ fn clean_conditionally_tag(
     }
 }
 
-fn should_remove_dom_node(element: ElementRef, tag: &str, marks: &HashSet<NodeId>) -> bool {
+/// Check if the element's class or id is listed in `content_containers`.
+///
+/// Entries starting with `.` only match a class and entries starting with `#`
+/// only match the id; bare names match either.
+fn is_content_container(element: ElementRef, content_containers: &[String]) -> bool {
+    content_containers.iter().any(|entry| {
+        let has_class = |name: &str| element.value().classes().any(|class| class == name);
+        let has_id = |name: &str| element.value().id() == Some(name);
+        match (entry.strip_prefix('.'), entry.strip_prefix('#')) {
+            (Some(class), _) => has_class(class),
+            (_, Some(id)) => has_id(id),
+            _ => has_class(entry) || has_id(entry),
+        }
+    })
+}
+
+fn should_remove_dom_node(
+    element: ElementRef,
+    tag: &str,
+    marks: &HashSet<NodeId>,
+    targets: &HashMap<NodeId, Vec<NodeId>>,
+    video_regex: &Regex,
+    recipe: Option<&Recipe>,
+) -> bool {
     let class_id = get_dom_class_id_string(element);
     if is_comment_section(&class_id) {
         return true;
     }
 
+    if holds_link_target(element, targets) {
+        return false;
+    }
+
+    if let Some(recipe) = recipe {
+        if node_has_recipe_list(element, recipe) {
+            return false;
+        }
+    }
+
     let text = element.text().collect::<String>();
     let trimmed = text.trim();
     if trimmed.len() > 600 {
@@ -688,10 +1640,6 @@ fn should_remove_dom_node(element: ElementRef, tag: &str, marks: &HashSet<NodeId
         return true;
     }
 
-    if trimmed.matches(',').count() >= 10 {
-        return false;
-    }
-
     let p = count_descendants(element, &P_SELECTOR);
     let img = count_descendants(element, &IMG_SELECTOR);
     let li = count_descendants(element, &LI_SELECTOR).saturating_sub(100);
@@ -700,13 +1648,23 @@ fn should_remove_dom_node(element: ElementRef, tag: &str, marks: &HashSet<NodeId
 
     let mut embed_count = 0usize;
     for embed in element.select(&EMBED_GROUP_SELECTOR) {
-        if node_has_allowed_video(embed) {
+        if node_has_allowed_video(embed, video_regex) {
             return false;
         }
         embed_count += 1;
     }
 
-    if REGEXPS.ad_words.is_match(trimmed) || REGEXPS.loading_words.is_match(trimmed) {
+    // Ads are often a label such as "Advertisement" followed by comma-heavy
+    // copy, so check the leading label too before the comma count keeps them.
+    let label = element
+        .text()
+        .map(str::trim)
+        .find(|text| !text.is_empty())
+        .unwrap_or_default();
+    if [trimmed, label]
+        .iter()
+        .any(|text| REGEXPS.ad_words.is_match(text) || REGEXPS.loading_words.is_match(text))
+    {
         return true;
     }
     let text_density = get_text_density(element, &TEXTISH_SELECTOR);
@@ -789,6 +1747,52 @@ fn mark_data_tables(root: ElementRef) -> HashSet<NodeId> {
     marks
 }
 
+/// Map elements that in-content `<a href="#id">` links point at to those links.
+fn find_link_targets(root: ElementRef) -> HashMap<NodeId, Vec<NodeId>> {
+    static ID_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("[id]").unwrap());
+    static FRAGMENT_LINK_SELECTOR: Lazy<Selector> =
+        Lazy::new(|| Selector::parse(r##"a[href^="#"]"##).unwrap());
+
+    let ids: HashMap<&str, NodeId> = root
+        .select(&ID_SELECTOR)
+        .filter_map(|el| Some((el.value().id()?, el.id())))
+        .collect();
+
+    let mut targets: HashMap<NodeId, Vec<NodeId>> = HashMap::new();
+    for link in root.select(&FRAGMENT_LINK_SELECTOR) {
+        let fragment = &link.value().attr("href").unwrap_or_default()[1..];
+        if let Some(&target) = ids.get(fragment) {
+            targets.entry(target).or_default().push(link.id());
+        }
+    }
+    targets
+}
+
+/// Check if the element holds the target of a link that lives outside it.
+///
+/// Removing such an element, even an empty footnote anchor, would leave the
+/// link pointing nowhere. Links already removed by earlier passes don't count.
+fn holds_link_target(element: ElementRef, targets: &HashMap<NodeId, Vec<NodeId>>) -> bool {
+    if targets.is_empty() {
+        return false;
+    }
+    let root_id = element.tree().root().id();
+
+    element
+        .descendants()
+        .filter_map(|node| targets.get(&node.id()))
+        .flatten()
+        .filter_map(|&link| element.tree().get(link))
+        .any(|link| {
+            // The first of the element and the tree root met on the way up
+            // tells whether the link is outside the element and still attached.
+            link.ancestors()
+                .map(|node| node.id())
+                .find(|&id| id == element.id() || id == root_id)
+                == Some(root_id)
+        })
+}
+
 fn has_ancestor<F>(element: ElementRef, mut predicate: F) -> bool
 where
     F: FnMut(ElementRef) -> bool,
@@ -859,60 +1863,429 @@ mod tests {
             </article>
         "##;
 
-        let cleaned = remove_conditionally(html);
+        let cleaned = remove_conditionally(html, &REGEXPS.videos, None, &[]);
         assert!(!cleaned.contains("nav-table"));
         assert!(cleaned.contains("Main story starts here"));
     }
 
     #[test]
-    fn test_replace_brs_simple() {
-        let html = "Line 1<br><br>Line 2";
-        let result = replace_brs(html);
-        assert!(result.contains("<p>Line 1</p>"));
-        assert!(result.contains("<p>Line 2</p>"));
+    fn test_remove_conditionally_keeps_video_containers() {
+        let html = r#"
+            <article>
+                <div><iframe src="https://fast.wistia.net/embed/iframe/abc123"></iframe><p>Demo</p></div>
+                <div><iframe src="https://w.soundcloud.com/player/?url=track"></iframe><p>Episode</p></div>
+                <div><iframe src="https://ads.example.com/x"></iframe><p>Promo</p></div>
+                <p>Main story starts here</p>
+            </article>
+        "#;
+
+        let cleaned = remove_conditionally(html, &REGEXPS.videos, None, &[]);
+        assert!(cleaned.contains("wistia.net"));
+        assert!(cleaned.contains("soundcloud.com"));
+        assert!(!cleaned.contains("ads.example.com"));
     }
 
     #[test]
-    fn test_replace_brs_with_whitespace() {
-        let html = "Line 1<br> <br>Line 2";
-        let result = replace_brs(html);
-        assert!(result.contains("<p>Line 1</p>"));
-        assert!(result.contains("<p>Line 2</p>"));
+    fn test_remove_conditionally_removes_comma_heavy_ads() {
+        let html = r#"
+            <article>
+                <div><p>Advertisement</p><p>Seeds, bulbs, gloves, pots, soil, compost, mulch, trellises, hoses, shears, and more, all on sale this week.</p></div>
+                <div><p>We planted tomatoes, peppers, squash, beans, peas, carrots, beets, radishes, lettuce, kale, and chard this spring.</p></div>
+            </article>
+        "#;
+
+        let cleaned = remove_conditionally(html, &REGEXPS.videos, None, &[]);
+        assert!(!cleaned.contains("all on sale"));
+        assert!(cleaned.contains("We planted tomatoes"));
     }
 
     #[test]
-    fn test_replace_brs_multiple() {
-        let html = "Para 1<br><br>Para 2<br><br><br>Para 3";
-        let result = replace_brs(html);
-        assert!(result.contains("<p>Para 1</p>"));
-        assert!(result.contains("<p>Para 2</p>"));
-        assert!(result.contains("<p>Para 3</p>"));
+    fn test_remove_conditionally_keeps_link_targets() {
+        let html = r##"
+            <article>
+                <p>The beds were watered at dawn.<a href="#note-1">1</a></p>
+                <div class="meta"><p id="note-1">Measured with a rain gauge.</p></div>
+                <div class="meta"><p id="note-2">Posted in Gardening.</p></div>
+                <div class="meta"><a href="#note-3">3</a><p id="note-3">Linked only from itself.</p></div>
+            </article>
+        "##;
+
+        let cleaned = remove_conditionally(html, &REGEXPS.videos, None, &[]);
+        assert!(cleaned.contains("Measured with a rain gauge."));
+        assert!(!cleaned.contains("Posted in Gardening."));
+        assert!(!cleaned.contains("Linked only from itself."));
     }
 
     #[test]
-    fn test_replace_brs_no_doubles() {
-        let html = "Line 1<br>Line 2";
-        let result = replace_brs(html);
-        assert!(result.contains("Line 1<br>Line 2"));
+    fn test_remove_conditionally_keeps_content_containers() {
+        let html = r#"
+            <article>
+                <div class="widget harvest-box"><p>Our first harvest of the year.</p></div>
+                <div class="widget" id="lede"><p>Notes from the allotment.</p></div>
+                <div class="widget"><p>Subscribe for updates.</p></div>
+                <p>Main story starts here</p>
+            </article>
+        "#;
+
+        let cleaned = remove_conditionally(html, &REGEXPS.videos, None, &[]);
+        assert!(!cleaned.contains("first harvest"));
+        assert!(!cleaned.contains("allotment"));
+
+        let containers = [".harvest-box".to_string(), "lede".to_string()];
+        let cleaned = remove_conditionally(html, &REGEXPS.videos, None, &containers);
+        assert!(cleaned.contains("first harvest"));
+        assert!(cleaned.contains("allotment"));
+        assert!(!cleaned.contains("Subscribe"));
+
+        let cleaned = remove_conditionally(html, &REGEXPS.videos, None, &["#harvest-box".into()]);
+        assert!(!cleaned.contains("first harvest"));
+    }
+
+    #[test]
+    fn test_prep_document_replaces_deprecated_tags() {
+        let html = r#"<CENTER class="intro"><p>Use <tt>ls</tt> to <strike>delete</strike> <big>list</big> files.</p></CENTER>"#;
+        let prepped = prep_document(html);
+        assert_eq!(
+            prepped,
+            r#"<div class="intro"><p>Use <code>ls</code> to <s>delete</s> <span>list</span> files.</p></div>"#
+        );
+    }
+
+    #[test]
+    fn test_prep_document_keeps_similar_tag_names() {
+        let html = "<table><tbody><tr><td>cell</td></tr></tbody></table><bigger>x</bigger><big-x>y</big-x><tt-note>z</tt-note>";
+        let prepped = prep_document(html);
+        assert!(prepped.contains("<tbody>"));
+        assert!(prepped.contains("<bigger>x</bigger>"));
+        assert!(prepped.contains("<big-x>y</big-x>"));
+        assert!(prepped.contains("<tt-note>z</tt-note>"));
+    }
+
+    #[test]
+    fn test_remove_conditionally_keeps_recipe_lists() {
+        let html = r##"
+            <article>
+                <div class="widget"><ul>
+                    <li><a href="#">2 cups flour</a></li>
+                    <li><a href="#">1 tsp salt</a></li>
+                    <li><a href="#">1 cup water</a></li>
+                </ul></div>
+                <p>Main story starts here</p>
+            </article>
+        "##;
+        let recipe = Recipe {
+            ingredients: vec![
+                "2 cups flour".to_string(),
+                "1 tsp salt".to_string(),
+                "1 cup  water".to_string(),
+            ],
+            instructions: vec![],
+        };
+
+        let without = remove_conditionally(html, &REGEXPS.videos, None, &[]);
+        assert!(!without.contains("2 cups flour"));
+
+        let with = remove_conditionally(html, &REGEXPS.videos, Some(&recipe), &[]);
+        assert!(with.contains("2 cups flour"));
+        assert!(with.contains("1 cup water"));
+    }
+
+    const NEWSLETTER: &str = r#"<html lang="en" dir="ltr" class="no-js" data-theme="light"><head><title>Digest</title></head><body><center>
+<table id="bodyTable" width="100%"><tr><td id="bodyCell">
+  <table class="templateContainer" width="600">
+    <tr><td id="templatePreheader"><a href="/view">View this email in your browser</a></td></tr>
+    <tr><td id="templateHeader"><img src="/logo.png" alt="Logo"></td></tr>
+    <tr><td id="templateBody">
+      <table class="mcnTextBlock"><tr><td class="mcnTextContent">
+        <h1>This week in gardening</h1>
+        <p>Spring has finally arrived, and with it the first wave of seedlings. We spent the week transplanting tomatoes, peppers, and squash into the raised beds.</p>
+        <p>The soil temperature matters more than the air temperature, so we waited for a steady fifteen degrees before moving anything outside.</p>
+      </td></tr></table>
+      <table class="mcnTextBlock"><tr><td class="mcnTextContent">
+        <h2>Reader question</h2>
+        <p>Water new transplants daily for the first week.</p>
+      </td></tr></table>
+    </td></tr>
+    <tr><td id="templateFooter"><em>Copyright 2024, All rights reserved.</em><br><a href="/unsub">Unsubscribe</a></td></tr>
+  </table>
+</td></tr></table>
+</center></body></html>"#;
+
+    #[test]
+    fn test_promote_layout_table_content() {
+        let promoted = promote_layout_table_content(NEWSLETTER);
+        let root = Html::parse_document(&promoted).root_element().value().clone();
+        assert_eq!(root.attr("lang"), Some("en"));
+        assert_eq!(root.attr("dir"), Some("ltr"));
+        assert_eq!(root.attr("class"), Some("no-js"));
+        assert_eq!(root.attr("data-theme"), Some("light"));
+        assert!(promoted.contains("This week in gardening"));
+        assert!(promoted.contains("Water new transplants daily"));
+        assert!(!promoted.contains("View this email"));
+        assert!(!promoted.contains("Unsubscribe"));
+        assert!(!promoted.contains("<table"));
+        assert!(!promoted.contains("<td"));
+    }
+
+    #[test]
+    fn test_promote_layout_table_content_skips_regular_pages() {
+        let article = NEWSLETTER.replace("<center>", "<article>").replace("</center>", "</article>");
+        assert_eq!(promote_layout_table_content(&article), article);
+
+        let data_table = r#"<html><body><table>
+            <thead><tr><th>Year</th><th>Harvest</th></tr></thead>
+            <tr><td>2023</td><td>A long description of the harvest that year with plenty of words in it, more than enough to pass the minimum text length required for promotion to be considered at all, and then some more text.</td></tr>
+        </table></body></html>"#;
+        assert_eq!(promote_layout_table_content(data_table), data_table);
+    }
+
+    #[test]
+    fn test_strip_internal_attributes() {
+        let html = r#"<table data-readability-datatable="true" class="x"><tr><td data-readability-score='12' data-readability-flag>1</td></tr></table><p>data-readability-datatable</p>"#;
+        assert_eq!(
+            strip_internal_attributes(html),
+            r#"<table class="x"><tr><td>1</td></tr></table><p>data-readability-datatable</p>"#
+        );
+    }
+
+    #[test]
+    fn test_convert_pygments_code_table() {
+        let html = r#"<div class="highlight"><table class="highlighttable"><tr><td class="linenos"><div class="linenodiv"><pre>1
+2
+3</pre></div></td><td class="code"><div class="highlight"><pre><span></span><span class="k">def</span> <span class="nf">add</span>(a, b):
+    <span class="k">return</span> a &lt; b
+print(add(1, 2))
+</pre></div></td></tr></table></div>"#;
+
+        let result = convert_code_listing_tables(html);
+        assert!(!result.contains("<table"));
+        assert!(result.contains(
+            "<pre><code>def add(a, b):\n    return a &lt; b\nprint(add(1, 2))</code></pre>"
+        ));
+    }
+
+    #[test]
+    fn test_convert_github_code_table() {
+        let html = r#"<div class="highlight-source-js"><table class="highlight tab-size">
+<tr><td id="L1" class="blob-num js-line-number" data-line-number="1"></td><td id="LC1" class="blob-code blob-code-inner"><span class="pl-k">const</span> x = 1;</td></tr>
+<tr><td id="L2" class="blob-num js-line-number" data-line-number="2"></td><td id="LC2" class="blob-code blob-code-inner"></td></tr>
+<tr><td id="L3" class="blob-num js-line-number" data-line-number="3"></td><td id="LC3" class="blob-code blob-code-inner">console.log(x);</td></tr>
+</table></div>"#;
+
+        let result = convert_code_listing_tables(html);
+        assert!(result.contains("<pre><code>const x = 1;\n\nconsole.log(x);</code></pre>"));
+        assert!(!result.contains("data-line-number"));
+    }
+
+    #[test]
+    fn test_code_table_language_and_plain_tables() {
+        let html = r#"<table class="sourceCode python numberLines"><tr class="sourceCode"><td class="lineNumbers"><pre>1</pre></td><td class="sourceCode"><pre><code class="sourceCode python">x = 1</code></pre></td></tr></table>"#;
+        assert_eq!(
+            convert_code_listing_tables(html),
+            r#"<pre><code class="language-python">x = 1</code></pre>"#
+        );
+
+        // Highlighter class but no line-number column, and a plain data table.
+        let no_numbers = r#"<table class="highlight"><tr><td>a</td><td>b</td></tr></table>"#;
+        assert_eq!(convert_code_listing_tables(no_numbers), no_numbers);
+        let data = r#"<table class="stats"><tr><td class="lineno">1</td><td>b</td></tr></table>"#;
+        assert_eq!(convert_code_listing_tables(data), data);
+    }
+
+    #[test]
+    fn test_remove_hinted_nodes() {
+        let html = r#"<html><body><div><p>Keep</p><div data-readability="remove"><p>Related posts</p></div><aside data-readability="remove">Ad</aside></div></body></html>"#;
+        let result = remove_hinted_nodes(html);
+        assert!(result.contains("<p>Keep</p>"));
+        assert!(!result.contains("Related posts"));
+        assert!(!result.contains("Ad"));
+
+        let untouched = r#"<div data-readability="content"><p>Body</p></div>"#;
+        assert_eq!(remove_hinted_nodes(untouched), untouched);
+    }
+
+    #[test]
+    fn test_remove_news_clutter() {
+        let html = r#"<html><body><div class="page-ad-wrapper"><article class="story ad-free"><p>Body</p>
+<div id="div-gpt-ad-1">Advertisement</div><ins class="adsbygoogle"></ins><div data-ad-slot="top"></div>
+<p><b>Read more:</b> <a href="/otters">Otters return</a></p><p>Read more about the count below.</p>
+<section class="related-stories"><a href="/a">Another story</a></section><div class="headline">Herons</div>
+</article></div></body></html>"#;
+        let result = remove_news_clutter(html);
+        assert!(result.contains(r#"<div class="page-ad-wrapper"><article class="story ad-free">"#));
+        assert!(result.contains("<p>Read more about the count below.</p>"));
+        assert!(result.contains("Herons"));
+        assert!(!result.contains("Advertisement"));
+        assert!(!result.contains("adsbygoogle"));
+        assert!(!result.contains("data-ad-slot"));
+        assert!(!result.contains("Otters return"));
+        assert!(!result.contains("Another story"));
+    }
+
+    #[test]
+    fn test_fix_relative_urls_in_html() {
+        let html = r##"<p><a href="/about">About</a> <a href="../2023/older">Older</a> <a href="#notes">Notes</a> <a href="mailto:ed@example.com">Mail</a> <a href="javascript:void(0)">Menu</a></p><img src="images/x.png" srcset="images/x.png 1x, //cdn.example.net/x@2x.png 2x"><video poster="poster.jpg" src="https://media.example.org/clip.mp4"></video><img src="data:image/gif;base64,R0lGOD">"##;
+        let result = fix_relative_urls_in_html(html, "https://example.com/articles/2024/post");
+        assert_eq!(
+            result,
+            r##"<p><a href="https://example.com/about">About</a> <a href="https://example.com/articles/2023/older">Older</a> <a href="#notes">Notes</a> <a href="mailto:ed@example.com">Mail</a> <a href="javascript:void(0)">Menu</a></p><img src="https://example.com/articles/2024/images/x.png" srcset="https://example.com/articles/2024/images/x.png 1x, https://cdn.example.net/x@2x.png 2x"><video poster="https://example.com/articles/2024/poster.jpg" src="https://media.example.org/clip.mp4"></video><img src="data:image/gif;base64,R0lGOD">"##
+        );
+        assert_eq!(fix_relative_urls_in_html(html, "not a url"), html);
+    }
+
+    #[test]
+    fn test_remove_consent_banners() {
+        let html = r#"<html><body><div><div><p>We use cookies to improve your experience.</p>
+<button>Accept all</button> <button>Reject all</button></div></div>
+<article><h1>Herons</h1><p>Herons accept the cold.</p></article></body></html>"#;
+        let result = remove_consent_banners(html);
+        assert!(!result.contains("We use cookies"));
+        assert!(!result.contains("Accept all"));
+        assert!(result.contains("<body><div></div>"));
+        assert!(result.contains("<article><h1>Herons</h1><p>Herons accept the cold.</p></article>"));
+
+        // Consent wording without a control, or deep inside the page, is kept.
+        let notice = "<p>This site uses cookies. <a href=\"/privacy\">Privacy policy</a></p>";
+        assert_eq!(remove_consent_banners(notice), notice);
+        let article = format!(
+            "<html><body><article><p>{}</p><div><p>We use cookies.</p><button>OK</button></div><p>{}</p></article></body></html>",
+            "Herons wade. ".repeat(100),
+            "Herons wait. ".repeat(100)
+        );
+        assert!(remove_consent_banners(&article).contains("We use cookies."));
+
+        // Articles about privacy mention consent and link to settings pages.
+        let lead = r#"<html><body><article><p>Regulators say most apps still do not explain how they collect and use personal data. <a href="/privacy">Manage your privacy</a> settings before you <a href="/ok">OK</a> anything.</p></article></body></html>"#;
+        assert!(remove_consent_banners(lead).contains("Regulators say most apps"));
+        let single = "<div><p>We use cookies.</p><button>Accept</button></div><p>Herons.</p>";
+        assert!(remove_consent_banners(single).contains("We use cookies."));
+    }
+
+    #[test]
+    fn test_flatten_wrapper_chains() {
+        // Unclosed wrappers before the article nest it twelve levels deep.
+        let html = format!(
+            "<html><body>{}<article><p>Body</p></article></body></html>",
+            r#"<div class="wrap">"#.repeat(12)
+        );
+        let result = flatten_wrapper_chains(&html);
+        assert_eq!(result.matches("<div").count(), 2);
+        assert!(result.contains(
+            r#"<body><div class="wrap"><div class="wrap"><article><p>Body</p></article></div></div></body>"#
+        ));
+
+        // Classes and the first id of the removed wrappers are kept, and a
+        // wrapper with other attributes ends the chain.
+        let html = format!(
+            r#"<html><body><div class="page">{}<div id="main" class="content"><div itemprop="articleBody"><div><article><p>Body</p></article>"#,
+            "<div>".repeat(6)
+        );
+        let result = flatten_wrapper_chains(&html);
+        assert!(result.contains(
+            r#"<body><div class="content page" id="main"><div itemprop="articleBody"><div><article>"#
+        ));
+
+        // Ordinary layout nesting is left alone, however deep.
+        let layout = format!(
+            "<html><head></head><body>{}<p>Body</p>{}</body></html>",
+            "<div>".repeat(MAX_WRAPPER_CHAIN + 1),
+            "</div>".repeat(MAX_WRAPPER_CHAIN + 1)
+        );
+        assert_eq!(flatten_wrapper_chains(&layout), layout);
+        let closed = format!(
+            "<html><head></head><body>{}<p>Body</p>{}</body></html>",
+            "<div>".repeat(12),
+            "</div>".repeat(12)
+        );
+        assert_eq!(flatten_wrapper_chains(&closed), closed);
+
+        // A wrapper with text of its own ends the chain.
+        let with_text = format!(
+            "<html><head></head><body>{}Note{}<p>Body</p>{}</body></html>",
+            "<div>".repeat(4),
+            "<div>".repeat(4),
+            "</div>".repeat(8)
+        );
+        assert_eq!(flatten_wrapper_chains(&with_text), with_text);
+    }
+
+    #[test]
+    fn test_normalize_br_tags() {
+        let html = "<p>One<BR>Two<br/>Three<br >Four<Br />Five</br><br clear=\"all\"><brand>Six</brand><br-x>Seven</br-x></p>";
+        assert_eq!(
+            normalize_br_tags(html),
+            "<p>One<br>Two<br>Three<br>Four<br>Five<br><br><brand>Six</brand><br-x>Seven</br-x></p>"
+        );
+    }
+
+    #[test]
+    fn test_sanitize_malformed_html() {
+        let html = "<html><head><title>Broken<style>p{}</head><body><!-- nav <p>Text</p><script>ok()</script><textarea></body></html>";
+        let sanitized = sanitize_malformed_html(html);
+        assert_eq!(
+            sanitized,
+            "<html><head>Brokenp{}</head><body> nav <p>Text</p><script>ok()</script></body></html>"
+        );
+        assert!(!has_body_content(&Html::parse_document(html)));
+        assert!(has_body_content(&Html::parse_document(&sanitized)));
+
+        let valid =
+            "<html><head><title>Fine</title></head><body><!-- c --><p>Text</p></body></html>";
+        assert_eq!(sanitize_malformed_html(valid), valid);
+    }
+
+    #[test]
+    fn test_reveal_hidden_tab_panels() {
+        let html = r#"<html><head></head><body><div class="tabs">
+<div role="tabpanel" id="overview" hidden><p>Short overview.</p></div>
+<div role="tabpanel" id="guide" hidden><p>The full installation guide, with every step spelled out.</p></div>
+</div></body></html>"#;
+        let result = Html::parse_document(&reveal_hidden_tab_panels(html));
+        let hidden = |id: &str| {
+            let sel = Selector::parse(&format!("#{id}")).unwrap();
+            let panel = result.select(&sel).next().unwrap();
+            panel.value().attr("hidden").is_some()
+        };
+        assert!(hidden("overview"));
+        assert!(!hidden("guide"));
+
+        // A group with a visible panel already shows its active tab.
+        let active = r#"<html><head></head><body><div class="tabs">
+<div class="tab-pane"><p>Short overview.</p></div>
+<div class="tab-pane" hidden=""><p>The full installation guide, with every step spelled out.</p></div>
+</div></body></html>"#;
+        assert_eq!(reveal_hidden_tab_panels(active), active);
+
+        let no_panels = r#"<div hidden><p>Hidden, but not a tab.</p></div>"#;
+        assert_eq!(reveal_hidden_tab_panels(no_panels), no_panels);
     }
 
     #[test]
-    fn test_replace_brs_with_wrapper_div() {
-        let html = "<div>Lorem ipsum<br/>dolor sit<br/> <br/><br/>amet, consectetur</div>";
-        let result = replace_brs(html);
-        assert!(result.starts_with("<div>"));
-        assert!(result.ends_with("</div>"));
-        assert!(result.contains("<p>Lorem ipsum<br/>dolor sit</p>"));
-        assert!(result.contains("<p>amet, consectetur</p>"));
+    fn test_annotate_source_lines() {
+        let html = "<div>\n<p>One</p>\n\n<p class=\"x\">Two<br/></p></div>";
+        let annotated = annotate_source_lines(html);
+        assert!(annotated.contains(r#"<div data-src-line="1">"#));
+        assert!(annotated.contains(r#"<p data-src-line="2">One</p>"#));
+        assert!(annotated.contains(r#"<p data-src-line="4" class="x">"#));
+        assert!(annotated.contains(r#"<br data-src-line="4"/>"#));
+
+        let html = r#"<!-- <b class="x"> -->
+<p title="a <b >c" data-x='<i>'>One</p>
+<script>if (a <b && c) {}</script><STYLE>b <i {}</style>
+<span>Two</span>"#;
+        let annotated = annotate_source_lines(html);
+        assert_eq!(annotated.matches("data-src-line").count(), 4);
+        assert!(annotated.starts_with(r#"<!-- <b class="x"> -->"#));
+        assert!(annotated.contains(r#"<p data-src-line="2" title="a <b >c" data-x='<i>'>One</p>"#));
+        assert!(annotated.contains(r#"<script data-src-line="3">if (a <b && c) {}</script>"#));
+        assert!(annotated.contains(r#"<STYLE data-src-line="3">b <i {}</style>"#));
+        assert!(annotated.contains(r#"<span data-src-line="4">Two</span>"#));
     }
 
     #[test]
-    fn test_replace_brs_preserves_attributes() {
-        let html = r#"<div class="content" id="main">Text 1<br><br>Text 2</div>"#;
-        let result = replace_brs(html);
-        assert!(result.contains("class=\"content\""));
-        assert!(result.contains("id=\"main\""));
-        assert!(result.contains("<p>Text 1</p>"));
-        assert!(result.contains("<p>Text 2</p>"));
+    fn test_retain_block_source_lines() {
+        let html = r#"<p data-src-line="2">See <a data-src-line="2" href="/x">this</a></p>"#;
+        let result = retain_block_source_lines(html);
+        assert_eq!(result, r#"<p data-src-line="2">See <a href="/x">this</a></p>"#);
     }
 }
@@ -5,7 +5,11 @@ use regex::Regex;
 
 // Bitflags for parsing strategies
 bitflags::bitflags! {
-    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    /// Heuristics enabled for an extraction attempt.
+    ///
+    /// All flags are set on the first attempt; a [`RetryStrategy`](crate::RetryStrategy)
+    /// decides which ones to drop when the result is too short.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
     pub struct ParseFlags: u32 {
         const STRIP_UNLIKELYS = 0x1;
         const WEIGHT_CLASSES = 0x2;
@@ -37,6 +41,7 @@ pub struct RegexPatterns {
     pub json_ld_article_types: Regex,
     pub ad_words: Regex,
     pub loading_words: Regex,
+    pub consent_words: Regex,
 }
 
 impl RegexPatterns {
@@ -61,7 +66,7 @@ impl RegexPatterns {
                 r"\s{2,}"
             ).unwrap(),
             videos: Regex::new(
-                r"(?i)//(www\.)?((dailymotion|youtube|youtube-nocookie|player\.vimeo|v\.qq|bilibili|live.bilibili)\.com|(archive|upload\.wikimedia)\.org|player\.twitch\.tv)"
+                r"(?i)//(www\.)?((dailymotion|youtube|youtube-nocookie|player\.vimeo|v\.qq|bilibili|live.bilibili|loom|open\.spotify|w\.soundcloud|cdn\.jwplayer)\.com|(archive|upload\.wikimedia)\.org|player\.twitch\.tv|([a-z0-9-]+\.)?wistia\.(com|net)|players\.brightcove\.net|content\.jwplatform\.com)"
             ).unwrap(),
             hash_url: Regex::new(
                 r"^#.+"
@@ -78,6 +83,9 @@ impl RegexPatterns {
             loading_words: Regex::new(
                 r"(?iu)^((loading|正在加载|Загрузка|chargement|cargando)(…|\.\.\.)?)$"
             ).unwrap(),
+            consent_words: Regex::new(
+                r"(?i)\b(we use cookies|uses cookies|use of cookies|cookies? (policy|settings|preferences|notice)|accept (all )?cookies|your consent|gdpr|personal data|privacy (settings|preferences))\b"
+            ).unwrap(),
         }
     }
 }
@@ -95,6 +103,21 @@ pub const DIV_TO_P_ELEMS: &[&str] = &[
     "UL",
 ];
 
+// Ids of single-page-app mount points (React, Next.js, Nuxt). These wrappers
+// hold the whole page, so like <body> they never take a candidate's score
+pub const SPA_MOUNT_POINT_IDS: &[&str] = &["app", "root", "__next", "__nuxt"];
+
+// Phrases that mark a paragraph as newsletter/social/legal boilerplate when
+// it sits at the start or end of the extracted content (matched lowercase)
+pub const DEFAULT_BOILERPLATE_PHRASES: &[&str] = &[
+    "sign up for our newsletter",
+    "subscribe to our newsletter",
+    "sign up for our free newsletter",
+    "get our newsletter",
+    "follow us on",
+    "like us on facebook",
+    "all rights reserved",
+];
 
 // Phrasing (inline) elements
 pub const PHRASING_ELEMS: &[&str] = &[
@@ -2,95 +2,300 @@
 
 use crate::constants::{ParseFlags, DEFAULT_TAGS_TO_SCORE, REGEXPS};
 use crate::error::Result;
-use crate::options::ReadabilityOptions;
-use crate::{dom_utils, scoring};
+use crate::options::{ContentProfile, ReadabilityOptions};
+use crate::retry::{AttemptStats, DefaultRetryStrategy, RetryStrategy};
+use crate::{dom_utils, metadata, post_processor, scoring, utils};
+use once_cell::sync::Lazy;
 use scraper::{ElementRef, Html, Selector};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
 use v_htmlescape::escape;
 
 /// Represents an extraction attempt
 #[derive(Debug, Clone)]
 struct Attempt {
-    content: String,
+    extraction: Extraction,
     text_length: usize,
 }
 
+/// Content selected by [`grab_article`].
+#[derive(Debug, Clone)]
+pub struct Extraction {
+    /// Serialized HTML of the selected content.
+    pub content: String,
+    /// Final content score of the selected candidate, or `None` when the
+    /// content root was given rather than scored (see [`root_extraction`]) or
+    /// the input was taken whole as a clean fragment.
+    pub candidate_score: Option<f64>,
+    /// Path of the selected candidate in the preprocessed version of the
+    /// document named by `source`, as built by [`dom_utils::element_path`].
+    pub path: String,
+    /// The document the content was taken from.
+    pub source: ExtractionSource,
+}
+
+/// The document an [`Extraction`] was taken from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtractionSource {
+    /// A candidate in the page itself.
+    Page,
+    /// The whole page, taken as a clean fragment by [`grab_clean_fragment`];
+    /// it only needs light cleaning.
+    CleanFragment,
+    /// HTML embedded in the page's script data.
+    EmbeddedHtml,
+    /// The `srcdoc` document of an iframe in the page.
+    IframeSrcdoc,
+}
+
+/// Take `root` as the content as is, without scoring candidates
+///
+/// Used for roots marked by the page, found by a content profile, or named
+/// by the caller's selector.
+pub fn root_extraction(root: ElementRef, options: &ReadabilityOptions) -> Extraction {
+    Extraction {
+        content: element_to_html(root, options.convert_brs, &options.preserve_whitespace_tags),
+        candidate_score: None,
+        path: dom_utils::element_path(root),
+        source: ExtractionSource::Page,
+    }
+}
+
 /// Main content extraction algorithm with retry logic
 ///
 /// Implements Mozilla's Readability algorithm with adaptive flag removal.
 /// If extraction fails with strict settings, retries with progressively
 /// looser criteria until content is found or all options are exhausted.
-pub fn grab_article(document: &Html, options: &ReadabilityOptions) -> Result<Option<String>> {
+/// The flags for each attempt come from `options.retry_strategy`, falling
+/// back to [`DefaultRetryStrategy`]. An element marked
+/// `data-readability="content"` is used as the article directly.
+///
+/// When no attempt reaches `options.char_threshold`, the longest attempt is
+/// returned, or `None` in strict mode. `title` is the article title from the
+/// metadata, used to recognize the article body by its heading.
+pub fn grab_article(
+    document: &Html,
+    options: &ReadabilityOptions,
+    title: Option<&str>,
+) -> Result<Option<Extraction>> {
+    // A content root marked by the page wins over scoring.
+    static CONTENT_HINT_SELECTOR: Lazy<Selector> =
+        Lazy::new(|| Selector::parse(r#"[data-readability="content"]"#).unwrap());
+    if let Some(root) = document.select(&CONTENT_HINT_SELECTOR).next() {
+        return Ok(Some(root_extraction(root, options)));
+    }
+    if options.content_profile == ContentProfile::Documentation {
+        if let Some(root) = find_documentation_root(document, options.char_threshold) {
+            return Ok(Some(root_extraction(root, options)));
+        }
+    }
+
+    let strategy: &dyn RetryStrategy = match options.retry_strategy.as_deref() {
+        Some(strategy) => strategy,
+        None => &DefaultRetryStrategy,
+    };
+
     let mut attempts = Vec::new();
-    let mut flags =
-        ParseFlags::STRIP_UNLIKELYS | ParseFlags::WEIGHT_CLASSES | ParseFlags::CLEAN_CONDITIONALLY;
+    let mut tried = HashSet::new();
+    let mut flags = strategy.initial_flags();
 
-    // Try extraction with different flag combinations
-    // Order: All flags -> Remove STRIP_UNLIKELYS -> Remove WEIGHT_CLASSES -> Remove CLEAN_CONDITIONALLY
-    for attempt_num in 0..4 {
-        let attempt_result = try_extract_with_flags(document, options, flags)?;
+    while tried.insert(flags) {
+        let (attempt_result, candidate_count) =
+            try_extract_with_flags(document, options, flags, title)?;
+        let mut text_length = 0;
 
-        if let Some(content) = attempt_result {
-            let text_length = extract_text_length(&content);
+        if let Some(extraction) = attempt_result {
+            text_length = extract_text_length(&extraction.content);
 
             // Check if we have enough content
             if text_length >= options.char_threshold {
-                return Ok(Some(content));
+                return Ok(Some(extraction));
             }
 
             // Save this attempt for potential fallback
             attempts.push(Attempt {
-                content,
+                extraction,
                 text_length,
             });
         }
 
-        // Modify flags for next attempt
-        match attempt_num {
-            0 => flags.remove(ParseFlags::STRIP_UNLIKELYS),
-            1 => flags.remove(ParseFlags::WEIGHT_CLASSES),
-            2 => flags.remove(ParseFlags::CLEAN_CONDITIONALLY),
-            _ => break, // All flags removed, no more attempts
+        let stats = AttemptStats {
+            attempt: tried.len() - 1,
+            flags,
+            text_length,
+            candidate_count,
+        };
+        match strategy.next_flags(&stats) {
+            Some(next) => flags = next,
+            None => break,
         }
     }
 
     // No successful extraction with threshold, return longest attempt
+    if options.strict {
+        return Ok(None);
+    }
     if !attempts.is_empty() {
-        attempts.sort_by(|a, b| b.text_length.cmp(&a.text_length));
+        attempts.sort_by_key(|a| std::cmp::Reverse(a.text_length));
         if attempts[0].text_length > 0 {
-            return Ok(Some(attempts[0].content.clone()));
+            return Ok(Some(attempts.swap_remove(0).extraction));
         }
     }
 
     Ok(None)
 }
 
+/// Find the content column of a documentation page
+///
+/// Docs layouts put the page in `<main>` (or `[role=main]`) between a
+/// navigation sidebar and an "on this page" rail, often with the page itself
+/// in an `<article>` inside. The `<article>` inside the main column is
+/// preferred, as the rail usually shares `<main>` with it, then the main
+/// column, then any `<article>`. Within each kind the element with the most
+/// text is taken, if it has at least `min_text_length` characters.
+fn find_documentation_root(document: &Html, min_text_length: usize) -> Option<ElementRef<'_>> {
+    static DOCUMENTATION_ROOT_SELECTORS: Lazy<[Selector; 3]> = Lazy::new(|| {
+        [
+            Selector::parse(r#"main article, [role="main"] article"#).unwrap(),
+            Selector::parse(r#"main, [role="main"]"#).unwrap(),
+            Selector::parse("article").unwrap(),
+        ]
+    });
+
+    DOCUMENTATION_ROOT_SELECTORS.iter().find_map(|selector| {
+        document
+            .select(selector)
+            .filter(|element| dom_utils::is_probably_visible(*element))
+            .map(|element| (dom_utils::get_inner_text(element, true).len(), element))
+            .max_by_key(|(text_length, _)| *text_length)
+            .filter(|(text_length, _)| *text_length >= min_text_length)
+            .map(|(_, element)| element)
+    })
+}
+
+/// Elements that mark a page layout rather than an article body.
+const FRAGMENT_LAYOUT_TAGS: [&str; 11] = [
+    "nav", "header", "footer", "aside", "menu", "form", "iframe", "button", "input", "select",
+    "textarea",
+];
+
+/// Fewest characters of text per element in a clean fragment.
+const FRAGMENT_MIN_TEXT_PER_ELEMENT: usize = 20;
+
+/// Highest link density of a clean fragment.
+const FRAGMENT_MAX_LINK_DENSITY: f64 = 0.25;
+
+/// Take the whole body of a small, clean fragment as the article
+///
+/// Inputs such as a CMS API's article body are already the content: scoring
+/// them is wasted work, and conditional cleaning can drop parts of them, such
+/// as an image gallery. The body qualifies when it holds at most
+/// `max_elements` elements, none of them layout elements (`<nav>`, `<aside>`,
+/// forms, ...) or classed or identified like boilerplate, with at least
+/// [`FRAGMENT_MIN_TEXT_PER_ELEMENT`] characters of text per element and little
+/// text in links. Whether the input was a fragment rather than a full
+/// document is up to the caller.
+pub(crate) fn grab_clean_fragment(document: &Html, max_elements: usize) -> Option<Extraction> {
+    static BODY_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("body").unwrap());
+
+    let body = document.select(&BODY_SELECTOR).next()?;
+    let elements: Vec<ElementRef> = body
+        .descendants()
+        .skip(1)
+        .filter_map(ElementRef::wrap)
+        .collect();
+    if elements.is_empty() || elements.len() > max_elements {
+        return None;
+    }
+
+    let looks_like_layout = |element: &ElementRef| {
+        let match_string = format!(
+            "{} {}",
+            element.value().attr("class").unwrap_or(""),
+            element.value().attr("id").unwrap_or("")
+        );
+        FRAGMENT_LAYOUT_TAGS.contains(&element.value().name())
+            || REGEXPS.unlikely_candidates.is_match(&match_string)
+            || REGEXPS.negative.is_match(&match_string)
+    };
+    if elements.iter().any(looks_like_layout) {
+        return None;
+    }
+
+    let text_length = dom_utils::get_inner_text(body, true).len();
+    if text_length < elements.len() * FRAGMENT_MIN_TEXT_PER_ELEMENT
+        || dom_utils::get_link_density(body) > FRAGMENT_MAX_LINK_DENSITY
+    {
+        return None;
+    }
+
+    Some(Extraction {
+        content: body.inner_html(),
+        candidate_score: None,
+        path: dom_utils::element_path(body),
+        source: ExtractionSource::CleanFragment,
+    })
+}
+
 /// Try to extract article content with specific flags
+///
+/// Returns the extracted content along with the number of candidates found.
 fn try_extract_with_flags(
     document: &Html,
     options: &ReadabilityOptions,
     flags: ParseFlags,
-) -> Result<Option<String>> {
+    title: Option<&str>,
+) -> Result<(Option<Extraction>, usize)> {
     let candidates = find_candidates(document, options, flags)?;
+    let candidate_count = candidates.len();
     if candidates.is_empty() {
-        return Ok(None);
+        return Ok((None, 0));
     }
 
     let mut scored_candidates = score_candidates(document, candidates, options, flags);
     apply_link_density_penalty(document, &mut scored_candidates);
 
-    if let Some(best) = find_best_candidate(document, &scored_candidates, options) {
-        let content = extract_article_content(document, best, &scored_candidates, options)?;
-        return Ok(Some(content));
+    if let Some((best, passed_over, best_score)) =
+        find_best_candidate(document, &scored_candidates, options, title)
+    {
+        let path = find_element_by_id(document, &best)
+            .map(dom_utils::element_path)
+            .unwrap_or_default();
+        let content = extract_article_content(
+            document,
+            best,
+            passed_over.as_deref(),
+            &scored_candidates,
+            options,
+        )?;
+
+        // Sibling aggregation can drag in link-heavy blocks the best-candidate
+        // check never saw; treat such content as a failed attempt.
+        if content_link_density(&content) > options.max_link_density {
+            return Ok((None, candidate_count));
+        }
+        let extraction = Extraction {
+            content,
+            candidate_score: Some(best_score),
+            path,
+            source: ExtractionSource::Page,
+        };
+        return Ok((Some(extraction), candidate_count));
     }
 
-    Ok(None)
+    Ok((None, candidate_count))
+}
+
+/// Link density of assembled HTML content
+fn content_link_density(html: &str) -> f64 {
+    let doc = Html::parse_fragment(html);
+    dom_utils::get_link_density(doc.root_element())
 }
 
 /// Extract plain text length from HTML content
 fn extract_text_length(html: &str) -> usize {
     let doc = Html::parse_fragment(html);
-    let text: String = doc.root_element().text().collect();
+    let text = utils::replace_nbsp(&doc.root_element().text().collect::<String>());
     text.trim().len()
 }
 
@@ -104,7 +309,7 @@ fn find_candidates<'a>(
 
     let p_selector = Selector::parse("p").unwrap();
     for p in document.select(&p_selector) {
-        if !dom_utils::is_probably_visible(p) {
+        if !dom_utils::is_probably_visible(p) || dom_utils::is_likely_chrome(p) {
             continue;
         }
 
@@ -131,7 +336,7 @@ fn find_candidates<'a>(
     for tag in DEFAULT_TAGS_TO_SCORE.iter() {
         let selector = Selector::parse(tag).unwrap();
         for elem in document.select(&selector) {
-            if !dom_utils::is_probably_visible(elem) {
+            if !dom_utils::is_probably_visible(elem) || dom_utils::is_likely_chrome(elem) {
                 continue;
             }
 
@@ -154,6 +359,39 @@ fn find_candidates<'a>(
         }
     }
 
+    // Web components hold their text in custom elements rather than `<p>`s;
+    // those used as paragraphs are scored like one.
+    let elements = document
+        .root_element()
+        .descendants()
+        .filter_map(ElementRef::wrap);
+    for elem in elements {
+        if !dom_utils::is_custom_element(elem)
+            || dom_utils::is_phrasing_content(elem)
+            || dom_utils::has_child_block_element(elem)
+            || !dom_utils::is_probably_visible(elem)
+            || dom_utils::is_likely_chrome(elem)
+        {
+            continue;
+        }
+
+        if flags.contains(ParseFlags::STRIP_UNLIKELYS) {
+            let class = elem.value().attr("class").unwrap_or("");
+            let id = elem.value().attr("id").unwrap_or("");
+            let match_string = format!("{class} {id}");
+
+            if REGEXPS.unlikely_candidates.is_match(&match_string)
+                && !REGEXPS.ok_maybe_its_a_candidate.is_match(&match_string)
+            {
+                continue;
+            }
+        }
+
+        if dom_utils::get_inner_text(elem, false).len() >= 25 {
+            candidates.push(elem);
+        }
+    }
+
     Ok(candidates)
 }
 
@@ -179,17 +417,30 @@ fn score_candidates<'a>(
         let candidate_id = get_element_id(&candidate);
         let candidate_entry = scores
             .entry(candidate_id)
-            .or_insert_with(|| scoring::initialize_node_score(candidate, flags));
+            .or_insert_with(|| {
+                scoring::initialize_node_score(candidate, flags, &options.base_score_overrides)
+            });
         *candidate_entry += content_score;
 
-        let ancestors = dom_utils::get_node_ancestors(candidate, Some(5));
+        let ancestors: Vec<ElementRef> =
+            dom_utils::get_node_ancestors(candidate, Some(options.ancestor_score_depth));
 
         // Propagate score to ancestors
         // Parent gets 1x, grandparent gets 0.5x, great-grandparent gets 0.33x, etc.
-        for (level, ancestor) in ancestors.iter().enumerate() {
+        // SPA mount points wrap the whole page, so they are transparent: they
+        // take no score, and the ancestors above them keep their own level.
+        for (level, ancestor) in ancestors
+            .iter()
+            .enumerate()
+            .filter(|(_, ancestor)| !dom_utils::is_spa_mount_point(**ancestor))
+        {
             let ancestor_id = get_element_id(ancestor);
             if !scores.contains_key(&ancestor_id) {
-                let base_score = scoring::initialize_node_score(*ancestor, flags);
+                let base_score = scoring::initialize_node_score(
+                    *ancestor,
+                    flags,
+                    &options.base_score_overrides,
+                );
                 scores.insert(ancestor_id.clone(), base_score);
             }
 
@@ -220,11 +471,16 @@ fn apply_link_density_penalty(document: &Html, scores: &mut HashMap<String, f64>
 }
 
 /// Find the best candidate based on scores, promoting parents when needed.
+///
+/// Also returns the candidate that was passed over for a content sibling or
+/// found to duplicate the best candidate, if any, so sibling aggregation can
+/// leave it out, and the final best score.
 fn find_best_candidate(
     document: &Html,
     scores: &HashMap<String, f64>,
     options: &ReadabilityOptions,
-) -> Option<String> {
+    title: Option<&str>,
+) -> Option<(String, Option<String>, f64)> {
     let mut sorted_scores: Vec<_> = scores.iter().collect();
     sorted_scores.sort_by(|a, b| b.1.partial_cmp(a.1).unwrap());
 
@@ -263,12 +519,18 @@ fn find_best_candidate(
         best_score = scores.get(&best_id).copied().unwrap_or(best_score);
     }
 
+    if let Some(replacement) = bypass_thin_main(document, &best_id) {
+        best_id = replacement;
+        best_score = scores.get(&best_id).copied().unwrap_or(0.0);
+    }
+
     // If the best candidate lives inside a single-child parent chain, walk up so we can pull siblings later.
     if let Some(promoted) = promote_single_child_parents(document, &best_id) {
         best_id = promoted;
     }
 
-    if let Some(promoted) = promote_dense_wrapper_child(document, &best_id, scores, &sorted_scores)
+    if let Some(promoted) =
+        promote_dense_wrapper_child(document, &best_id, scores, &sorted_scores, title)
     {
         best_id = promoted;
         best_score = scores.get(&best_id).copied().unwrap_or(best_score);
@@ -280,7 +542,92 @@ fn find_best_candidate(
         best_id = promoted;
     }
 
-    Some(best_id)
+    if let Some((kept, duplicate)) =
+        find_duplicate_candidates(document, &best_id, best_score, &top_candidates)
+    {
+        let kept_score = scores.get(&kept).copied().unwrap_or(best_score);
+        return Some((kept, Some(duplicate), kept_score));
+    }
+
+    if let Some(promoted) = promote_semantic_sibling(document, &best_id, scores) {
+        return Some((promoted, Some(best_id), best_score));
+    }
+
+    Some((best_id, None, best_score))
+}
+
+/// Top candidates scoring below this share of the best score are not compared.
+const DUPLICATE_MIN_SCORE_RATIO: f64 = 0.25;
+
+/// Word overlap at which two candidates count as copies of the same text.
+const DUPLICATE_SIMILARITY: f64 = 0.9;
+
+/// Find two top candidates holding the same text, such as an article and the
+/// hidden print or AMP copy some pages ship alongside it.
+///
+/// Only containers that would end up in the extracted content are compared:
+/// the best candidate itself, its descendants and its siblings. Single
+/// paragraphs are skipped, since pages repeat boilerplate sentences. Aggregating both
+/// doubles the article, so the higher scoring copy is returned to replace the
+/// best candidate together with the duplicate to leave out.
+fn find_duplicate_candidates(
+    document: &Html,
+    best_id: &str,
+    best_score: f64,
+    top_candidates: &[(String, f64)],
+) -> Option<(String, String)> {
+    const MIN_TEXT_LENGTH: usize = 200;
+
+    let best = find_element_by_id(document, best_id)?;
+    let in_content = |element: ElementRef| {
+        element.id() == best.id()
+            || element.ancestors().any(|ancestor| ancestor.id() == best.id())
+            || (element.parent().is_some() && element.parent() == best.parent())
+    };
+
+    let copies: Vec<(String, ElementRef, String)> = top_candidates
+        .iter()
+        .filter(|(_, score)| *score >= best_score * DUPLICATE_MIN_SCORE_RATIO)
+        .filter_map(|(id, _)| {
+            let element = find_element_by_id(document, id)?;
+            let text = dom_utils::get_inner_text(element, true).to_lowercase();
+            let is_copy_sized =
+                dom_utils::has_child_block_element(element) && text.len() >= MIN_TEXT_LENGTH;
+            (is_copy_sized && in_content(element)).then(|| (id.clone(), element, text))
+        })
+        .collect();
+
+    for (i, (kept_id, kept, kept_text)) in copies.iter().enumerate() {
+        for (duplicate_id, duplicate, duplicate_text) in &copies[i + 1..] {
+            let nested = kept.ancestors().any(|node| node.id() == duplicate.id())
+                || duplicate.ancestors().any(|node| node.id() == kept.id());
+            if !nested && text_similarity(kept_text, duplicate_text) >= DUPLICATE_SIMILARITY {
+                return Some((kept_id.clone(), duplicate_id.clone()));
+            }
+        }
+    }
+
+    None
+}
+
+/// Overlap of the word sets of two texts, from `0.0` to `1.0`.
+///
+/// Texts whose lengths differ by more than the duplicate threshold are
+/// rejected before any words are compared.
+fn text_similarity(a: &str, b: &str) -> f64 {
+    let (shorter, longer) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    if longer.is_empty() || (shorter.len() as f64) < longer.len() as f64 * DUPLICATE_SIMILARITY {
+        return 0.0;
+    }
+
+    let a_words: HashSet<&str> = a.split_whitespace().collect();
+    let b_words: HashSet<&str> = b.split_whitespace().collect();
+    let shared = a_words.intersection(&b_words).count();
+    let total = a_words.union(&b_words).count();
+    if total == 0 {
+        return 0.0;
+    }
+    shared as f64 / total as f64
 }
 
 /// Promote parent nodes when the current candidate is the only child, mirroring Mozilla's logic.
@@ -293,7 +640,9 @@ fn promote_single_child_parents(document: &Html, best_id: &str) -> Option<String
             break;
         };
 
-        if parent.value().name().eq_ignore_ascii_case("body") {
+        if parent.value().name().eq_ignore_ascii_case("body")
+            || dom_utils::is_spa_mount_point(parent)
+        {
             break;
         }
 
@@ -432,12 +781,69 @@ fn promote_high_scoring_parents(
     None
 }
 
+/// A `<main>` holding less text than this is checked against its siblings.
+const THIN_MAIN_TEXT_LENGTH: usize = 500;
+
+/// Replace a `<main>` or `role="main"` best candidate that holds little text
+/// while a sibling holds the article.
+///
+/// Some templates put only the breadcrumbs, title and standfirst in `<main>`
+/// and render the body in a sibling `<div>`. The semantic tag and its class
+/// weight can make `<main>` the best candidate anyway, and the body is then
+/// lost unless sibling aggregation happens to pick it up. When a sibling has
+/// at least twice the text at low link density and no negative class or id,
+/// the semantic tag is not trusted and that sibling becomes the candidate
+/// instead. The `<main>` is then judged like any other sibling during
+/// aggregation.
+fn bypass_thin_main(document: &Html, best_id: &str) -> Option<String> {
+    let best_elem = find_element_by_id(document, best_id)?;
+    let is_main = best_elem.value().name().eq_ignore_ascii_case("main")
+        || best_elem
+            .value()
+            .attr("role")
+            .is_some_and(|role| role.eq_ignore_ascii_case("main"));
+    if !is_main {
+        return None;
+    }
+
+    let main_text_length = dom_utils::get_inner_text(best_elem, false).len();
+    if main_text_length >= THIN_MAIN_TEXT_LENGTH {
+        return None;
+    }
+
+    let parent = best_elem.parent()?;
+    let sibling = parent
+        .children()
+        .filter_map(ElementRef::wrap)
+        .filter(|sibling| {
+            sibling.id() != best_elem.id()
+                && matches!(sibling.value().name(), "div" | "section" | "article")
+                && scoring::get_class_weight(*sibling, ParseFlags::WEIGHT_CLASSES) >= 0
+        })
+        .map(|sibling| (sibling, dom_utils::get_inner_text(sibling, false).len()))
+        .filter(|(sibling, text_length)| {
+            *text_length >= THIN_MAIN_TEXT_LENGTH
+                && *text_length >= main_text_length * 2
+                && dom_utils::get_link_density(*sibling) <= 0.33
+        })
+        .max_by_key(|(_, text_length)| *text_length)?
+        .0;
+
+    Some(get_element_id(&sibling))
+}
+
 /// If our best candidate is a wrapper with high link density, look for a better child candidate.
+///
+/// A child holding an `<h1>` or `<h2>` that matches `title` is likely the
+/// article body, so it needs a smaller share of the wrapper's score to be
+/// promoted. It still has to pass the link density, class weight and
+/// paragraph checks, and a higher-scoring child is preferred over it.
 fn promote_dense_wrapper_child(
     document: &Html,
     best_id: &str,
     scores: &HashMap<String, f64>,
     sorted_scores: &[(&String, &f64)],
+    title: Option<&str>,
 ) -> Option<String> {
     let best_elem = find_element_by_id(document, best_id)?;
 
@@ -449,7 +855,7 @@ fn promote_dense_wrapper_child(
     let parent_score = scores.get(best_id).copied().unwrap_or(0.0);
     let best_link_density = dom_utils::get_link_density(best_elem);
 
-    let mut fallback = None;
+    let mut promoted: Option<(String, f64)> = None;
 
     for (candidate_id, candidate_score) in sorted_scores.iter().take(20) {
         if *candidate_id == best_id {
@@ -500,23 +906,26 @@ fn promote_dense_wrapper_child(
             continue;
         }
 
+        // The title heading lowers the share of the wrapper's score a child
+        // needs, but a higher-scoring child still wins.
         let score = **candidate_score;
-        if fallback
+        let holds_title = title
+            .is_some_and(|title| post_processor::contains_title_heading(candidate_elem, title));
+        let required_share = if holds_title { 0.25 } else { 0.45 };
+        if parent_score != 0.0 && score < parent_score * required_share {
+            continue;
+        }
+
+        if promoted
             .as_ref()
             .map(|(_, existing_score)| score > *existing_score)
             .unwrap_or(true)
         {
-            fallback = Some(((*candidate_id).clone(), score));
-        }
-    }
-
-    if let Some((candidate_id, score)) = fallback {
-        if parent_score == 0.0 || score >= parent_score * 0.45 {
-            return Some(candidate_id);
+            promoted = Some(((*candidate_id).clone(), score));
         }
     }
 
-    None
+    promoted.map(|(candidate_id, _)| candidate_id)
 }
 
 fn promote_semantic_descendant(
@@ -618,6 +1027,75 @@ fn promote_semantic_descendant(
     promoted_child.map(|(id, _)| id)
 }
 
+/// Prefer a content sibling such as WordPress's `.entry-content` over the best candidate.
+///
+/// Themes place a link-heavy `.entry-meta` (dates, categories, tags, share links)
+/// next to `.entry-content`; with enough comma-separated link lists the metadata
+/// block can outscore the post body. When the best candidate lacks a content class
+/// itself and a sibling carries one with low link density, the sibling wins.
+fn promote_semantic_sibling(
+    document: &Html,
+    best_id: &str,
+    scores: &HashMap<String, f64>,
+) -> Option<String> {
+    const CONTENT_CLASSES: [&str; 6] = [
+        "entry-content",
+        "post-content",
+        "post-body",
+        "article-body",
+        "articlebody",
+        "story-body",
+    ];
+    let is_content_container = |element: ElementRef| {
+        let match_string = format!(
+            "{} {} {}",
+            element.value().attr("class").unwrap_or(""),
+            element.value().attr("id").unwrap_or(""),
+            element.value().attr("itemprop").unwrap_or("")
+        )
+        .to_lowercase();
+        CONTENT_CLASSES
+            .iter()
+            .any(|class| match_string.contains(class))
+    };
+
+    let best_elem = find_element_by_id(document, best_id)?;
+    if is_content_container(best_elem) {
+        return None;
+    }
+
+    let best_link_density = dom_utils::get_link_density(best_elem);
+    let parent = best_elem.parent()?;
+
+    let mut promoted: Option<(String, f64)> = None;
+    for sibling in parent.children().filter_map(ElementRef::wrap) {
+        if sibling.id() == best_elem.id() || !is_content_container(sibling) {
+            continue;
+        }
+
+        if dom_utils::get_inner_text(sibling, false).len() < 200 {
+            continue;
+        }
+
+        let link_density = dom_utils::get_link_density(sibling);
+        if link_density > 0.25 || link_density >= best_link_density {
+            continue;
+        }
+
+        let sibling_id = get_element_id(&sibling);
+        let score = scores.get(&sibling_id).copied().unwrap_or(0.0);
+        if promoted
+            .as_ref()
+            .map(|(_, existing_score)| score > *existing_score)
+            .unwrap_or(true)
+        {
+            promoted = Some((sibling_id, score));
+        }
+    }
+
+    promoted.map(|(id, _)| id)
+}
+
 /// Extract article content from the best candidate
 ///
 /// This implements Mozilla's sibling aggregation strategy:
@@ -626,31 +1104,37 @@ fn promote_semantic_descendant(
 /// 3. Include siblings that either:
 ///    - Score >= 20% of the best candidate's score, OR
 ///    - Are good paragraphs (low link density, decent text length)
+///
+///    The candidate passed over in favour of the best candidate is never included.
 /// 4. Aggregate all content together
 fn extract_article_content(
     document: &Html,
     best_candidate_id: String,
+    passed_over_id: Option<&str>,
     all_scores: &HashMap<String, f64>,
-    _options: &ReadabilityOptions,
+    options: &ReadabilityOptions,
 ) -> Result<String> {
     let Some(best_candidate) = find_element_by_id(document, &best_candidate_id) else {
         return Ok(String::new());
     };
 
     let best_score = all_scores.get(&best_candidate_id).copied().unwrap_or(0.0);
-    let best_candidate_class = best_candidate
-        .value()
-        .attr("class")
-        .unwrap_or("")
-        .to_string();
+    let best_candidate_class =
+        utils::normalize_class_list(best_candidate.value().attr("class").unwrap_or(""));
 
     let sibling_score_threshold = (best_score * 0.2).max(10.0);
-    let mut article_content = Vec::new();
+    let lang = options
+        .locale
+        .clone()
+        .or_else(|| metadata::extract_language_from_document(document));
+    let mut article_content = String::new();
     let Some(parent) = best_candidate.parent() else {
         // No parent, just return the best candidate
-        let html = element_to_html(best_candidate);
-        let html = crate::cleaner::replace_brs(&html);
-        return Ok(html);
+        return Ok(element_to_html(
+            best_candidate,
+            options.convert_brs,
+            &options.preserve_whitespace_tags,
+        ));
     };
 
     for child_node in parent.children() {
@@ -663,12 +1147,15 @@ fn extract_article_content(
 
         let should_include = if is_best_candidate {
             true
+        } else if passed_over_id == Some(sibling_id.as_str()) {
+            false
         } else {
             let sibling_score = all_scores.get(&sibling_id).copied().unwrap_or(0.0);
             let class_bonus = if !best_candidate_class.is_empty() {
                 sibling
                     .value()
                     .attr("class")
+                    .map(utils::normalize_class_list)
                     .filter(|class_name| {
                         !class_name.is_empty() && *class_name == best_candidate_class
                     })
@@ -680,7 +1167,7 @@ fn extract_article_content(
 
             let weighted_sibling_score = sibling_score + class_bonus;
             if weighted_sibling_score >= sibling_score_threshold
-                || is_good_sibling_paragraph(sibling)
+                || is_good_sibling_paragraph(sibling, lang.as_deref())
             {
                 true
             } else {
@@ -689,16 +1176,24 @@ fn extract_article_content(
         };
 
         if should_include {
-            let mut sibling_html = element_to_html(sibling);
-            sibling_html = crate::cleaner::replace_brs(&sibling_html);
-
-            if !sibling_html.trim().is_empty() {
-                article_content.push(sibling_html);
+            let start = article_content.len();
+            if start > 0 {
+                article_content.push('\n');
+            }
+            // Writing to a String cannot fail.
+            let _ = serialize_into(
+                sibling,
+                options.convert_brs,
+                &options.preserve_whitespace_tags,
+                &mut article_content,
+            );
+            if article_content[start..].trim().is_empty() {
+                article_content.truncate(start);
             }
         }
     }
 
-    Ok(article_content.join("\n"))
+    Ok(article_content)
 }
 
 /// Check if a sibling element is a "good paragraph" worth including
@@ -708,7 +1203,7 @@ fn extract_article_content(
 /// - Has reasonable text length (> 80 chars)
 /// - Has low link density (< 33%)
 /// - Looks like actual content, not navigation
-fn is_good_sibling_paragraph(element: ElementRef) -> bool {
+fn is_good_sibling_paragraph(element: ElementRef, lang: Option<&str>) -> bool {
     let tag_name = element.value().name();
     if tag_name != "p" {
         return false;
@@ -735,7 +1230,7 @@ fn is_good_sibling_paragraph(element: ElementRef) -> bool {
         return true;
     }
 
-    if text_length <= 80 && link_density == 0.0 && has_sentence_boundary(&text) {
+    if text_length <= 80 && link_density == 0.0 && utils::has_sentence_boundary(&text, lang) {
         return true;
     }
 
@@ -788,20 +1283,6 @@ fn should_keep_block_element(element: ElementRef, best_score: f64) -> bool {
     }
 }
 
-/// Detects whether text contains a sentence-ending period followed by whitespace or end.
-fn has_sentence_boundary(text: &str) -> bool {
-    let mut chars = text.chars().peekable();
-    while let Some(ch) = chars.next() {
-        if ch == '.' {
-            match chars.peek() {
-                Some(next) if next.is_whitespace() => return true,
-                None => return true,
-                _ => {}
-            }
-        }
-    }
-    false
-}
 
 /// List of void elements (self-closing tags) in HTML5
 const VOID_ELEMENTS: &[&str] = &[
@@ -830,7 +1311,10 @@ fn should_convert_div_to_p(element: ElementRef) -> bool {
         if let Some(child_elem) = ElementRef::wrap(child) {
             let child_tag = child_elem.value().name().to_uppercase();
 
-            if DIV_TO_P_ELEMS.contains(&child_tag.as_str()) {
+            // A card link wrapping paragraphs holds blocks of its own.
+            if DIV_TO_P_ELEMS.contains(&child_tag.as_str())
+                || (child_tag == "A" && dom_utils::is_block_wrapping_link(child_elem))
+            {
                 return false;
             }
         }
@@ -840,6 +1324,16 @@ fn should_convert_div_to_p(element: ElementRef) -> bool {
     true
 }
 
+/// Check if a custom element is used as a paragraph: it holds text but no
+/// blocks, outside running text. Its tag means nothing without the page's
+/// scripts, so it is serialized as a `<p>`.
+fn is_custom_paragraph(element: ElementRef) -> bool {
+    dom_utils::is_custom_element(element)
+        && !dom_utils::is_phrasing_content(element)
+        && !dom_utils::has_child_block_element(element)
+        && !dom_utils::get_inner_text(element, false).is_empty()
+}
+
 /// Count element children (ignoring text/comment nodes).
 fn count_element_children(element: ElementRef) -> usize {
     element
@@ -892,63 +1386,197 @@ fn is_viable_best_candidate(element: ElementRef, score: f64) -> bool {
 
 /// Serialize an element and its children to proper HTML (without ancestor tags)
 ///
+/// See [`serialize_into`]; this collects the output into a new `String`.
+fn element_to_html(element: ElementRef, convert_brs: bool, preserve_tags: &[String]) -> String {
+    let mut html = String::new();
+    // Writing to a String cannot fail.
+    let _ = serialize_into(element, convert_brs, preserve_tags, &mut html);
+    html
+}
+
+/// Serialize an element and its children onto `out` (without ancestor tags)
+///
 /// The scraper crate's `.html()` method includes ancestor tags as empty elements,
 /// which creates malformed HTML like `<body></body><html></html><div>content</div>`.
-/// This function properly serializes just the element and its descendants.
+/// This function properly serializes just the element and its descendants,
+/// writing straight to `out` instead of building a string per element.
 ///
 /// Additionally, this function implements DIV→P transformation: DIVs without
 /// block-level children are converted to P tags to match Mozilla's behavior.
-fn element_to_html(element: ElementRef) -> String {
-    use scraper::node::Node;
+/// With `convert_brs`, runs of two or more `<br>` children are turned into
+/// paragraphs while serializing (Mozilla's `_replaceBrs`), so no string
+/// reparse is needed later. `<br>`s inside `preserve_tags` are left alone.
+fn serialize_into<W: fmt::Write>(
+    element: ElementRef,
+    convert_brs: bool,
+    preserve_tags: &[String],
+    out: &mut W,
+) -> fmt::Result {
     if !dom_utils::is_probably_visible(element) {
-        return String::new();
+        return Ok(());
     }
 
     let elem_data = element.value();
     let original_tag_name = elem_data.name();
+    let convert_brs = convert_brs && !dom_utils::preserves_whitespace(element, preserve_tags);
+    let splits_brs = convert_brs && has_br_run(element);
 
-    let tag_name = if should_convert_div_to_p(element) {
+    // Paragraphs cannot nest, so a `<p>` split into paragraphs is replaced by
+    // them, as the old string reparse did, and a split DIV stays a DIV.
+    if splits_brs && original_tag_name == "p" {
+        return write_br_paragraphs(element, preserve_tags, out);
+    }
+    let tag_name = if splits_brs {
+        original_tag_name
+    } else if should_convert_div_to_p(element) || is_custom_paragraph(element) {
         "p"
     } else {
         original_tag_name
     };
 
-    let mut html = String::new();
-    html.push_str(&format!("<{tag_name}"));
-
+    write!(out, "<{tag_name}")?;
     for (name, value) in elem_data.attrs.iter() {
-        html.push_str(&format!(" {}=\"{}\"", name.local, escape(value)));
+        write!(out, " {}=\"{}\"", name.local, escape(value))?;
     }
 
     if is_void_element(tag_name) {
-        html.push_str(" />");
-        return html;
+        return out.write_str(" />");
+    }
+
+    out.write_char('>')?;
+
+    if splits_brs {
+        write_br_paragraphs(element, preserve_tags, out)?;
+    } else {
+        for child in element.children() {
+            write_node(child, convert_brs, preserve_tags, out)?;
+        }
+    }
+
+    write!(out, "</{tag_name}>")
+}
+
+/// Serialize a single child node (element, text, or comment) onto `out`.
+fn write_node<W: fmt::Write>(
+    node: ego_tree::NodeRef<scraper::node::Node>,
+    convert_brs: bool,
+    preserve_tags: &[String],
+    out: &mut W,
+) -> fmt::Result {
+    use scraper::node::Node;
+    match node.value() {
+        Node::Element(_) => match ElementRef::wrap(node) {
+            Some(child_elem) => serialize_into(child_elem, convert_brs, preserve_tags, out),
+            None => Ok(()),
+        },
+        Node::Text(text) => write!(out, "{}", escape(&text.text)),
+        Node::Comment(comment) => write!(out, "<!--{}-->", comment.comment),
+        _ => Ok(()),
     }
+}
+
+fn is_br(node: ego_tree::NodeRef<scraper::node::Node>) -> bool {
+    node.value()
+        .as_element()
+        .map(|elem| elem.name().eq_ignore_ascii_case("br"))
+        .unwrap_or(false)
+}
 
-    html.push('>');
+fn is_whitespace_text(node: ego_tree::NodeRef<scraper::node::Node>) -> bool {
+    node.value()
+        .as_text()
+        .map(|text| text.trim().is_empty())
+        .unwrap_or(false)
+}
+
+/// Check whether an element has two or more consecutive `<br>` children
+/// (ignoring whitespace between them).
+fn has_br_run(element: ElementRef) -> bool {
+    if element.value().name() == "code" {
+        return false;
+    }
 
+    let mut run = 0;
     for child in element.children() {
-        match child.value() {
-            Node::Element(_) => {
-                if let Some(child_elem) = ElementRef::wrap(child) {
-                    let child_html = element_to_html(child_elem);
-                    if !child_html.is_empty() {
-                        html.push_str(&child_html);
-                    }
-                }
+        if is_br(child) {
+            run += 1;
+            if run >= 2 {
+                return true;
             }
-            Node::Text(text) => {
-                html.push_str(&escape(&text.text).to_string());
+        } else if !is_whitespace_text(child) {
+            run = 0;
+        }
+    }
+    false
+}
+
+/// Serialize the children of `element`, splitting them into paragraphs at
+/// every run of two or more `<br>` elements.
+///
+/// Single `<br>`s are kept inline. Segments holding block-level content are
+/// emitted as-is, since they cannot live inside a `<p>`. Segments are
+/// separated by a newline so their text does not run together.
+fn write_br_paragraphs<W: fmt::Write>(
+    element: ElementRef,
+    preserve_tags: &[String],
+    out: &mut W,
+) -> fmt::Result {
+    let mut segment = String::new();
+    let mut segment_has_block = false;
+    let mut run = String::new();
+    let mut run_len = 0;
+    let mut written = false;
+
+    let mut flush = |segment: &mut String, has_block: &mut bool, out: &mut W| {
+        let trimmed = segment.trim();
+        let result = if trimmed.is_empty() {
+            Ok(())
+        } else {
+            let separator = if written { "\n    " } else { "" };
+            written = true;
+            if *has_block {
+                write!(out, "{separator}{trimmed}")
+            } else {
+                write!(out, "{separator}<p>{trimmed}</p>")
             }
-            Node::Comment(comment) => {
-                html.push_str(&format!("<!--{}-->", comment.comment));
+        };
+        segment.clear();
+        *has_block = false;
+        result
+    };
+
+    for child in element.children() {
+        if is_br(child) {
+            run_len += 1;
+            write_node(child, true, preserve_tags, &mut run)?;
+            continue;
+        }
+
+        if run_len > 0 && is_whitespace_text(child) {
+            write_node(child, true, preserve_tags, &mut run)?;
+            continue;
+        }
+
+        if run_len >= 2 {
+            flush(&mut segment, &mut segment_has_block, out)?;
+        } else {
+            segment.push_str(&run);
+        }
+        run.clear();
+        run_len = 0;
+
+        if let Some(child_elem) = ElementRef::wrap(child) {
+            if !dom_utils::is_phrasing_content(child_elem) {
+                segment_has_block = true;
             }
-            _ => {}
         }
+        write_node(child, true, preserve_tags, &mut segment)?;
     }
 
-    html.push_str(&format!("</{tag_name}>"));
-    html
+    if run_len == 1 {
+        segment.push_str(&run);
+    }
+    flush(&mut segment, &mut segment_has_block, out)
 }
 
 fn get_element_id(element: &ElementRef) -> String {
@@ -981,7 +1609,10 @@ mod tests {
 
         let document = Html::parse_document(html);
         let options = ReadabilityOptions::builder().char_threshold(100).build();
-        let content = grab_article(&document, &options).unwrap().unwrap();
+        let content = grab_article(&document, &options, None)
+            .unwrap()
+            .unwrap()
+            .content;
 
         // The attribute value must be round-trippable: re-parsing the output
         // must yield exactly the original (decoded) attribute value.
@@ -997,6 +1628,162 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_ancestor_score_depth_reaches_deep_container() {
+        let paragraph = "<p>A substantial paragraph of article text, with commas, clauses, and enough length to score.</p>";
+        let html = format!(
+            "<html><body><article id=\"story\">{}{}{}</article></body></html>",
+            "<div>".repeat(9),
+            paragraph.repeat(3),
+            "</div>".repeat(9)
+        );
+        let document = Html::parse_document(&html);
+        let story = document
+            .select(&Selector::parse("#story").unwrap())
+            .next()
+            .unwrap();
+        let story_id = get_element_id(&story);
+
+        let score_with = |options: &ReadabilityOptions| {
+            let candidates = find_candidates(&document, options, ParseFlags::all()).unwrap();
+            score_candidates(&document, candidates, options, ParseFlags::all())
+        };
+
+        let story_score = |options: ReadabilityOptions| {
+            score_with(&options).get(&story_id).copied().unwrap_or(0.0)
+        };
+
+        // At the default depth the paragraphs sit too far below the article to
+        // pass it any score; only the wrapper divs near the top contribute.
+        let shallow = story_score(ReadabilityOptions::default());
+        let deep = story_score(ReadabilityOptions::builder().ancestor_score_depth(10).build());
+        let unlimited = story_score(ReadabilityOptions::builder().ancestor_score_depth(0).build());
+
+        assert!(deep > shallow);
+        assert_eq!(deep, unlimited);
+    }
+
+    #[test]
+    fn test_sibling_class_bonus_ignores_class_whitespace() {
+        let paragraph = "<p>A substantial paragraph of article text, with commas, clauses, and enough length to score well in the candidate ranking.</p>";
+        let html = format!(
+            "<html><body><main><div class=\"column wide\">{}</div><div class=\"  column\t\twide  \"><span>Filed under the weekly column</span></div><div class=\"other\"><span>Unrelated sibling text block</span></div></main></body></html>",
+            paragraph.repeat(6)
+        );
+        let document = Html::parse_document(&html);
+        let options = ReadabilityOptions::builder().char_threshold(100).build();
+        let content = grab_article(&document, &options, None)
+            .unwrap()
+            .unwrap()
+            .content;
+
+        assert!(content.contains("Filed under the weekly column"));
+        assert!(!content.contains("Unrelated sibling text block"));
+    }
+
+    fn grab_with_short_sibling(lang_attr: &str, sibling: &str, options: &ReadabilityOptions) -> String {
+        let paragraph = "<p>A substantial paragraph of article text, with commas, clauses, and enough length to score well in the candidate ranking.</p>";
+        let html = format!(
+            "<html{lang_attr}><body><main><div>{}</div><p>{sibling}</p></main></body></html>",
+            paragraph.repeat(6)
+        );
+        let document = Html::parse_document(&html);
+        grab_article(&document, options, None)
+            .unwrap()
+            .unwrap()
+            .content
+    }
+
+    #[test]
+    fn test_japanese_sibling_sentence_uses_document_lang() {
+        let options = ReadabilityOptions::builder().char_threshold(100).build();
+        let sibling = "短い段落です。";
+
+        let content = grab_with_short_sibling(" lang=\"ja\"", sibling, &options);
+        assert!(content.contains(sibling));
+
+        let content = grab_with_short_sibling("", sibling, &options);
+        assert!(!content.contains(sibling));
+    }
+
+    #[test]
+    fn test_thai_sibling_sentence_uses_locale_option() {
+        let sibling = "วันนี้อากาศดี พรุ่งนี้ฝนตก";
+
+        let options = ReadabilityOptions::builder()
+            .char_threshold(100)
+            .locale("th")
+            .build();
+        let content = grab_with_short_sibling("", sibling, &options);
+        assert!(content.contains(sibling));
+
+        let options = ReadabilityOptions::builder().char_threshold(100).build();
+        let content = grab_with_short_sibling("", sibling, &options);
+        assert!(!content.contains(sibling));
+    }
+
+    fn serialize_first(html: &str, selector: &str) -> String {
+        let doc = Html::parse_fragment(html);
+        let selector = Selector::parse(selector).unwrap();
+        let preserve_tags = ReadabilityOptions::default().preserve_whitespace_tags;
+        element_to_html(doc.select(&selector).next().unwrap(), true, &preserve_tags)
+    }
+
+    #[test]
+    fn test_br_runs_become_paragraphs() {
+        let result = serialize_first("<div>Line 1<br><br>Line 2</div>", "div");
+        assert_eq!(result, "<div><p>Line 1</p>\n    <p>Line 2</p></div>");
+    }
+
+    #[test]
+    fn test_br_runs_with_whitespace() {
+        let result = serialize_first("<div>Para 1<br> <br>Para 2<br><br><br>Para 3</div>", "div");
+        assert_eq!(
+            result,
+            "<div><p>Para 1</p>\n    <p>Para 2</p>\n    <p>Para 3</p></div>"
+        );
+    }
+
+    #[test]
+    fn test_single_br_is_kept_inline() {
+        let result = serialize_first(
+            "<div>Lorem ipsum<br/>dolor sit<br/> <br/><br/>amet, consectetur</div>",
+            "div",
+        );
+        assert_eq!(
+            result,
+            "<div><p>Lorem ipsum<br />dolor sit</p>\n    <p>amet, consectetur</p></div>"
+        );
+    }
+
+    #[test]
+    fn test_br_runs_preserve_attributes() {
+        let result = serialize_first(
+            r#"<div class="content" id="main">Text 1<br><br>Text &amp; 2</div>"#,
+            "div",
+        );
+        assert!(result.starts_with(r#"<div class="content" id="main">"#));
+        assert!(result.contains("<p>Text 1</p>\n    <p>Text &amp; 2</p>"));
+    }
+
+    #[test]
+    fn test_br_runs_in_paragraph_do_not_nest() {
+        let result = serialize_first("<section><p>One<br><br>Two</p></section>", "p");
+        assert_eq!(result, "<p>One</p>\n    <p>Two</p>");
+    }
+
+    #[test]
+    fn test_content_without_brs_is_unchanged() {
+        let result = serialize_first(
+            r#"<article><p>First <em>para</em></p><div><p>Nested</p></div></article>"#,
+            "article",
+        );
+        assert_eq!(
+            result,
+            "<article><p>First <em>para</em></p><div><p>Nested</p></div></article>"
+        );
+    }
+
     #[test]
     fn test_grab_article_simple() {
         let html = r#"
@@ -1015,16 +1802,257 @@ mod tests {
         let document = Html::parse_document(html);
         let options = ReadabilityOptions::builder().char_threshold(100).build();
 
-        let result = grab_article(&document, &options);
+        let result = grab_article(&document, &options, None);
         assert!(result.is_ok());
 
         let content = result.unwrap();
         assert!(content.is_some());
 
-        let content_html = content.unwrap();
+        let content_html = content.unwrap().content;
         assert!(content_html.contains("first paragraph"));
     }
 
+    #[test]
+    fn test_grab_article_from_custom_elements() {
+        let html = r#"<html><body>
+<site-nav><a href="/">Home</a> <a href="/birds">Birds</a></site-nav>
+<news-article>
+<article-body>
+<text-block>Grey herons hunt the estuary at first light, standing motionless in the <glossary-term>shallows</glossary-term> until a fish comes close.</text-block>
+<text-block>They nest in colonies high in the trees along the river, returning to the same heronry year after year to rebuild old nests.</text-block>
+<text-block>Young herons leave the nest after about seven weeks, but stay close to the colony, begging for food, for several weeks more.</text-block>
+<slot><p>Herons are protected by law, and disturbing a nest, even by accident, is an offence.</p></slot>
+</article-body>
+</news-article>
+</body></html>"#;
+
+        let document = Html::parse_document(html);
+        let options = ReadabilityOptions::builder().char_threshold(100).build();
+        let extraction = grab_article(&document, &options, None).unwrap().unwrap();
+
+        assert_eq!(extraction.path, "/html/body/news-article");
+        let content = extraction.content;
+        assert_eq!(content.matches("<p>").count(), 4);
+        assert!(content.contains("<p>Grey herons hunt the estuary at first light, standing motionless in the <glossary-term>shallows</glossary-term> until a fish comes close.</p>"));
+        assert!(!content.contains("Home"));
+    }
+
+    #[test]
+    fn test_grab_article_custom_retry_strategy() {
+        use std::sync::{Arc, Mutex};
+
+        /// Relaxes conditional cleaning and unlikely stripping, never class weights.
+        #[derive(Debug, Default)]
+        struct KeepClassWeights {
+            seen: Mutex<Vec<AttemptStats>>,
+        }
+
+        impl RetryStrategy for KeepClassWeights {
+            fn next_flags(&self, stats: &AttemptStats) -> Option<ParseFlags> {
+                self.seen.lock().unwrap().push(*stats);
+                [ParseFlags::CLEAN_CONDITIONALLY, ParseFlags::STRIP_UNLIKELYS]
+                    .into_iter()
+                    .find(|flag| stats.flags.contains(*flag))
+                    .map(|flag| stats.flags - flag)
+            }
+        }
+
+        let html = r#"<html><body><article>
+            <p>A short article that never reaches the configured threshold, no matter which flags are used.</p>
+            <p>It still has enough text to be extracted as the best available attempt.</p>
+        </article></body></html>"#;
+        let document = Html::parse_document(html);
+
+        let strategy = Arc::new(KeepClassWeights::default());
+        let options = ReadabilityOptions::builder()
+            .char_threshold(10_000)
+            .retry_strategy(strategy.clone())
+            .build();
+        let content = grab_article(&document, &options, None)
+            .unwrap()
+            .unwrap()
+            .content;
+        assert!(content.contains("short article"));
+
+        let seen = strategy.seen.lock().unwrap();
+        let flags: Vec<ParseFlags> = seen.iter().map(|stats| stats.flags).collect();
+        assert_eq!(
+            flags,
+            vec![
+                ParseFlags::all(),
+                ParseFlags::STRIP_UNLIKELYS | ParseFlags::WEIGHT_CLASSES,
+                ParseFlags::WEIGHT_CLASSES,
+            ]
+        );
+        assert_eq!(seen.iter().map(|s| s.attempt).collect::<Vec<_>>(), vec![0, 1, 2]);
+        assert!(seen.iter().all(|s| s.text_length > 0 && s.candidate_count > 0));
+    }
+
+    #[test]
+    fn test_link_heavy_article_fails_the_attempt() {
+        let html = r#"<html><body><div class="roundup">
+<p>This week, <a href="/a">the city council voted to extend the bike lane network downtown</a>, and <a href="/b">the library announced longer weekend opening hours</a>.</p>
+<p>Elsewhere, <a href="/c">the farmers market returns to the square on Saturday mornings</a>, while <a href="/d">the river path reopens after months of repair work</a>.</p>
+<p>Finally, <a href="/e">the school board published its budget for the coming year</a>, and <a href="/f">the museum unveiled a new wing for local history</a>.</p>
+</div></body></html>"#;
+        let document = Html::parse_document(html);
+
+        let content = grab_article(
+            &document,
+            &ReadabilityOptions::builder()
+                .char_threshold(100)
+                .max_link_density(1.0)
+                .build(),
+            None,
+        )
+        .unwrap()
+        .unwrap()
+        .content;
+        assert!(content_link_density(&content) > 0.5);
+
+        let options = ReadabilityOptions::builder().char_threshold(100).build();
+        assert!(grab_article(&document, &options, None).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_base_score_override_for_blockquote() {
+        let html = r#"<html><body><div class="thread">
+<div class="question">
+  <p>How do I keep basil from bolting? Mine grows well for a few weeks, then flowers, and the leaves turn bitter, no matter how much I water it.</p>
+  <p>It sits on a sunny windowsill, in a small pot, with regular potting soil, and I pick a few leaves, here and there, for cooking.</p>
+</div>
+<blockquote class="accepted">
+  <p>Pinch off the flower buds as soon as they appear, and harvest from the top, just above a pair of leaves, so the plant branches instead of flowering.</p>
+  <p>A bigger pot and a little afternoon shade also help, since heat and cramped roots both push basil to flower early.</p>
+</blockquote>
+</div></body></html>"#;
+        let document = Html::parse_document(html);
+
+        let flags = ParseFlags::all();
+        let score_of = |options: &ReadabilityOptions, selector: &str| {
+            let candidates = find_candidates(&document, options, flags).unwrap();
+            let scores = score_candidates(&document, candidates, options, flags);
+            let sel = Selector::parse(selector).unwrap();
+            let element = document.select(&sel).next().unwrap();
+            scores[&get_element_id(&element)]
+        };
+
+        let defaults = ReadabilityOptions::default();
+        assert!(score_of(&defaults, "blockquote") < score_of(&defaults, ".question"));
+
+        let options = ReadabilityOptions::builder()
+            .base_score_overrides(HashMap::from([("blockquote".to_string(), 30.0)]))
+            .build();
+        assert!(score_of(&options, "blockquote") > score_of(&options, ".question"));
+        let gain = score_of(&options, "blockquote") - score_of(&defaults, "blockquote");
+        assert!((gain - 27.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_spa_mount_point_is_not_the_candidate() {
+        let html = r#"<html><head><title>Shipping faster with incremental builds</title></head><body>
+<div id="__next">
+  <div class="topbar"><a href="/">Home</a> <a href="/blog">Blog</a> <a href="/docs">Docs</a> <a href="/pricing">Pricing</a></div>
+  <div class="intro">
+    <p>Incremental builds changed how our team ships software. Instead of rebuilding the entire site on every change, we rebuild only the pages whose data actually changed, which cut our deploy times dramatically.</p>
+    <p>The idea is simple, but the details matter: you need a reliable dependency graph between pages and data sources, and a cache that survives across deploys without serving stale content.</p>
+  </div>
+  <div class="details">
+    <p>We started by tagging every data fetch with the set of pages that used it. When a source changed, the tags told us exactly which pages to regenerate, and everything else was served from the cache.</p>
+    <p>The cache itself lives next to the build output, keyed by a hash of the inputs. A page is only rebuilt when its inputs hash differently, so unrelated edits never trigger a rebuild.</p>
+  </div>
+  <div class="newsletter">Subscribe to our newsletter for more posts like this one, delivered weekly to your inbox.</div>
+</div>
+</body></html>"#;
+
+        let document = Html::parse_document(html);
+        let options = ReadabilityOptions::builder().char_threshold(100).build();
+        let content = grab_article(&document, &options, None)
+            .unwrap()
+            .unwrap()
+            .content;
+
+        assert!(content.contains("Incremental builds changed"));
+        assert!(content.contains("keyed by a hash of the inputs"));
+        assert!(!content.contains("__next"));
+        assert!(!content.contains("Pricing"));
+        assert!(!content.contains("Subscribe to our newsletter"));
+    }
+
+    #[test]
+    fn test_entry_content_preferred_over_entry_meta_sibling() {
+        let html = r#"<html><head><title>Repotting a fiddle-leaf fig</title></head><body>
+<div id="page" class="site"><main id="main" class="site-main"><div class="inside">
+  <h1 class="entry-title">Repotting a fiddle-leaf fig</h1>
+  <div class="entry-meta">
+    <p>Posted on <a href="/2024/03/">March 4, 2024</a> by <a href="/author/sam/">Sam</a>, filed under <a href="/c/plants/">Plants</a>, <a href="/c/indoor/">Indoor</a>, <a href="/c/howto/">How-to</a></p>
+    <p>Tags: <a href="/t/ficus/">ficus</a>, <a href="/t/repotting/">repotting</a>, <a href="/t/soil/">soil</a>, <a href="/t/roots/">roots</a>, <a href="/t/pots/">pots</a>, <a href="/t/spring/">spring</a></p>
+    <p>Share this, if you liked it: <a href="/s/tw">Twitter</a>, <a href="/s/fb">Facebook</a>, <a href="/s/pin">Pinterest</a>, <a href="/s/mail">Email</a></p>
+    <p>About the author: Sam writes about houseplants, balcony gardens, composting, seed swaps, and, occasionally, the slow heartbreak of overwatering, from a small flat in Leeds, with three cats.</p>
+    <p>Sam has also written, for magazines, newsletters, and, once, a seed catalogue, about herbs, cacti, succulents, orchids, ferns, and, mostly, the plants nobody else wants to adopt.</p>
+    <p>Comments, questions, corrections, or, better still, photos of your own repotting disasters, are welcome below, by email, or, if you must, on social media.</p>
+  </div>
+  <div class="entry-content">
+    <p>Fiddle-leaf figs hate being moved, so repot only when roots start circling the bottom of the pot, usually every other spring. Choose a container no more than two inches wider than the old one, with a drainage hole, and mix a chunky soil of bark, perlite, and potting mix so the roots can breathe between waterings.</p>
+    <p>Water the plant the day before, then tip it out gently, tease the outer roots loose, and set it at the same depth in the new pot. Fill around the root ball, firm the soil lightly, and water until it drains. Keep it out of direct sun for a week while it settles in, and expect a dropped leaf or two.</p>
+  </div>
+  <ul class="post-list">
+    <li><a href="/p/1">Why your fiddle-leaf fig is dropping leaves</a></li>
+    <li><a href="/p/2">The best soil mixes for indoor trees</a></li>
+    <li><a href="/p/3">How to prune a ficus without killing it</a></li>
+    <li><a href="/p/4">Choosing the right pot for every houseplant</a></li>
+    <li><a href="/p/5">Watering schedules for busy people</a></li>
+    <li><a href="/p/6">Light, humidity, and the indoor jungle</a></li>
+    <li><a href="/p/7">Common pests and how to beat them</a></li>
+    <li><a href="/p/8">Propagating figs from cuttings</a></li>
+    <li><a href="/p/9">Fertiliser basics for beginners</a></li>
+    <li><a href="/p/10">When to move plants outside for summer</a></li>
+    <li><a href="/p/11">Reviving a plant after a long holiday</a></li>
+    <li><a href="/p/12">Ten houseplants that tolerate low light</a></li>
+  </ul>
+</div></main></div>
+</body></html>"#;
+
+        let document = Html::parse_document(html);
+        let options = ReadabilityOptions::builder().char_threshold(100).build();
+        let content = grab_article(&document, &options, None)
+            .unwrap()
+            .unwrap()
+            .content;
+
+        assert!(content.starts_with(r#"<div class="entry-content">"#));
+        assert!(content.contains("Fiddle-leaf figs hate being moved"));
+        assert!(content.contains("expect a dropped leaf or two"));
+        assert!(!content.contains("Posted on"));
+        assert!(!content.contains("About the author"));
+        assert!(!content.contains("Propagating figs from cuttings"));
+    }
+
+    #[test]
+    fn test_hidden_print_copy_is_not_aggregated() {
+        let story = r#"<p>The council voted on Tuesday to expand the bike lane network, adding twelve miles of protected lanes along the river and through the old market district.</p>
+<p>Construction starts in the spring. Residents near the market worried about parking, while cycling groups argued that the plan still leaves the northern suburbs without a safe route.</p>
+<p>The project is funded by a regional transport grant, and the first section, between the station and the university, should open before the end of the year.</p>"#;
+        let html = format!(
+            r#"<html><head><title>Council expands bike lanes</title></head><body><div id="page">
+<div class="nav"><a href="/">Home</a> <a href="/news">News</a></div>
+<div class="story">{story}</div>
+<div class="print-only"><p>Printed from example.com</p>{story}</div>
+</div></body></html>"#
+        );
+
+        let document = Html::parse_document(&html);
+        let options = ReadabilityOptions::builder().char_threshold(100).build();
+        let content = grab_article(&document, &options, None)
+            .unwrap()
+            .unwrap()
+            .content;
+
+        assert_eq!(content.matches("The council voted on Tuesday").count(), 1);
+        assert!(content.contains("before the end of the year"));
+        assert!(!content.contains("Printed from"));
+    }
+
     #[test]
     fn test_grab_article_short_content() {
         let html = r#"
@@ -1038,7 +2066,7 @@ mod tests {
         let document = Html::parse_document(html);
         let options = ReadabilityOptions::default();
 
-        let result = grab_article(&document, &options);
+        let result = grab_article(&document, &options, None);
         assert!(result.is_ok());
 
         assert!(result.unwrap().is_none());
@@ -1067,10 +2095,10 @@ mod tests {
         let flags = ParseFlags::WEIGHT_CLASSES | ParseFlags::CLEAN_CONDITIONALLY;
 
         let candidates = find_candidates(&document, &options, flags).unwrap();
-        assert!(candidates.len() > 0);
+        assert!(!candidates.is_empty());
 
         let scores = score_candidates(&document, candidates, &options, flags);
-        assert!(scores.len() > 0);
+        assert!(!scores.is_empty());
     }
 
     #[test]
@@ -1095,13 +2123,13 @@ mod tests {
         let document = Html::parse_document(html);
         let options = ReadabilityOptions::builder().char_threshold(100).build();
 
-        let result = grab_article(&document, &options);
+        let result = grab_article(&document, &options, None);
         assert!(result.is_ok());
 
         let content = result.unwrap();
         assert!(content.is_some());
 
-        let content_html = content.unwrap();
+        let content_html = content.unwrap().content;
 
         assert!(content_html.contains("first paragraph"));
         assert!(content_html.contains("second paragraph"));
@@ -1131,14 +2159,130 @@ mod tests {
         let document = Html::parse_document(html);
         let options = ReadabilityOptions::builder().char_threshold(100).build();
 
-        let result = grab_article(&document, &options);
+        let result = grab_article(&document, &options, None);
         assert!(result.is_ok());
 
         let content = result.unwrap();
         assert!(content.is_some());
 
-        let content_html = content.unwrap();
+        let content_html = content.unwrap().content;
         assert!(!content_html.contains("<script>"));
         assert!(content_html.contains("&lt;script&gt;"));
     }
+
+    #[test]
+    fn test_dense_wrapper_promotes_child_with_title_heading() {
+        let cards: String = (0..60)
+            .map(|i| format!(r#"<div class="card"><h3><a href="/p/{i}">Related story number {i}: everything we learned about the orchard and its trees this year</a></h3><p>Apples, pears, plums, and cherries, pruned and trained.</p></div>"#))
+            .collect();
+        let html = format!(
+            r#"<html><body><div id="page"><div class="wrap">
+<div class="story">
+<h1>Pruning apple trees in winter</h1>
+<p>Winter is the best time to prune, because without leaves you can see the whole <a href="/g/structure">structure of the tree</a>. Start with <a href="/g/dead-wood">dead, damaged or diseased wood</a>, then remove branches that cross or rub.</p>
+<p>Aim for an open, goblet shape that lets light reach the centre. Never remove more than a quarter of the canopy in one year, or the tree will respond with a thicket of <a href="/g/water-shoots">water shoots</a> next summer.</p>
+<p>Use <a href="/tools/secateurs">sharp secateurs</a> for thin growth and a <a href="/tools/saws">pruning saw</a> for anything thicker than a finger, and clean the blades between trees.</p>
+</div>
+<div class="cards">{cards}</div>
+</div></div></body></html>"#
+        );
+        let document = Html::parse_document(&html);
+        let options = ReadabilityOptions::default();
+        let candidates = find_candidates(&document, &options, ParseFlags::all()).unwrap();
+        let mut scores = score_candidates(&document, candidates, &options, ParseFlags::all());
+        apply_link_density_penalty(&document, &mut scores);
+
+        let best_name = |title: Option<&str>| {
+            let (best_id, _, _) = find_best_candidate(&document, &scores, &options, title).unwrap();
+            let best = find_element_by_id(&document, &best_id).unwrap();
+            let name = best.value().id().or(best.value().attr("class"));
+            name.unwrap_or_default().to_string()
+        };
+
+        // The story scores well below the link-heavy wrapper, so only the
+        // title heading marks it as the article.
+        assert_eq!(best_name(None), "page");
+        assert_eq!(best_name(Some("Other headline")), "page");
+        assert_eq!(best_name(Some("Pruning apple trees in winter")), "story");
+    }
+
+    #[test]
+    fn test_dense_wrapper_prefers_body_over_boilerplate_title_frame() {
+        let cards: String = (0..40)
+            .map(|i| format!(r#"<div class="card"><h3><a href="/p/{i}">Related story number {i}: everything we learned about the orchard and its trees this year</a></h3><p>Apples, pears, plums, and cherries, pruned and trained.</p></div>"#))
+            .collect();
+        let nav: String = ["News", "Tools", "Trees", "Pests", "Soil", "Forum", "Shop"]
+            .iter()
+            .map(|section| format!(r#"<a href="/{section}">{section}</a> "#))
+            .collect();
+        let html = format!(
+            r#"<html><body><div id="page"><div class="wrap">
+<div class="frame">
+<nav>{nav}</nav>
+<h1>Pruning apple trees in winter</h1>
+<p>12.01.2024 09:30 Uhr, <a href="/authors/garden-desk">Garden desk</a>, <a href="/comments">read the 48 comments on this article</a></p>
+<div class="body">
+<p>Winter is the best time to prune, because without leaves you can see the whole structure of the tree. Start with dead, damaged or diseased wood, then remove branches that cross or rub.</p>
+<p>Aim for an open, goblet shape that lets light reach the centre. Never remove more than a quarter of the canopy in one year, or the tree will respond with a thicket of water shoots next summer.</p>
+<p>Use sharp secateurs for thin growth and a pruning saw for anything thicker than a finger, and clean the blades between trees so that canker does not spread.</p>
+<p>Stone fruit is the exception: cherries and plums are pruned in summer, when silver leaf spores are less active and the cuts heal quickly.</p>
+<p>Young trees need formative pruning for their first three winters, shortening the leaders by a third to build a strong framework of branches.</p>
+</div>
+<p><a href="/newsletter">Newsletter</a>, <a href="/print">print</a>, <a href="/share">share</a>, <a href="/feedback">send feedback</a>, <a href="/archive">more from the archive</a></p>
+</div>
+<div class="cards">{cards}</div>
+</div></div></body></html>"#
+        );
+        let document = Html::parse_document(&html);
+        let options = ReadabilityOptions::default();
+        let candidates = find_candidates(&document, &options, ParseFlags::all()).unwrap();
+        let mut scores = score_candidates(&document, candidates, &options, ParseFlags::all());
+        apply_link_density_penalty(&document, &mut scores);
+
+        let best_class = |title: Option<&str>| {
+            let (best_id, _, _) = find_best_candidate(&document, &scores, &options, title).unwrap();
+            let best = find_element_by_id(&document, &best_id).unwrap();
+            best.value().attr("class").unwrap_or_default().to_string()
+        };
+
+        // The frame holds the heading, but also the navigation, dateline and
+        // share links; the body below it scores higher and stays the pick.
+        assert_eq!(best_class(None), "body");
+        assert_eq!(best_class(Some("Pruning apple trees in winter")), "body");
+    }
+
+    #[test]
+    fn test_thin_main_defers_to_body_sibling() {
+        let paragraph = "<p>The council approved the new cycle lanes on Tuesday, after months of consultation, objections and redrawn plans.</p>";
+        let html = format!(
+            r#"<html><body><div id="page">
+<main role="main" id="main-content" class="article-header">
+<nav class="breadcrumbs"><a href="/">Home</a> / <a href="/news">News</a></nav>
+<h1>Cycle lanes approved</h1>
+<p>A short standfirst, with a comma, that sits above the story.</p>
+</main>
+<div class="story">{}</div>
+</div></body></html>"#,
+            paragraph.repeat(8)
+        );
+        let document = Html::parse_document(&html);
+        let options = ReadabilityOptions::default();
+        let candidates = find_candidates(&document, &options, ParseFlags::all()).unwrap();
+        let mut scores = score_candidates(&document, candidates, &options, ParseFlags::all());
+
+        // Stand in for a class weight that lifts the header above the story.
+        let main = document
+            .select(&Selector::parse("main").unwrap())
+            .next()
+            .unwrap();
+        scores.insert(get_element_id(&main), 1000.0);
+
+        let (best_id, _, _) = find_best_candidate(&document, &scores, &options, None).unwrap();
+        let best = find_element_by_id(&document, &best_id).unwrap();
+        assert_eq!(best.value().attr("class"), Some("story"));
+
+        let content = extract_article_content(&document, best_id, None, &scores, &options).unwrap();
+        assert!(content.contains("Cycle lanes approved"));
+        assert_eq!(content.matches("cycle lanes on Tuesday").count(), 8);
+    }
 }
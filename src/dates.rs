@@ -0,0 +1,181 @@
+//! Parsing of publish and modified dates (requires the `dates` feature).
+//!
+//! Sites write dates in many shapes: RFC 3339 from JSON-LD and OpenGraph,
+//! RFC 2822 from feeds, bare ISO dates, and human-readable forms such as
+//! `March 1, 2024` in `<time>` elements. [`parse_date`] normalizes the common
+//! ones to a [`DateTime`] with the offset the page declared. Values without an
+//! offset are taken as UTC, and dates without a time as midnight.
+
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime};
+use scraper::{Html, Selector};
+
+/// Formats carrying an explicit offset, tried after RFC 3339 and RFC 2822.
+const OFFSET_FORMATS: &[&str] = &[
+    "%Y-%m-%dT%H:%M:%S%.f%z",
+    "%Y-%m-%dT%H:%M%z",
+    "%Y-%m-%d %H:%M:%S%.f%z",
+    "%Y-%m-%d %H:%M:%S%.f %z",
+    "%Y-%m-%d %H:%M%z",
+];
+
+/// Formats without an offset, read as UTC.
+const NAIVE_DATETIME_FORMATS: &[&str] = &[
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M",
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%d %H:%M",
+    "%Y/%m/%d %H:%M:%S",
+    "%Y/%m/%d %H:%M",
+    "%B %d, %Y %I:%M %p",
+    "%B %d, %Y at %I:%M %p",
+    "%d %B %Y %H:%M",
+];
+
+/// Date-only formats, read as midnight UTC.
+const DATE_FORMATS: &[&str] = &[
+    "%Y-%m-%d",
+    "%Y/%m/%d",
+    "%Y.%m.%d",
+    "%Y%m%d",
+    "%d.%m.%Y",
+    "%B %d, %Y",
+    "%B %d %Y",
+    "%d %B %Y",
+    "%d %B, %Y",
+    "%A, %B %d, %Y",
+    "%A, %d %B %Y",
+];
+
+/// Parse a date string as found in page metadata.
+///
+/// Returns `None` when no known format matches; callers keep the raw string
+/// for those.
+pub(crate) fn parse_date(raw: &str) -> Option<DateTime<FixedOffset>> {
+    let value = raw.trim();
+    if value.is_empty() {
+        return None;
+    }
+
+    if let Ok(date) = DateTime::parse_from_rfc3339(value) {
+        return Some(date);
+    }
+    if let Ok(date) = DateTime::parse_from_rfc2822(value) {
+        return Some(date);
+    }
+
+    // Unix timestamps in seconds or milliseconds.
+    if value.bytes().all(|b| b.is_ascii_digit()) {
+        let timestamp = match value.len() {
+            10 => value.parse::<i64>().ok()?,
+            13 => value.parse::<i64>().ok()? / 1000,
+            _ => return parse_naive(value),
+        };
+        return DateTime::from_timestamp(timestamp, 0).map(|date| date.fixed_offset());
+    }
+
+    // `%z` does not accept `Z` or a named UTC zone.
+    let value = match value
+        .strip_suffix('Z')
+        .or_else(|| value.strip_suffix(" UTC"))
+        .or_else(|| value.strip_suffix(" GMT"))
+    {
+        Some(rest) => format!("{}+00:00", rest.trim_end()),
+        None => value.to_string(),
+    };
+    OFFSET_FORMATS
+        .iter()
+        .find_map(|format| DateTime::parse_from_str(&value, format).ok())
+        .or_else(|| parse_naive(value.trim_end_matches("+00:00")))
+}
+
+fn parse_naive(value: &str) -> Option<DateTime<FixedOffset>> {
+    // Month abbreviations are often written with a period (`Mar. 1, 2024`).
+    let value = value.replacen(". ", " ", 1);
+    let naive = NAIVE_DATETIME_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(&value, format).ok())
+        .or_else(|| {
+            DATE_FORMATS.iter().find_map(|format| {
+                NaiveDate::parse_from_str(&value, format)
+                    .ok()
+                    .and_then(|date| date.and_hms_opt(0, 0, 0))
+            })
+        })?;
+    Some(naive.and_utc().fixed_offset())
+}
+
+/// Parse the `datetime` of the first `<time>` element matching `selector`.
+pub(crate) fn time_element_date(document: &Html, selector: &str) -> Option<DateTime<FixedOffset>> {
+    let selector = Selector::parse(selector).ok()?;
+    document
+        .select(&selector)
+        .filter_map(|time| time.value().attr("datetime"))
+        .find_map(parse_date)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rfc3339(raw: &str) -> Option<String> {
+        parse_date(raw).map(|date| date.to_rfc3339())
+    }
+
+    #[test]
+    fn test_parse_date_formats() {
+        let cases = [
+            ("2024-03-01T09:30:00Z", "2024-03-01T09:30:00+00:00"),
+            (
+                "2024-03-01T09:30:00.123+01:00",
+                "2024-03-01T09:30:00.123+01:00",
+            ),
+            ("2024-03-01T09:30:00+0100", "2024-03-01T09:30:00+01:00"),
+            ("2024-03-01T09:30Z", "2024-03-01T09:30:00+00:00"),
+            ("2024-03-01T09:30:00", "2024-03-01T09:30:00+00:00"),
+            ("2024-03-01 09:30:00 +0530", "2024-03-01T09:30:00+05:30"),
+            ("2024-03-01 09:30:00 UTC", "2024-03-01T09:30:00+00:00"),
+            ("Fri, 01 Mar 2024 09:30:00 GMT", "2024-03-01T09:30:00+00:00"),
+            (
+                "Fri, 01 Mar 2024 09:30:00 -0500",
+                "2024-03-01T09:30:00-05:00",
+            ),
+            ("2024-03-01", "2024-03-01T00:00:00+00:00"),
+            ("2024/03/01", "2024-03-01T00:00:00+00:00"),
+            ("01.03.2024", "2024-03-01T00:00:00+00:00"),
+            ("March 1, 2024", "2024-03-01T00:00:00+00:00"),
+            ("Mar. 1, 2024", "2024-03-01T00:00:00+00:00"),
+            ("1 March 2024", "2024-03-01T00:00:00+00:00"),
+            ("Friday, March 1, 2024", "2024-03-01T00:00:00+00:00"),
+            ("March 1, 2024 9:30 AM", "2024-03-01T09:30:00+00:00"),
+            ("1709285400", "2024-03-01T09:30:00+00:00"),
+            ("1709285400000", "2024-03-01T09:30:00+00:00"),
+        ];
+        for (raw, expected) in cases {
+            assert_eq!(rfc3339(raw).as_deref(), Some(expected), "{raw}");
+        }
+    }
+
+    #[test]
+    fn test_parse_date_rejects_unknown() {
+        for raw in [
+            "",
+            "yesterday",
+            "Updated 3 hours ago",
+            "2024-13-45",
+            "12345",
+        ] {
+            assert_eq!(parse_date(raw), None, "{raw}");
+        }
+    }
+
+    #[test]
+    fn test_time_element_date() {
+        let document = Html::parse_document(
+            r#"<article><time>Friday</time><time datetime="2024-03-01">March 1</time></article>"#,
+        );
+        assert_eq!(
+            time_element_date(&document, "time[datetime]").map(|date| date.to_rfc3339()),
+            Some("2024-03-01T00:00:00+00:00".to_string())
+        );
+    }
+}
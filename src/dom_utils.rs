@@ -1,7 +1,31 @@
 //! DOM manipulation and traversal utilities.
 
-use crate::constants::{PHRASING_ELEMS, REGEXPS};
+use crate::constants::{PHRASING_ELEMS, REGEXPS, SPA_MOUNT_POINT_IDS};
+use crate::options::TextNormalizer;
+use once_cell::sync::Lazy;
+use regex::Regex;
 use scraper::{ElementRef, Html, Selector};
+use std::cell::RefCell;
+
+thread_local! {
+    /// Normalizer of the parse running on this thread, see [`with_text_normalizer`].
+    static TEXT_NORMALIZER: RefCell<Option<TextNormalizer>> = const { RefCell::new(None) };
+}
+
+/// Run `f` with `normalizer` in place of whitespace collapsing in [`get_inner_text`].
+///
+/// The text helpers are called from deep inside scoring and cleaning without
+/// access to the options, so the normalizer of the current parse is kept per
+/// thread for its duration.
+pub(crate) fn with_text_normalizer<R>(
+    normalizer: Option<&TextNormalizer>,
+    f: impl FnOnce() -> R,
+) -> R {
+    let previous = TEXT_NORMALIZER.with(|current| current.replace(normalizer.cloned()));
+    let result = f();
+    TEXT_NORMALIZER.with(|current| *current.borrow_mut() = previous);
+    result
+}
 
 /// Get inner text of an element - cross browser compatibly.
 /// This also strips out any excess whitespace to be found.
@@ -10,18 +34,40 @@ use scraper::{ElementRef, Html, Selector};
 /// * `element` - The element to extract text from
 /// * `normalize_spaces` - Whether to normalize whitespace (default: true)
 pub fn get_inner_text(element: ElementRef, normalize_spaces: bool) -> String {
-    let text = element.text().collect::<String>();
+    let text = crate::utils::replace_nbsp(&element.text().collect::<String>());
     let trimmed = text.trim();
 
     if normalize_spaces {
-        crate::utils::normalize_whitespace(trimmed)
+        TEXT_NORMALIZER.with(|normalizer| match normalizer.borrow().as_ref() {
+            Some(normalizer) => normalizer.normalize(trimmed),
+            None => crate::utils::normalize_whitespace(trimmed),
+        })
     } else {
         trimmed.to_string()
     }
 }
 
+/// Check if an element is a single-page-app mount point such as `#__next`.
+///
+/// Mount points wrap the entire rendered page, navigation and footer included,
+/// so they are never the article container themselves. Ids like `app` and
+/// `root` are common on other pages too, so only direct children of `<body>`
+/// count.
+pub fn is_spa_mount_point(element: ElementRef) -> bool {
+    element
+        .value()
+        .id()
+        .is_some_and(|id| SPA_MOUNT_POINT_IDS.contains(&id))
+        && element
+            .parent()
+            .and_then(ElementRef::wrap)
+            .is_some_and(|parent| parent.value().name() == "body")
+}
+
 /// Get the density of links as a percentage of the content.
 /// This is the amount of text that is inside a link divided by the total text in the node.
+/// Card links wrapping whole paragraphs (see [`is_block_wrapping_link`]) are
+/// not counted.
 ///
 /// # Arguments
 /// * `element` - The element to calculate link density for
@@ -38,6 +84,10 @@ pub fn get_link_density(element: ElementRef) -> f64 {
 
     let link_selector = Selector::parse("a").unwrap();
     for link in element.select(&link_selector) {
+        if is_nested_link(link, element) || is_block_wrapping_link(link) {
+            continue;
+        }
+
         if let Some(href) = link.value().attr("href") {
             let coefficient = if REGEXPS.hash_url.is_match(href) {
                 0.3
@@ -48,7 +98,37 @@ pub fn get_link_density(element: ElementRef) -> f64 {
         }
     }
 
-    link_length / text_length as f64
+    (link_length / text_length as f64).min(1.0)
+}
+
+/// Shortest text, in characters, of a link wrapping block content to be taken
+/// for a card link.
+const CARD_LINK_MIN_TEXT_LENGTH: usize = 100;
+
+/// Check if a link wraps whole paragraphs of text.
+///
+/// Some templates make each paragraph clickable by wrapping it in an `<a>`.
+/// A link whose children are all `<p>`s, holding at least
+/// `CARD_LINK_MIN_TEXT_LENGTH` characters of text, is a card link around
+/// content rather than navigation. Teaser cards, which wrap a heading or an
+/// image along with their blurb, don't qualify.
+pub fn is_block_wrapping_link(link: ElementRef) -> bool {
+    let mut children = link.children().filter_map(ElementRef::wrap).peekable();
+    children.peek().is_some()
+        && children.all(|child| child.value().name() == "p")
+        && get_inner_text(link, true).chars().count() >= CARD_LINK_MIN_TEXT_LENGTH
+}
+
+/// Check if a link sits inside another link below `within`.
+///
+/// The parser keeps some nested anchors (e.g. an SVG `<a>` inside an HTML `<a>`);
+/// the outer link's text already covers the inner one, so link-density
+/// calculations skip nested links to avoid counting the same text twice.
+pub fn is_nested_link(link: ElementRef, within: ElementRef) -> bool {
+    link.ancestors()
+        .take_while(|node| node.id() != within.id())
+        .filter_map(ElementRef::wrap)
+        .any(|ancestor| ancestor.value().name() == "a")
 }
 
 /// Check if a node is phrasing content (inline element).
@@ -79,12 +159,91 @@ pub fn is_phrasing_content(element: ElementRef) -> bool {
         });
     }
 
+    // Custom elements are inline where they are used inline, and render as
+    // blocks (or hold blocks) elsewhere.
+    if is_custom_element(element) {
+        return in_phrasing_context(element)
+            && element
+                .children()
+                .filter_map(ElementRef::wrap)
+                .all(is_phrasing_content);
+    }
+
     false
 }
 
+/// Check if an element is a custom element such as `<my-article>`, or a `<slot>`.
+///
+/// Web components render their content inside these. Custom element names
+/// always contain a hyphen; their content model is unknown, so they are
+/// treated like whatever they are used as: see [`is_phrasing_content`].
+pub fn is_custom_element(element: ElementRef) -> bool {
+    let name = element.value().name();
+    name.contains('-') || name == "slot"
+}
+
+/// Check if an element sits inside running text: next to text, or within a
+/// paragraph, heading or inline element.
+fn in_phrasing_context(element: ElementRef) -> bool {
+    let Some(parent) = element.parent().and_then(ElementRef::wrap) else {
+        return false;
+    };
+
+    let has_text = parent.children().any(|child| {
+        child
+            .value()
+            .as_text()
+            .is_some_and(|text| !text.trim().is_empty())
+    });
+    if has_text {
+        return true;
+    }
+
+    let parent_tag = parent.value().name().to_uppercase();
+    if PHRASING_ELEMS.contains(&parent_tag.as_str())
+        || matches!(
+            parent_tag.as_str(),
+            "P" | "A" | "DEL" | "INS" | "H1" | "H2" | "H3" | "H4" | "H5" | "H6"
+        )
+    {
+        return true;
+    }
+
+    is_custom_element(parent) && in_phrasing_context(parent)
+}
+
+/// Get the language of an element from the nearest `lang` attribute.
+///
+/// Empty `lang` values mark the language unknown and yield `None`.
+pub fn inherited_lang(element: ElementRef<'_>) -> Option<&str> {
+    std::iter::once(element)
+        .chain(element.ancestors().filter_map(ElementRef::wrap))
+        .find_map(|el| el.value().attr("lang"))
+        .map(str::trim)
+        .filter(|lang| !lang.is_empty())
+}
+
+/// Count the `<q>` elements enclosing an element.
+pub fn quote_depth(element: ElementRef) -> usize {
+    element
+        .ancestors()
+        .filter_map(ElementRef::wrap)
+        .filter(|el| el.value().name() == "q")
+        .count()
+}
+
+/// Check whether an element is one of `tags`, within which whitespace is kept.
+pub fn preserves_whitespace(element: ElementRef, tags: &[String]) -> bool {
+    let name = element.value().name();
+    tags.iter().any(|tag| tag.eq_ignore_ascii_case(name))
+}
+
 /// Check if a node is probably visible.
 ///
 /// Checks CSS display/visibility, hidden attribute, and aria-hidden.
+/// `aria-hidden` on an `<abbr>` or `<dfn>` is ignored: glossary markup hides
+/// the visible term from screen readers next to a spoken expansion, and
+/// dropping the term would leave a gap in the sentence.
 ///
 /// # Arguments
 /// * `element` - The element to check
@@ -112,7 +271,7 @@ pub fn is_probably_visible(element: ElementRef) -> bool {
         }
 
         if let Some(aria_hidden) = node.value().attr("aria-hidden") {
-            if aria_hidden == "true" {
+            if aria_hidden == "true" && !matches!(node.value().name(), "abbr" | "dfn") {
                 let is_fallback_image = node
                     .value()
                     .attr("class")
@@ -131,6 +290,37 @@ pub fn is_probably_visible(element: ElementRef) -> bool {
     true
 }
 
+/// Check if a node is probably page chrome pinned to the viewport.
+///
+/// Sticky headers, cookie banners, and floating tables of contents usually carry
+/// an inline `position: fixed` or `position: sticky`. The node counts as chrome
+/// when it or one of its ancestors is positioned this way and that positioned
+/// container holds little text; containers with substantial text are assumed to
+/// be article content laid out with sticky positioning.
+///
+/// # Arguments
+/// * `element` - The element to check
+///
+/// # Returns
+/// True if the element sits inside a small fixed or sticky container
+pub fn is_likely_chrome(element: ElementRef) -> bool {
+    const MAX_CHROME_TEXT_LENGTH: usize = 500;
+    static POSITION_REGEX: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"(?i)position\s*:\s*(fixed|sticky)").unwrap());
+
+    let mut current = Some(element);
+    while let Some(node) = current {
+        if let Some(style) = node.value().attr("style") {
+            if POSITION_REGEX.is_match(style) {
+                return get_inner_text(node, true).len() < MAX_CHROME_TEXT_LENGTH;
+            }
+        }
+        current = node.parent().and_then(ElementRef::wrap);
+    }
+
+    false
+}
+
 /// Get the ancestors of a node up to a maximum depth.
 ///
 /// # Arguments
@@ -210,6 +400,61 @@ pub fn get_article_direction(document: &Html) -> Option<String> {
     None
 }
 
+/// Build an XPath-like path such as `/html/body/div[2]/article` to an element.
+///
+/// Each step is the tag name, followed by the 1-based position among siblings
+/// of the same name when there are several. [`find_element_by_path`] resolves
+/// the path back to the element in the same document.
+pub fn element_path(element: ElementRef) -> String {
+    let mut steps = Vec::new();
+    let mut current = Some(element);
+    while let Some(node) = current {
+        let name = node.value().name();
+        let parent = node.parent().and_then(ElementRef::wrap);
+        let same_name: Vec<ElementRef> = match node.parent() {
+            Some(parent) => parent
+                .children()
+                .filter_map(ElementRef::wrap)
+                .filter(|sibling| sibling.value().name() == name)
+                .collect(),
+            None => Vec::new(),
+        };
+        match same_name
+            .iter()
+            .position(|sibling| sibling.id() == node.id())
+        {
+            Some(position) if same_name.len() > 1 => {
+                steps.push(format!("{name}[{}]", position + 1))
+            }
+            _ => steps.push(name.to_string()),
+        }
+        current = parent;
+    }
+    steps.reverse();
+    format!("/{}", steps.join("/"))
+}
+
+/// Find the element at a path built by [`element_path`].
+///
+/// A step without a position matches the first element of that name.
+/// Returns `None` when a step is malformed or matches nothing.
+pub fn find_element_by_path<'a>(document: &'a Html, path: &str) -> Option<ElementRef<'a>> {
+    let mut current = document.tree.root();
+    for step in path.trim().trim_start_matches('/').split('/') {
+        let (name, position) = match step.split_once('[') {
+            Some((name, position)) => (name, position.strip_suffix(']')?.parse::<usize>().ok()?),
+            None => (step, 1),
+        };
+        current = current
+            .children()
+            .filter_map(ElementRef::wrap)
+            .filter(|child| child.value().name().eq_ignore_ascii_case(name))
+            .nth(position.checked_sub(1)?)
+            .map(|child| *child)?;
+    }
+    ElementRef::wrap(current)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -224,6 +469,77 @@ mod tests {
         assert_eq!(get_inner_text(elem, true), "Hello world");
     }
 
+    #[test]
+    fn test_get_inner_text_with_text_normalizer() {
+        let html = Html::parse_fragment("<p> Hello \n  WORLD </p>");
+        let selector = Selector::parse("p").unwrap();
+        let elem = html.select(&selector).next().unwrap();
+
+        let lowercase = TextNormalizer::new(|text| text.to_lowercase());
+        with_text_normalizer(Some(&lowercase), || {
+            assert_eq!(get_inner_text(elem, true), "hello \n  world");
+            assert_eq!(get_inner_text(elem, false), "Hello \n  WORLD");
+        });
+        assert_eq!(get_inner_text(elem, true), "Hello WORLD");
+    }
+
+    #[test]
+    fn test_get_inner_text_replaces_nbsp() {
+        let html = Html::parse_fragment("<p>&nbsp;&nbsp;Hello&nbsp;&nbsp; world\u{a0}</p>");
+        let selector = Selector::parse("p").unwrap();
+        let elem = html.select(&selector).next().unwrap();
+
+        assert_eq!(get_inner_text(elem, false), "Hello   world");
+        assert_eq!(get_inner_text(elem, false).len(), 13);
+        assert_eq!(get_inner_text(elem, true), "Hello world");
+    }
+
+    #[test]
+    fn test_get_link_density_nested_links() {
+        // The parser keeps an SVG link inside an HTML link, so the inner text
+        // used to be counted twice.
+        let html = Html::parse_fragment(
+            r#"<div><a href="/outer">Read <svg><a href="/inner">the full story</a></svg></a></div>"#,
+        );
+        let selector = Selector::parse("div").unwrap();
+        let elem = html.select(&selector).next().unwrap();
+
+        let link_selector = Selector::parse("a").unwrap();
+        let summed: usize = elem
+            .select(&link_selector)
+            .map(|link| get_inner_text(link, false).len())
+            .sum();
+        assert!(summed > get_inner_text(elem, false).len());
+
+        assert_eq!(get_link_density(elem), 1.0);
+    }
+
+    #[test]
+    fn test_get_link_density_partial() {
+        let html = Html::parse_fragment(r#"<p>Some text and <a href="/x">link</a></p>"#);
+        let selector = Selector::parse("p").unwrap();
+        let elem = html.select(&selector).next().unwrap();
+        let density = get_link_density(elem);
+        assert!(density > 0.0 && density < 1.0);
+    }
+
+    #[test]
+    fn test_get_link_density_card_links() {
+        let html = Html::parse_fragment(
+            r#"<div><a href="/story"><p>Grey herons have returned to the estuary in numbers not seen for a decade, the county wildlife trust said.</p></a>
+<a href="/teaser"><p>Short teaser</p></a><a href="/more">More stories</a></div>"#,
+        );
+        let selector = Selector::parse("a").unwrap();
+        let links: Vec<_> = html.select(&selector).collect();
+        assert!(is_block_wrapping_link(links[0]));
+        assert!(!is_block_wrapping_link(links[1]));
+        assert!(!is_block_wrapping_link(links[2]));
+
+        let selector = Selector::parse("div").unwrap();
+        let div = html.select(&selector).next().unwrap();
+        assert!(get_link_density(div) < 0.25);
+    }
+
     #[test]
     fn test_is_phrasing_content() {
         let html = Html::parse_fragment("<span>inline</span><div>block</div>");
@@ -237,6 +553,87 @@ mod tests {
         assert!(!is_phrasing_content(div));
     }
 
+    #[test]
+    fn test_is_spa_mount_point() {
+        let html = Html::parse_document(
+            r#"<html><body><div id="__next"><div id="root"><main id="app"></main></div></div></body></html>"#,
+        );
+        let is_mount = |id: &str| {
+            let selector = Selector::parse(&format!("#{id}")).unwrap();
+            is_spa_mount_point(html.select(&selector).next().unwrap())
+        };
+
+        assert!(is_mount("__next"));
+        // Nested below the shell, `root` and `app` are ordinary containers.
+        assert!(!is_mount("root"));
+        assert!(!is_mount("app"));
+    }
+
+    #[test]
+    fn test_custom_elements_follow_their_use() {
+        let html = Html::parse_fragment(
+            "<news-article><article-body><text-block>A paragraph of text.</text-block>\
+             <slot><p>Another <x-term>term</x-term>.</p></slot></article-body></news-article>",
+        );
+        let select = |selector: &str| {
+            html.select(&Selector::parse(selector).unwrap())
+                .next()
+                .unwrap()
+        };
+
+        assert!(is_custom_element(select("slot")));
+        assert!(!is_custom_element(select("p")));
+        assert!(is_phrasing_content(select("x-term")));
+        assert!(!is_phrasing_content(select("text-block")));
+        assert!(!has_child_block_element(select("text-block")));
+        assert!(has_child_block_element(select("article-body")));
+        assert!(has_child_block_element(select("slot")));
+    }
+
+    #[test]
+    fn test_element_path_round_trip() {
+        let html = Html::parse_document(
+            "<html><body><div><p>a</p></div><div><p>b</p><p id=\"c\">c</p><span>d</span></div></body></html>",
+        );
+        let selector = Selector::parse("#c").unwrap();
+        let elem = html.select(&selector).next().unwrap();
+
+        let path = element_path(elem);
+        assert_eq!(path, "/html/body/div[2]/p[2]");
+        assert_eq!(
+            find_element_by_path(&html, &path).map(|el| el.id()),
+            Some(elem.id())
+        );
+        assert_eq!(
+            find_element_by_path(&html, "/html/body/div[2]/span").map(|el| el.inner_html()),
+            Some("d".to_string())
+        );
+        assert!(find_element_by_path(&html, "/html/body/div[3]").is_none());
+        assert!(find_element_by_path(&html, "/html/body/div[x]").is_none());
+    }
+
+    #[test]
+    fn test_is_likely_chrome() {
+        let long_text = "Article text that goes on for a while. ".repeat(20);
+        let html = Html::parse_fragment(&format!(
+            r#"
+            <div style="position: fixed; top: 0"><p id="banner">We use cookies to improve your experience.</p></div>
+            <nav style="POSITION:sticky"><p id="toc">Contents: Intro, Method, Results</p></nav>
+            <div style="position: sticky"><p id="long">{long_text}</p></div>
+            <div style="position: relative"><p id="plain">Regular paragraph text.</p></div>
+        "#
+        ));
+
+        let get = |id: &str| {
+            let sel = Selector::parse(&format!("#{id}")).unwrap();
+            html.select(&sel).next().unwrap()
+        };
+        assert!(is_likely_chrome(get("banner")));
+        assert!(is_likely_chrome(get("toc")));
+        assert!(!is_likely_chrome(get("long")));
+        assert!(!is_likely_chrome(get("plain")));
+    }
+
     #[test]
     fn test_is_probably_visible() {
         let html = Html::parse_fragment(
@@ -251,4 +648,18 @@ mod tests {
         let visible = html.select(&visible_sel).next().unwrap();
         assert!(is_probably_visible(visible));
     }
+
+    #[test]
+    fn test_aria_hidden_terms_are_visible() {
+        let html = Html::parse_fragment(
+            r#"<p>The <abbr aria-hidden="true" title="Content Delivery Network">CDN</abbr>
+            <span aria-hidden="true"><dfn>edge</dfn></span></p>"#,
+        );
+        let get = |tag: &str| {
+            let selector = Selector::parse(tag).unwrap();
+            html.select(&selector).next().unwrap()
+        };
+        assert!(is_probably_visible(get("abbr")));
+        assert!(!is_probably_visible(get("dfn")));
+    }
 }
@@ -28,12 +28,41 @@ static DATA_SRCSET_RE: Lazy<Regex> =
 static SRCSET_ATTR_RE: Lazy<Regex> =
     Lazy::new(|| Regex::new(r#"srcset="([^"]*)""#).unwrap());
 
+static PICTURE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?si)<picture\b[^>]*>.*?</picture>").unwrap());
+
+static SOURCE_TAG_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?si)<source\s[^>]*?/?>").unwrap());
+
+static OWN_SRCSET_ATTR_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"\ssrcset="[^"]*""#).unwrap());
+
+static MEDIA_ATTR_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"\smedia="([^"]*)""#).unwrap());
+
+static MEDIA_FEATURE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)^\(\s*(min|max)-width\s*:\s*(\d+\.?\d*)(px|em|rem)\s*\)$").unwrap());
+
 /// Standardize images:
 /// 1. Resolve lazy-loaded images (`data-src` → `src`).
 /// 2. Pick best source from `srcset`.
 /// 3. Remove tiny images (width AND height both < 100).
 pub fn standardize_images(html: &str) -> String {
-    IMG_TAG_RE.replace_all(html, |caps: &regex::Captures| {
+    standardize_images_for_width(html, None)
+}
+
+/// Standardize images, picking responsive sources for a target viewport width.
+///
+/// Same as [`standardize_images`], but with a `target_width` the matching
+/// `<source media>` of each `<picture>` is resolved first and `srcset` picks
+/// the candidate closest to that width instead of the largest.
+pub fn standardize_images_for_width(html: &str, target_width: Option<u32>) -> String {
+    let html = match target_width {
+        Some(width) => resolve_picture_sources(html, width),
+        None => html.to_string(),
+    };
+
+    IMG_TAG_RE.replace_all(&html, |caps: &regex::Captures| {
         let full = &caps[0];
 
         // Check for small images
@@ -61,22 +90,99 @@ pub fn standardize_images(html: &str) -> String {
         }
 
         // Handle srcset / data-srcset
-        let srcset = SRCSET_ATTR_RE.captures(full).map(|c| c[1].to_string()).unwrap_or_default();
-        let data_srcset = DATA_SRCSET_RE.captures(full).map(|c| c[1].to_string()).unwrap_or_default();
-        let effective = if !data_srcset.is_empty() && srcset.is_empty() { &data_srcset } else { &srcset };
-        if !effective.is_empty() {
-            if let Some(best) = pick_best_srcset(effective) {
-                let current_src = SRC_RE.captures(&result).map(|c| c[1].to_string()).unwrap_or_default();
-                if !current_src.is_empty() {
-                    result = replace_src_attr(&result, &current_src, &escape_attr(&best));
-                }
-            }
-        }
+        apply_srcset(&result, target_width)
+    }).to_string()
+}
 
-        result
+/// Point images at the responsive source best matching a viewport width.
+///
+/// For each `<picture>`, the first `<source>` whose `media` query matches
+/// `target_width` supplies the `srcset` of the picture's `<img>`; each image's
+/// `src` is then set to the `srcset` candidate picked for that width. The
+/// `<source>` elements and `srcset` attributes are kept.
+pub fn resolve_responsive_images(html: &str, target_width: u32) -> String {
+    let html = resolve_picture_sources(html, target_width);
+    IMG_TAG_RE
+        .replace_all(&html, |caps: &regex::Captures| apply_srcset(&caps[0], Some(target_width)))
+        .to_string()
+}
+
+/// Replace an `<img>` tag's `src` with its `srcset` (or `data-srcset`) pick.
+fn apply_srcset(img_tag: &str, target_width: Option<u32>) -> String {
+    let srcset = SRCSET_ATTR_RE.captures(img_tag).map(|c| c[1].to_string()).unwrap_or_default();
+    let data_srcset = DATA_SRCSET_RE.captures(img_tag).map(|c| c[1].to_string()).unwrap_or_default();
+    let effective = if !data_srcset.is_empty() && srcset.is_empty() { &data_srcset } else { &srcset };
+    if effective.is_empty() {
+        return img_tag.to_string();
+    }
+
+    let intrinsic_width: Option<u32> = WIDTH_RE.captures(img_tag).and_then(|c| c[1].parse().ok());
+    let picked = match target_width {
+        Some(width) => pick_srcset_for_width(effective, width, intrinsic_width),
+        None => pick_best_srcset(effective),
+    };
+    let current_src = SRC_RE.captures(img_tag).map(|c| c[1].to_string()).unwrap_or_default();
+    match picked {
+        Some(best) if !current_src.is_empty() => replace_src_attr(img_tag, &current_src, &escape_attr(&best)),
+        _ => img_tag.to_string(),
+    }
+}
+
+/// Copy the `srcset` of each picture's matching `<source>` onto its `<img>`.
+fn resolve_picture_sources(html: &str, target_width: u32) -> String {
+    PICTURE_RE.replace_all(html, |caps: &regex::Captures| {
+        let picture = &caps[0];
+        let source_srcset = SOURCE_TAG_RE
+            .find_iter(picture)
+            .map(|source| source.as_str())
+            .filter(|source| {
+                MEDIA_ATTR_RE
+                    .captures(source)
+                    .is_none_or(|c| media_matches(&c[1], target_width))
+            })
+            .find_map(|source| SRCSET_ATTR_RE.captures(source).map(|c| c[1].to_string()));
+        let (Some(source_srcset), Some(img)) = (source_srcset, IMG_TAG_RE.find(picture)) else {
+            return picture.to_string();
+        };
+
+        let img_tag = img.as_str();
+        let srcset_attr = format!(" srcset=\"{}\"", source_srcset);
+        let resolved = if OWN_SRCSET_ATTR_RE.is_match(img_tag) {
+            OWN_SRCSET_ATTR_RE.replace(img_tag, regex::NoExpand(&srcset_attr)).to_string()
+        } else {
+            img_tag.replacen("<img", &format!("<img{}", srcset_attr), 1)
+        };
+        format!("{}{}{}", &picture[..img.start()], resolved, &picture[img.end()..])
     }).to_string()
 }
 
+/// Check whether a `media` query matches a viewport of `width` CSS pixels.
+///
+/// Only `min-width`/`max-width` features (in `px`, `em` or `rem`) and the
+/// `all`/`screen` media types are understood; comma-separated queries match
+/// when any of them does. Queries on anything else never match.
+fn media_matches(media: &str, width: u32) -> bool {
+    let width = width as f64;
+    media.split(',').any(|query| {
+        let query = query.trim().to_lowercase();
+        !query.is_empty()
+            && query.split(" and ").all(|condition| {
+                let condition = condition.trim();
+                if condition == "all" || condition == "screen" || condition == "only screen" {
+                    return true;
+                }
+                let Some(cap) = MEDIA_FEATURE_RE.captures(condition) else {
+                    return false;
+                };
+                let mut limit: f64 = cap[2].parse().unwrap_or(0.0);
+                if &cap[3] != "px" {
+                    limit *= 16.0;
+                }
+                if &cap[1] == "min" { width >= limit } else { width <= limit }
+            })
+    })
+}
+
 fn is_placeholder_src(src: &str) -> bool {
     if src.starts_with("data:") {
         return BASE64_PLACEHOLDER_RE.is_match(src);
@@ -117,6 +223,47 @@ pub fn pick_best_srcset(srcset: &str) -> Option<String> {
     best_url
 }
 
+/// Parse srcset and pick the source best suited to a viewport width.
+///
+/// Width descriptors pick the smallest candidate at least `target_width` wide,
+/// falling back to the largest. Density descriptors are converted to widths
+/// using the image's `intrinsic_width` when known; otherwise the `1x` candidate
+/// (or the lowest density above it) is picked. Entries without a descriptor
+/// count as `1x`.
+pub fn pick_srcset_for_width(srcset: &str, target_width: u32, intrinsic_width: Option<u32>) -> Option<String> {
+    let target = target_width as f64;
+    let mut candidates: Vec<(String, f64)> = Vec::new();
+
+    for entry in srcset.split(',') {
+        let entry = entry.trim();
+        if entry.is_empty() {
+            continue;
+        }
+        let (url, value, descriptor) = match SRCSET_ENTRY_RE.captures(entry) {
+            Some(cap) => (cap[1].to_string(), cap[2].parse().unwrap_or(0.0), cap[3].to_string()),
+            None => match entry.split_whitespace().next() {
+                Some(url) => (url.to_string(), 1.0, "x".to_string()),
+                None => continue,
+            },
+        };
+        let width = match (descriptor.as_str(), intrinsic_width) {
+            ("w", _) => value,
+            (_, Some(intrinsic)) => value * intrinsic as f64,
+            // Without a layout width, a density of 1 matches the target.
+            _ => value * target,
+        };
+        candidates.push((url, width));
+    }
+
+    let fitting = candidates
+        .iter()
+        .filter(|(_, width)| *width >= target)
+        .min_by(|a, b| a.1.total_cmp(&b.1));
+    fitting
+        .or_else(|| candidates.iter().max_by(|a, b| a.1.total_cmp(&b.1)))
+        .map(|(url, _)| url.clone())
+}
+
 /// Replace the `src` attribute value without accidentally matching `data-src`.
 fn replace_src_attr(html: &str, old_val: &str, new_val: &str) -> String {
     let old_pattern = format!(" src=\"{}\"", old_val);
@@ -155,6 +302,42 @@ mod tests {
         assert_eq!(pick_best_srcset(srcset), Some("large.jpg".to_string()));
     }
 
+    #[test]
+    fn test_srcset_pick_for_width() {
+        let srcset = "small.jpg 400w, medium.jpg 800w, large.jpg 1200w";
+        assert_eq!(pick_srcset_for_width(srcset, 600, None), Some("medium.jpg".to_string()));
+        assert_eq!(pick_srcset_for_width(srcset, 800, None), Some("medium.jpg".to_string()));
+        assert_eq!(pick_srcset_for_width(srcset, 2000, None), Some("large.jpg".to_string()));
+
+        let density = "photo.jpg, photo@2x.jpg 2x, photo@3x.jpg 3x";
+        assert_eq!(pick_srcset_for_width(density, 600, None), Some("photo.jpg".to_string()));
+        assert_eq!(pick_srcset_for_width(density, 600, Some(320)), Some("photo@2x.jpg".to_string()));
+    }
+
+    #[test]
+    fn test_media_matches() {
+        assert!(media_matches("(max-width: 600px)", 480));
+        assert!(!media_matches("(max-width: 600px)", 1024));
+        assert!(media_matches("screen and (min-width: 40em)", 800));
+        assert!(media_matches("(min-width: 601px) and (max-width: 1200px), print", 1024));
+        assert!(!media_matches("(orientation: landscape)", 1024));
+    }
+
+    #[test]
+    fn test_picture_source_for_width() {
+        let html = r#"<picture><source media="(max-width: 600px)" srcset="bed-480.jpg 480w, bed-960.jpg 960w"><source media="(min-width: 601px)" srcset="bed-1600.jpg 1600w, bed-3200.jpg 3200w"><img src="bed.jpg" srcset="bed-800.jpg 800w, bed-4000.jpg 4000w" alt="Raised bed"></picture>"#;
+
+        let mobile = resolve_responsive_images(html, 480);
+        assert!(mobile.contains(r#"<img src="bed-480.jpg" srcset="bed-480.jpg 480w, bed-960.jpg 960w""#));
+        assert!(mobile.contains(r#"<source media="(min-width: 601px)""#));
+
+        let desktop = standardize_images_for_width(html, Some(1280));
+        assert!(desktop.contains(r#"<img src="bed-1600.jpg""#));
+
+        // Without a target width the largest image candidate wins.
+        assert!(standardize_images(html).contains(r#"<img src="bed-4000.jpg""#));
+    }
+
     #[test]
     fn test_normal_image_preserved() {
         let html = r#"<img src="photo.jpg" alt="Nice photo" width="800" height="600"/>"#;
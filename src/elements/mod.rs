@@ -10,11 +10,19 @@ pub mod math;
 /// This normalizes vendor-specific HTML (code blocks, headings, images,
 /// footnotes, math) into canonical forms before markdown conversion.
 pub fn standardize_all(html: &str, title: Option<&str>) -> String {
+    standardize_all_for_width(html, title, None)
+}
+
+/// Run the full standardization pipeline, sizing images for a viewport width.
+///
+/// With a `target_width`, responsive images use the `<source>`/`srcset`
+/// candidate matching that width rather than the largest one.
+pub fn standardize_all_for_width(html: &str, title: Option<&str>, target_width: Option<u32>) -> String {
     let mut result = html.to_string();
 
     result = code_blocks::standardize_code_blocks(&result);
     result = headings::standardize_headings(&result, title);
-    result = images::standardize_images(&result);
+    result = images::standardize_images_for_width(&result, target_width);
     result = footnotes::standardize_footnotes(&result);
     result = math::standardize_math(&result);
 
@@ -0,0 +1,116 @@
+//! Recovery of article HTML embedded in script data or iframes.
+//!
+//! Client-rendered pages often ship the article as an HTML string inside a
+//! JSON payload, such as Next.js `__NEXT_DATA__` or a `window.__DATA__ = {...}`
+//! assignment, and build the DOM from it with JavaScript. Fetched without
+//! running scripts, the page body is an empty shell. [`find_embedded_html`]
+//! finds the largest such string so extraction can run on it instead.
+//!
+//! CMS previews and some embeds instead put the article document in an
+//! `<iframe srcdoc="...">`, which [`find_iframe_srcdoc`] recovers.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use scraper::{Html, Selector};
+
+static SCRIPT_SELECTOR: Lazy<Selector> = Lazy::new(|| {
+    Selector::parse(r#"script:not([type="application/ld+json"]):not([src])"#).unwrap()
+});
+
+static SRCDOC_IFRAME_SELECTOR: Lazy<Selector> =
+    Lazy::new(|| Selector::parse("iframe[srcdoc]").unwrap());
+
+/// A double-quoted JSON or JavaScript string literal.
+static STRING_LITERAL_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#""(?:[^"\\]|\\.)*""#).unwrap());
+
+/// Tags that mark a string as article markup rather than plain text.
+static BLOCK_TAG_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)<(?:p|h[1-6]|article|section|blockquote|li)\b").unwrap());
+
+/// Find the longest HTML string of at least `min_length` characters in the
+/// document's inline scripts.
+///
+/// Every double-quoted string literal in a script is decoded with JSON
+/// escaping rules (`\"`, `\u003c`, ...); strings whose markup is itself
+/// entity-encoded (`&lt;p&gt;`) are decoded once more. Only strings containing
+/// block-level tags such as `<p>` or `<h2>` count, which skips long plain text
+/// and serialized data.
+pub(crate) fn find_embedded_html(document: &Html, min_length: usize) -> Option<String> {
+    document
+        .select(&SCRIPT_SELECTOR)
+        .flat_map(|script| {
+            let source = script.text().collect::<String>();
+            STRING_LITERAL_REGEX
+                .find_iter(&source)
+                .filter(|literal| literal.len() >= min_length)
+                .filter_map(|literal| serde_json::from_str::<String>(literal.as_str()).ok())
+                .collect::<Vec<_>>()
+        })
+        .map(|value| {
+            if value.contains('<') {
+                value
+            } else {
+                crate::utils::unescape_html_entities(&value)
+            }
+        })
+        .filter(|html| html.len() >= min_length && BLOCK_TAG_REGEX.is_match(html))
+        .max_by_key(|html| html.len())
+}
+
+/// Find the longest `srcdoc` of at least `min_length` characters among the
+/// document's iframes.
+///
+/// The attribute value is already unescaped by the parser. As with script
+/// data, only documents containing block-level tags count.
+pub(crate) fn find_iframe_srcdoc(document: &Html, min_length: usize) -> Option<String> {
+    document
+        .select(&SRCDOC_IFRAME_SELECTOR)
+        .filter_map(|iframe| iframe.value().attr("srcdoc"))
+        .filter(|html| html.len() >= min_length && BLOCK_TAG_REGEX.is_match(html))
+        .max_by_key(|html| html.len())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_embedded_html() {
+        let html = r#"<html><body><div id="__next"></div>
+<script id="__NEXT_DATA__" type="application/json">{"props":{"title":"A title that is long enough to pass the threshold on its own","body":"<p>First paragraph with a \"quote\".</p><p>Second paragraph.</p>"}}</script>
+<script>window.__DATA__ = {"teaser": "&lt;p&gt;A short teaser paragraph.&lt;/p&gt;"};</script>
+</body></html>"#;
+        let document = Html::parse_document(html);
+
+        assert_eq!(
+            find_embedded_html(&document, 40).as_deref(),
+            Some(r#"<p>First paragraph with a "quote".</p><p>Second paragraph.</p>"#)
+        );
+        assert_eq!(find_embedded_html(&document, 1000), None);
+
+        let teaser = Html::parse_document(
+            r#"<script>window.__DATA__ = {"teaser": "&lt;p&gt;A short teaser paragraph.&lt;/p&gt;"};</script>"#,
+        );
+        assert_eq!(
+            find_embedded_html(&teaser, 20).as_deref(),
+            Some("<p>A short teaser paragraph.</p>")
+        );
+    }
+
+    #[test]
+    fn test_find_iframe_srcdoc() {
+        let html = r#"<html><body><iframe srcdoc="&lt;p&gt;A &amp;quot;short&amp;quot; preview.&lt;/p&gt;"></iframe>
+<iframe srcdoc="<h1>Title</h1><p>The full article body, long enough to pass.</p>"></iframe>
+<iframe srcdoc="Just a long line of plain text without any markup in it."></iframe>
+<iframe src="/embed"></iframe></body></html>"#;
+        let document = Html::parse_document(html);
+
+        assert_eq!(
+            find_iframe_srcdoc(&document, 20).as_deref(),
+            Some("<h1>Title</h1><p>The full article body, long enough to pass.</p>")
+        );
+        assert_eq!(find_iframe_srcdoc(&document, 1000), None);
+    }
+}
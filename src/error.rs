@@ -145,6 +145,21 @@ pub enum ReadabilityError {
     #[error("No article content found in document")]
     NoContentFound,
 
+    /// Invalid CSS selector.
+    ///
+    /// This error occurs when the selector passed to
+    /// [`Readability::parse_within`](crate::Readability::parse_within) cannot
+    /// be parsed.
+    #[error("Invalid selector: {0}")]
+    InvalidSelector(String),
+
+    /// No element matches the content selector.
+    ///
+    /// Returned by [`Readability::parse_within`](crate::Readability::parse_within)
+    /// when the selector matches nothing and `selector_fallback` is disabled.
+    #[error("No element matches selector: {0}")]
+    SelectorNotFound(String),
+
     /// General error.
     ///
     /// A catch-all error type for conditions that don't fit other categories.
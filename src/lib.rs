@@ -15,6 +15,9 @@
 //!
 //! - **Content Extraction**: Intelligently identifies and extracts main article content
 //! - **Markdown Output**: Optional HTML-to-Markdown conversion with content standardization
+//! - **AMP Output**: Optional conversion of the content to AMP-safe markup
+//! - **Block Output**: Optional conversion of the content to typed JSON blocks
+//! - **Plain-Text Output**: Optional conversion of the content to text, one line per block
 //! - **Metadata Extraction**: Extracts title, author, description, site name, language, and publish date
 //! - **JSON-LD Support**: Parses structured data from JSON-LD markup
 //! - **Multiple Retry Strategies**: Uses adaptive algorithms to handle various page layouts
@@ -112,26 +115,39 @@
 //! This implementation strives to match the behavior of Mozilla's Readability.js as closely
 //! as possible while leveraging Rust's type system and safety guarantees.
 
+pub mod amp;
 mod article;
+pub mod blocks;
 mod cleaner;
 mod constants;
 mod content_extractor;
+#[cfg(feature = "dates")]
+mod dates;
 mod dom_utils;
+mod embedded;
 pub mod elements;
 mod error;
 pub mod markdown;
 mod metadata;
 mod options;
+pub mod plain_text;
 mod post_processor;
 mod readability;
 mod readerable;
+mod retry;
 mod scoring;
+#[cfg(any(test, feature = "testutil"))]
+pub mod testutil;
 mod utils;
 
 // Public exports
-pub use article::Article;
+pub use article::{Article, MediaItem, MediaKind, Recipe, DOCUMENT_STYLESHEET};
+pub use blocks::Block;
+pub use constants::ParseFlags;
 pub use error::{ReadabilityError, Result};
 pub use markdown::MarkdownOptions;
-pub use options::ReadabilityOptions;
-pub use readability::Readability;
+pub use metadata::Metadata;
+pub use options::{ContentProfile, ReadabilityOptions, SvgPolicy, TextNormalizer};
+pub use readability::{clean, Readability};
 pub use readerable::{is_probably_readerable, ReaderableOptions};
+pub use retry::{AttemptStats, DefaultRetryStrategy, RetryStrategy};
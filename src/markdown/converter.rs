@@ -45,7 +45,8 @@ fn convert_children(
                 } else {
                     // Collapse consecutive whitespace to a single space,
                     // mirroring browser behavior for normal flow content.
-                    let collapsed = collapse_whitespace(text);
+                    // Soft break hints are dropped so split words rejoin.
+                    let collapsed = collapse_whitespace(&crate::utils::remove_soft_breaks(text, true));
                     result.push_str(&rules::text::escape_markdown(&collapsed));
                 }
             }
@@ -79,7 +80,8 @@ fn convert_element(
             let inner = convert_children(el, opts, state);
             rules::text::convert_emphasis(&inner, opts, state)
         }
-        "code" if !state.in_code_block => {
+        // Keystrokes and sample output render like inline code
+        "code" | "kbd" | "samp" if !state.in_code_block => {
             // Check if inside a <pre> — handled by the pre/code path
             let inner = el.text().collect::<String>();
             rules::text::convert_inline_code(&inner, opts, state)
@@ -222,8 +224,10 @@ fn convert_element(
             rules::footnotes::convert_footnote_ref(text.trim())
         }
 
-        // Block elements — just convert children with paragraph spacing
-        "p" => {
+        // Block elements — just convert children with paragraph spacing.
+        // Captions of image figures are handled by `convert_figure`; captions
+        // of quotes, tables and code become their own paragraph.
+        "p" | "figcaption" => {
             let inner = convert_children(el, opts, state);
             let trimmed = inner.trim();
             if trimmed.is_empty() {
@@ -244,20 +248,47 @@ fn convert_element(
         "sup" => {
             let inner = convert_children(el, opts, state);
             let trimmed = inner.trim();
-            if trimmed.is_empty() { String::new() } else { format!("^{}^", trimmed) }
+            if trimmed.is_empty() {
+                String::new()
+            } else if let Some(unicode) = opts
+                .unicode_sub_sup
+                .then(|| crate::utils::to_unicode_superscript(trimmed))
+                .flatten()
+            {
+                unicode
+            } else {
+                format!("^{}^", trimmed)
+            }
         }
         "sub" => {
             let inner = convert_children(el, opts, state);
             let trimmed = inner.trim();
-            if trimmed.is_empty() { String::new() } else { format!("~{}~", trimmed) }
+            if trimmed.is_empty() {
+                String::new()
+            } else if let Some(unicode) = opts
+                .unicode_sub_sup
+                .then(|| crate::utils::to_unicode_subscript(trimmed))
+                .flatten()
+            {
+                unicode
+            } else {
+                format!("~{}~", trimmed)
+            }
         }
 
         // Details/summary — preserve as raw HTML (most renderers support it)
         "details" => format!("\n\n{}\n\n", el.html()),
 
+        // Inline quotes — quotation marks of the content's language
+        "q" => {
+            let lang = crate::dom_utils::inherited_lang(el).or(opts.lang.as_deref());
+            let (open, close) = crate::utils::quote_marks(lang, crate::dom_utils::quote_depth(el));
+            format!("{open}{}{close}", convert_children(el, opts, state))
+        }
+
         // Spans and other inline — transparent pass-through
         "span" | "abbr" | "cite" | "dfn" | "kbd" | "samp" | "var" | "time" | "data"
-        | "small" | "ins" | "u" | "q" | "bdo" | "bdi" | "wbr"
+        | "small" | "ins" | "u" | "bdo" | "bdi" | "wbr"
         | "ruby" | "rt" | "rp" | "summary" | "label" => {
             convert_children(el, opts, state)
         }
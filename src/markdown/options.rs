@@ -15,6 +15,14 @@ pub struct MarkdownOptions {
     pub link_style: LinkStyle,
     /// Keep complex tables (colspan/rowspan) as raw HTML.
     pub preserve_complex_tables: bool,
+    /// Render `<sup>`/`<sub>` with Unicode characters (`x²`, `H₂O`) when every
+    /// character has a Unicode form, instead of `^x^`/`~x~`.
+    pub unicode_sub_sup: bool,
+    /// How `<mark>` highlights are written.
+    pub highlight_style: HighlightStyle,
+    /// Language of the content, picking the quotation marks `<q>` is wrapped
+    /// in when the markup has no `lang` attribute. `None` uses English marks.
+    pub lang: Option<String>,
 }
 
 /// Heading output style.
@@ -26,6 +34,15 @@ pub enum HeadingStyle {
     Setext,
 }
 
+/// Highlight (`<mark>`) output style.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HighlightStyle {
+    /// `==text==` — extended syntax (default).
+    Equals,
+    /// Strong delimiters, for renderers without highlight support.
+    Strong,
+}
+
 /// Link output style.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LinkStyle {
@@ -45,6 +62,9 @@ impl Default for MarkdownOptions {
             strong_delimiter: "**".to_string(),
             link_style: LinkStyle::Inline,
             preserve_complex_tables: true,
+            unicode_sub_sup: false,
+            highlight_style: HighlightStyle::Equals,
+            lang: None,
         }
     }
 }
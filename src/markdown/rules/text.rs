@@ -1,5 +1,7 @@
-use crate::markdown::options::MarkdownOptions;
+use crate::markdown::options::{HighlightStyle, MarkdownOptions};
 use crate::markdown::state::ConversionState;
+use once_cell::sync::Lazy;
+use regex::Regex;
 
 /// Convert `<strong>` / `<b>` content to markdown.
 pub fn convert_strong(inner: &str, opts: &MarkdownOptions, _state: &ConversionState) -> String {
@@ -34,21 +36,51 @@ pub fn convert_inline_code(inner: &str, _opts: &MarkdownOptions, _state: &Conver
 }
 
 /// Convert `<del>` / `<s>` / `<strike>` to markdown.
+///
+/// Strikethrough cannot span paragraphs, so a `<del>` wrapping block content
+/// strikes each line separately, keeping list, heading and quote markers
+/// and `<br>` line breaks outside the delimiters.
 pub fn convert_strikethrough(inner: &str, _opts: &MarkdownOptions, _state: &ConversionState) -> String {
+    static BLOCK_MARKER: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"^\s*(?:[-*+]|\d+[.)]|#{1,6}|>)\s+").unwrap());
+
     let trimmed = inner.trim();
     if trimmed.is_empty() {
         return String::new();
     }
-    format!("~~{}~~", trimmed)
+    if !trimmed.contains('\n') {
+        return format!("~~{}~~", trimmed);
+    }
+
+    trimmed
+        .lines()
+        .map(|line| {
+            let marker_len = BLOCK_MARKER.find(line).map_or(0, |m| m.end());
+            let (marker, rest) = line.split_at(marker_len);
+            let text = rest.trim_end();
+            if text.is_empty() {
+                line.to_string()
+            } else {
+                format!("{}~~{}~~{}", marker, text, &rest[text.len()..])
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
-/// Convert `<mark>` to markdown (extended syntax).
-pub fn convert_highlight(inner: &str, _opts: &MarkdownOptions, _state: &ConversionState) -> String {
+/// Convert `<mark>` to markdown.
+///
+/// Uses the `==text==` extended syntax, or strong delimiters when
+/// `highlight_style` is [`HighlightStyle::Strong`].
+pub fn convert_highlight(inner: &str, opts: &MarkdownOptions, state: &ConversionState) -> String {
     let trimmed = inner.trim();
     if trimmed.is_empty() {
         return String::new();
     }
-    format!("=={}==", trimmed)
+    match opts.highlight_style {
+        HighlightStyle::Equals => format!("=={}==", trimmed),
+        HighlightStyle::Strong => convert_strong(trimmed, opts, state),
+    }
 }
 
 /// Convert `<br>` to markdown.
@@ -99,6 +131,32 @@ mod tests {
         assert_eq!(convert_emphasis("italic", &opts, &state), "*italic*");
     }
 
+    #[test]
+    fn test_strikethrough_across_blocks() {
+        let opts = MarkdownOptions::default();
+        let state = ConversionState::default();
+        assert_eq!(convert_strikethrough(" gone ", &opts, &state), "~~gone~~");
+        assert_eq!(
+            convert_strikethrough(
+                "First.\n\nSecond line  \nwrapped.\n\n- item\n\n",
+                &opts,
+                &state
+            ),
+            "~~First.~~\n\n~~Second line~~  \n~~wrapped.~~\n\n- ~~item~~"
+        );
+    }
+
+    #[test]
+    fn test_highlight_styles() {
+        let state = ConversionState::default();
+        let mut opts = MarkdownOptions::default();
+        assert_eq!(convert_highlight(" key ", &opts, &state), "==key==");
+        assert_eq!(convert_highlight("", &opts, &state), "");
+
+        opts.highlight_style = HighlightStyle::Strong;
+        assert_eq!(convert_highlight("key", &opts, &state), "**key**");
+    }
+
     #[test]
     fn test_inline_code_with_backticks() {
         let opts = MarkdownOptions::default();
@@ -1,26 +1,155 @@
 //! Metadata extraction from HTML documents (JSON-LD, meta tags, etc.).
 
+use crate::article::Recipe;
 use crate::constants::REGEXPS;
 use crate::utils;
 use once_cell::sync::Lazy;
 use scraper::node::Node;
 use scraper::{ElementRef, Html, Selector};
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::borrow::Cow;
 use std::collections::HashMap;
 
-/// Metadata extracted from the document
-#[derive(Debug, Clone, Default)]
+/// Metadata extracted from the document head.
+///
+/// Combines JSON-LD, OpenGraph, Twitter Cards, Dublin Core, and standard meta
+/// tags in that priority order. [`Readability::parse`](crate::Readability::parse)
+/// fills the matching [`Article`](crate::Article) fields from this struct, and
+/// [`Metadata::from_html`] exposes the same extraction on its own for link
+/// previews and unfurling, without running content extraction.
+///
+/// ## Example
+///
+/// ```rust
+/// use readabilityrs::Metadata;
+///
+/// let html = r#"<html lang="en"><head>
+///     <meta property="og:title" content="Hello">
+///     <link rel="canonical" href="/hello">
+/// </head><body></body></html>"#;
+///
+/// let metadata = Metadata::from_html(html, Some("https://example.com/a"));
+/// assert_eq!(metadata.title.as_deref(), Some("Hello"));
+/// assert_eq!(metadata.canonical_url.as_deref(), Some("https://example.com/hello"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Metadata {
+    /// Article title, with the site name stripped where detected.
     pub title: Option<String>,
+    /// Author line.
     pub byline: Option<String>,
+    /// Short description of the page.
     pub excerpt: Option<String>,
+    /// Name of the publishing site.
     pub site_name: Option<String>,
+    /// Publication timestamp, typically ISO 8601.
     pub published_time: Option<String>,
+    /// Last modification timestamp, typically ISO 8601.
+    #[serde(default)]
+    pub modified_time: Option<String>,
+    /// Publication time parsed from `published_time`, or from a `<time>`
+    /// element marked as the publish date.
+    #[cfg(feature = "dates")]
+    #[serde(default)]
+    pub published: Option<chrono::DateTime<chrono::FixedOffset>>,
+    /// Modification time parsed from `modified_time`, or from a `<time>`
+    /// element marked as the modified date.
+    #[cfg(feature = "dates")]
+    #[serde(default)]
+    pub modified: Option<chrono::DateTime<chrono::FixedOffset>>,
+    /// Language of the document.
     pub lang: Option<String>,
+    /// Lead image URL.
     pub image: Option<String>,
+    /// Alternate-language versions as `(lang, url)` pairs.
+    #[serde(default)]
+    pub alternates: Vec<(String, String)>,
+    /// Recipe or how-to steps from JSON-LD.
+    #[serde(default)]
+    pub recipe: Option<Recipe>,
+    /// Canonical URL from `<link rel="canonical">`, falling back to `og:url`.
+    #[serde(default)]
+    pub canonical_url: Option<String>,
+    /// Highest-resolution site icon URL, falling back to `og:image`.
+    #[serde(default)]
+    pub icon: Option<String>,
+    /// Breadcrumb trail, outermost first.
+    ///
+    /// Filled by [`Metadata::from_html`]; the parse pipeline only extracts it
+    /// when `extract_breadcrumbs` is enabled.
+    #[serde(default)]
+    pub breadcrumbs: Vec<String>,
+    /// Section or category, from JSON-LD `articleSection` or `article:section`.
+    #[serde(default)]
+    pub section: Option<String>,
+    /// The JSON-LD article object the typed fields were read from.
+    #[serde(default)]
+    pub json_ld: Option<Value>,
+}
+
+impl Metadata {
+    /// Extract metadata from an HTML string without running content extraction.
+    ///
+    /// Relative URLs (canonical, icon, alternates) are resolved against
+    /// `base_url` when it is given and valid.
+    pub fn from_html(html: &str, base_url: Option<&str>) -> Self {
+        let document = Html::parse_document(html);
+        let mut metadata = Self::from_document(&document, base_url, true);
+        metadata.breadcrumbs = get_breadcrumbs(&document, true);
+        metadata
+    }
+
+    /// Extract metadata from a parsed document.
+    ///
+    /// Shared by [`Metadata::from_html`] and the main parse pipeline. When
+    /// `use_json_ld` is `false`, JSON-LD scripts are ignored.
+    pub(crate) fn from_document(document: &Html, base_url: Option<&str>, use_json_ld: bool) -> Self {
+        let json_ld = if use_json_ld {
+            get_json_ld(document)
+        } else {
+            Metadata::default()
+        };
+
+        let mut metadata = get_article_metadata(document, json_ld);
+        if metadata.title.as_deref().is_none_or(str::is_empty) {
+            if let Some(title) = get_heading_title(document) {
+                metadata.title = Some(title);
+            }
+        }
+        metadata.alternates = get_alternate_links(document, base_url);
+        metadata.canonical_url = get_canonical_url(document, base_url);
+        metadata.icon = get_icon(document, base_url);
+        if use_json_ld {
+            metadata.recipe = get_recipe(document);
+        }
+        #[cfg(feature = "dates")]
+        {
+            metadata.published = metadata
+                .published_time
+                .as_deref()
+                .and_then(crate::dates::parse_date)
+                .or_else(|| crate::dates::time_element_date(document, PUBLISHED_TIME_SELECTOR))
+                .or_else(|| crate::dates::time_element_date(document, "time[datetime]"));
+            metadata.modified = metadata
+                .modified_time
+                .as_deref()
+                .and_then(crate::dates::parse_date)
+                .or_else(|| crate::dates::time_element_date(document, MODIFIED_TIME_SELECTOR));
+        }
+        metadata
+    }
 }
 
+/// `<time>` elements marked as the publish date.
+#[cfg(feature = "dates")]
+const PUBLISHED_TIME_SELECTOR: &str = r#"time[itemprop~="datePublished"][datetime], time[pubdate][datetime], time.published[datetime], time.entry-date[datetime]"#;
+
+/// `<time>` elements marked as the last-modified date.
+#[cfg(feature = "dates")]
+const MODIFIED_TIME_SELECTOR: &str =
+    r#"time[itemprop~="dateModified"][datetime], time.updated[datetime]"#;
+
 /// Extract JSON-LD structured data from document
 ///
 /// Looks for <script type="application/ld+json"> tags and parses them for article metadata.
@@ -43,14 +172,7 @@ pub fn get_json_ld(document: &Html) -> Metadata {
 
         if let Ok(mut parsed) = serde_json::from_str::<Value>(content) {
             if let Some(arr) = parsed.as_array() {
-                if let Some(article) = arr.iter().find(|item| {
-                    if let Some(type_val) = item.get("@type") {
-                        if let Some(type_str) = type_val.as_str() {
-                            return REGEXPS.json_ld_article_types.is_match(type_str);
-                        }
-                    }
-                    false
-                }) {
+                if let Some(article) = arr.iter().find(|item| is_json_ld_article(item)) {
                     parsed = article.clone();
                 } else {
                     continue;
@@ -81,32 +203,21 @@ pub fn get_json_ld(document: &Html) -> Metadata {
             // Check for @graph array
             if parsed.get("@type").is_none() {
                 if let Some(graph) = parsed.get("@graph").and_then(|g| g.as_array()) {
-                    if let Some(article) = graph.iter().find(|item| {
-                        if let Some(type_val) = item.get("@type") {
-                            if let Some(type_str) = type_val.as_str() {
-                                return REGEXPS.json_ld_article_types.is_match(type_str);
-                            }
-                        }
-                        false
-                    }) {
+                    if let Some(article) = graph.iter().find(|item| is_json_ld_article(item)) {
                         parsed = article.clone();
                     }
                 }
             }
 
             // Verify it's an article type
-            if let Some(type_val) = parsed.get("@type") {
-                if let Some(type_str) = type_val.as_str() {
-                    if !REGEXPS.json_ld_article_types.is_match(type_str) {
-                        continue;
-                    }
-                } else {
-                    continue;
-                }
-            } else {
+            if !is_json_ld_article(&parsed) {
                 continue;
             }
 
+            if metadata.json_ld.is_none() {
+                metadata.json_ld = Some(parsed.clone());
+            }
+
             // Extract title (name or headline)
             // Schema.org is flexible: "name" can be the article title OR publisher name
             // Heuristic: if "name" matches publisher name, use "headline" instead
@@ -170,6 +281,24 @@ pub fn get_json_ld(document: &Html) -> Metadata {
                 }
             }
 
+            if metadata.modified_time.is_none() {
+                if let Some(date_modified) = parsed.get("dateModified").and_then(|v| v.as_str()) {
+                    metadata.modified_time = Some(date_modified.trim().to_string());
+                }
+            }
+
+            // articleSection may list several sections; the first is the main one
+            if metadata.section.is_none() {
+                metadata.section = match parsed.get("articleSection") {
+                    Some(Value::Array(sections)) => sections.iter().find_map(|v| v.as_str()),
+                    Some(section) => section.as_str(),
+                    None => None,
+                }
+                .map(str::trim)
+                .filter(|section| !section.is_empty())
+                .map(str::to_string);
+            }
+
             // Extract image from JSON-LD
             if metadata.image.is_none() {
                 metadata.image = extract_json_ld_image(&parsed);
@@ -180,6 +309,20 @@ pub fn get_json_ld(document: &Html) -> Metadata {
     metadata
 }
 
+/// Whether a JSON-LD object's `@type` is an article type
+///
+/// `@type` may be a single type or a list such as `["NewsArticle", "Article"]`.
+fn is_json_ld_article(value: &Value) -> bool {
+    match value.get("@type") {
+        Some(Value::String(type_str)) => REGEXPS.json_ld_article_types.is_match(type_str),
+        Some(Value::Array(types)) => types
+            .iter()
+            .filter_map(Value::as_str)
+            .any(|type_str| REGEXPS.json_ld_article_types.is_match(type_str)),
+        _ => false,
+    }
+}
+
 /// Extract image URL from JSON-LD data
 ///
 /// Handles various Schema.org image formats:
@@ -250,7 +393,7 @@ fn extract_json_ld_image(parsed: &Value) -> Option<String> {
 pub fn get_article_metadata(document: &Html, json_ld: Metadata) -> Metadata {
     let mut values: HashMap<String, String> = HashMap::new();
     let property_pattern = regex::Regex::new(
-        r"(?i)\s*(article|dc|dcterm|og|twitter)\s*:\s*(author|creator|description|published_time|title|site_name|image:url|image:secure_url|image$)\s*"
+        r"(?i)\s*(article|dc|dcterm|og|twitter)\s*:\s*(author|creator|description|published_time|modified_time|updated_time|section|title|site_name|image:url|image:secure_url|image$)\s*"
     ).unwrap();
 
     let name_pattern = regex::Regex::new(
@@ -375,6 +518,19 @@ pub fn get_article_metadata(document: &Html, json_ld: Metadata) -> Metadata {
             .cloned()
     });
 
+    metadata.json_ld = json_ld.json_ld;
+
+    metadata.section = json_ld
+        .section
+        .or_else(|| values.get("article:section").cloned());
+
+    metadata.modified_time = json_ld.modified_time.or_else(|| {
+        values
+            .get("article:modified_time")
+            .or_else(|| values.get("og:updated_time"))
+            .cloned()
+    });
+
     // Extract image from meta tags with priority order
     metadata.image = json_ld.image.or_else(|| {
         values
@@ -452,6 +608,9 @@ pub fn get_article_metadata(document: &Html, json_ld: Metadata) -> Metadata {
     metadata.published_time = metadata
         .published_time
         .map(|p| utils::unescape_html_entities(&p));
+    metadata.modified_time = metadata
+        .modified_time
+        .map(|m| utils::unescape_html_entities(&m));
 
     // Clean up image URL
     metadata.image = metadata.image.and_then(|img| {
@@ -1360,16 +1519,13 @@ fn is_noise_byline_context(element: &ElementRef) -> bool {
 /// 1. <html lang=""> attribute
 /// 2. Content-Language meta tag
 /// 3. http-equiv="Content-Language"
-fn extract_language_from_document(document: &Html) -> Option<String> {
-    if let Some(html_elem) = document.root_element().first_child() {
-        if let Some(node_ref) = scraper::ElementRef::wrap(html_elem) {
-            if node_ref.value().name() == "html" {
-                if let Some(lang) = node_ref.value().attr("lang") {
-                    let lang = lang.trim();
-                    if !lang.is_empty() {
-                        return Some(lang.to_string());
-                    }
-                }
+pub(crate) fn extract_language_from_document(document: &Html) -> Option<String> {
+    let html_elem = document.root_element();
+    if html_elem.value().name() == "html" {
+        if let Some(lang) = html_elem.value().attr("lang") {
+            let lang = lang.trim();
+            if !lang.is_empty() {
+                return Some(lang.to_string());
             }
         }
     }
@@ -1401,6 +1557,533 @@ fn extract_language_from_document(document: &Html) -> Option<String> {
     None
 }
 
+/// Extract alternate-language versions of the page
+///
+/// Collects `<link rel="alternate" hreflang="...">` entries as `(lang, url)` pairs,
+/// resolving each URL against `base_url` when one is available. Duplicate
+/// languages keep their first occurrence.
+pub fn get_alternate_links(document: &Html, base_url: Option<&str>) -> Vec<(String, String)> {
+    let selector = Selector::parse("link[hreflang][href]").unwrap();
+    let base = base_url.and_then(|b| url::Url::parse(b).ok());
+    let mut alternates: Vec<(String, String)> = Vec::new();
+
+    for link in document.select(&selector) {
+        if !link_rel_contains(link, "alternate") {
+            continue;
+        }
+
+        let lang = link.value().attr("hreflang").unwrap_or("").trim();
+        let href = link.value().attr("href").unwrap_or("").trim();
+        if lang.is_empty() || href.is_empty() {
+            continue;
+        }
+
+        let resolved = resolve_url(base.as_ref(), href);
+
+        if alternates
+            .iter()
+            .any(|(existing, _)| existing.eq_ignore_ascii_case(lang))
+        {
+            continue;
+        }
+        alternates.push((lang.to_string(), utils::unescape_html_entities(&resolved)));
+    }
+
+    alternates
+}
+
+/// Extract the canonical URL of the page
+///
+/// Prefers `<link rel="canonical">` and falls back to the `og:url` meta tag.
+pub fn get_canonical_url(document: &Html, base_url: Option<&str>) -> Option<String> {
+    let base = base_url.and_then(|b| url::Url::parse(b).ok());
+    let link_selector = Selector::parse("link[rel][href]").unwrap();
+    let og_selector = Selector::parse("meta[property='og:url'][content]").unwrap();
+
+    let href = document
+        .select(&link_selector)
+        .find(|link| link_rel_contains(*link, "canonical"))
+        .and_then(|link| link.value().attr("href"))
+        .or_else(|| {
+            document
+                .select(&og_selector)
+                .next()
+                .and_then(|meta| meta.value().attr("content"))
+        })?
+        .trim();
+
+    if href.is_empty() {
+        return None;
+    }
+    Some(utils::unescape_html_entities(&resolve_url(base.as_ref(), href)))
+}
+
+/// Extract the site icon URL
+///
+/// Considers `<link rel="icon">` (including `rel="shortcut icon"`) and
+/// `<link rel="apple-touch-icon">`, preferring the largest declared `sizes`.
+/// `sizes="any"` (scalable icons) wins over any fixed size, and an
+/// apple-touch-icon without `sizes` counts as 180x180, its platform default.
+/// Falls back to `og:image` when the page declares no icon.
+pub fn get_icon(document: &Html, base_url: Option<&str>) -> Option<String> {
+    let base = base_url.and_then(|b| url::Url::parse(b).ok());
+    let link_selector = Selector::parse("link[rel][href]").unwrap();
+
+    let mut best: Option<(u32, &str)> = None;
+    for link in document.select(&link_selector) {
+        let is_touch_icon = link_rel_contains(link, "apple-touch-icon")
+            || link_rel_contains(link, "apple-touch-icon-precomposed");
+        if !is_touch_icon && !link_rel_contains(link, "icon") {
+            continue;
+        }
+        let href = link.value().attr("href").unwrap_or("").trim();
+        if href.is_empty() {
+            continue;
+        }
+
+        let size = match link.value().attr("sizes") {
+            Some(sizes) => icon_size(sizes),
+            None if is_touch_icon => 180,
+            None => 0,
+        };
+        if best.is_none_or(|(best_size, _)| size > best_size) {
+            best = Some((size, href));
+        }
+    }
+
+    let href = match best {
+        Some((_, href)) => href,
+        None => {
+            let og_selector = Selector::parse("meta[property='og:image'][content]").unwrap();
+            document
+                .select(&og_selector)
+                .next()
+                .and_then(|meta| meta.value().attr("content"))?
+                .trim()
+        }
+    };
+
+    if href.is_empty() {
+        return None;
+    }
+    Some(utils::unescape_html_entities(&resolve_url(base.as_ref(), href)))
+}
+
+/// Largest edge declared in a `sizes` attribute such as `"16x16 32x32"` or `"any"`.
+fn icon_size(sizes: &str) -> u32 {
+    sizes
+        .split_whitespace()
+        .filter_map(|size| {
+            if size.eq_ignore_ascii_case("any") {
+                return Some(u32::MAX);
+            }
+            let (w, h) = size.to_ascii_lowercase().split_once('x').map(|(w, h)| {
+                (w.parse::<u32>().ok(), h.parse::<u32>().ok())
+            })?;
+            Some(w?.max(h?))
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+fn link_rel_contains(link: ElementRef, token: &str) -> bool {
+    link.value()
+        .attr("rel")
+        .map(|rel| rel.split_whitespace().any(|r| r.eq_ignore_ascii_case(token)))
+        .unwrap_or(false)
+}
+
+fn resolve_url(base: Option<&url::Url>, href: &str) -> String {
+    match base {
+        Some(base) => base
+            .join(href)
+            .map(|u| u.to_string())
+            .unwrap_or_else(|_| href.to_string()),
+        None => href.to_string(),
+    }
+}
+
+/// Extract recipe ingredients and steps from schema.org JSON-LD
+///
+/// Looks for a `Recipe` or `HowTo` object at the top level, inside an array, or
+/// inside an `@graph`. Instructions may be plain strings, `HowToStep` objects, or
+/// `HowToSection`s grouping further steps; they are flattened in document order.
+pub fn get_recipe(document: &Html) -> Option<Recipe> {
+    let script_selector = Selector::parse("script[type='application/ld+json']").unwrap();
+
+    for script in document.select(&script_selector) {
+        let content = script.text().collect::<String>();
+        let content = content
+            .trim()
+            .trim_start_matches("<![CDATA[")
+            .trim_end_matches("]]>")
+            .trim();
+
+        let Ok(parsed) = serde_json::from_str::<Value>(content) else {
+            continue;
+        };
+
+        if let Some(recipe) = find_recipe_object(&parsed).and_then(parse_recipe) {
+            return Some(recipe);
+        }
+    }
+
+    None
+}
+
+fn find_recipe_object(value: &Value) -> Option<&Value> {
+    if let Some(items) = value.as_array() {
+        return items.iter().find_map(find_recipe_object);
+    }
+
+    let is_recipe = |type_str: &str| type_str == "Recipe" || type_str == "HowTo";
+    let matches = match value.get("@type") {
+        Some(Value::String(t)) => is_recipe(t),
+        Some(Value::Array(types)) => types.iter().filter_map(|t| t.as_str()).any(is_recipe),
+        _ => false,
+    };
+    if matches {
+        return Some(value);
+    }
+
+    value.get("@graph").and_then(find_recipe_object)
+}
+
+fn parse_recipe(value: &Value) -> Option<Recipe> {
+    let mut ingredients = Vec::new();
+    for key in ["recipeIngredient", "ingredients", "supply"] {
+        if let Some(list) = value.get(key) {
+            collect_recipe_texts(list, &mut ingredients);
+            break;
+        }
+    }
+
+    let mut instructions = Vec::new();
+    for key in ["recipeInstructions", "step"] {
+        if let Some(list) = value.get(key) {
+            collect_recipe_texts(list, &mut instructions);
+            break;
+        }
+    }
+
+    if ingredients.is_empty() && instructions.is_empty() {
+        return None;
+    }
+    Some(Recipe {
+        ingredients,
+        instructions,
+    })
+}
+
+fn collect_recipe_texts(value: &Value, out: &mut Vec<String>) {
+    match value {
+        Value::String(text) => {
+            for line in text.lines() {
+                let line = utils::normalize_whitespace(line.trim());
+                if !line.is_empty() {
+                    out.push(utils::unescape_html_entities(&line));
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_recipe_texts(item, out);
+            }
+        }
+        Value::Object(obj) => {
+            if let Some(children) = obj.get("itemListElement") {
+                collect_recipe_texts(children, out);
+            } else if let Some(text) = obj.get("text").or_else(|| obj.get("name")) {
+                collect_recipe_texts(text, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Extract the breadcrumb trail of the page
+///
+/// Prefers a schema.org `BreadcrumbList` from JSON-LD (when `use_json_ld` is
+/// set), ordered by `position`. Falls back to `BreadcrumbList` microdata, then
+/// to the innermost navigation markup labelled or classed as a breadcrumb,
+/// taking its list items (or links when there are none) and skipping bare
+/// separators.
+pub fn get_breadcrumbs(document: &Html, use_json_ld: bool) -> Vec<String> {
+    if use_json_ld {
+        let script_selector = Selector::parse("script[type='application/ld+json']").unwrap();
+        for script in document.select(&script_selector) {
+            let content = script.text().collect::<String>();
+            let content = content
+                .trim()
+                .trim_start_matches("<![CDATA[")
+                .trim_end_matches("]]>")
+                .trim();
+
+            let Ok(parsed) = serde_json::from_str::<Value>(content) else {
+                continue;
+            };
+
+            let crumbs = find_breadcrumb_list(&parsed)
+                .map(parse_breadcrumb_list)
+                .unwrap_or_default();
+            if !crumbs.is_empty() {
+                return crumbs;
+            }
+        }
+    }
+
+    static MICRODATA_SELECTOR: Lazy<Selector> = Lazy::new(|| {
+        Selector::parse("[itemtype*='BreadcrumbList'] [itemprop='itemListElement']").unwrap()
+    });
+    static MICRODATA_NAME_SELECTOR: Lazy<Selector> =
+        Lazy::new(|| Selector::parse("[itemprop='name']").unwrap());
+    let crumbs: Vec<String> = document
+        .select(&MICRODATA_SELECTOR)
+        .filter_map(|item| item.select(&MICRODATA_NAME_SELECTOR).next())
+        .filter_map(|name| breadcrumb_text(&name.text().collect::<String>()))
+        .collect();
+    if !crumbs.is_empty() {
+        return crumbs;
+    }
+
+    static CONTAINER_SELECTOR: Lazy<Selector> =
+        Lazy::new(|| Selector::parse("nav, ol, ul, div").unwrap());
+    static LI_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("li").unwrap());
+    static A_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("a").unwrap());
+    let is_breadcrumb = |container: ElementRef| {
+        let el = container.value();
+        [el.attr("aria-label"), el.attr("class"), el.attr("id")]
+            .into_iter()
+            .flatten()
+            .any(|value| value.to_lowercase().contains("breadcrumb"))
+    };
+    for container in document.select(&CONTAINER_SELECTOR) {
+        // Take the innermost labelled container, so a page-wide wrapper that
+        // merely holds the trail does not pull in unrelated lists.
+        if !is_breadcrumb(container)
+            || container
+                .select(&CONTAINER_SELECTOR)
+                .any(|inner| inner.id() != container.id() && is_breadcrumb(inner))
+        {
+            continue;
+        }
+
+        let mut items: Vec<ElementRef> = container.select(&LI_SELECTOR).collect();
+        if items.is_empty() {
+            items = container.select(&A_SELECTOR).collect();
+        }
+        let crumbs: Vec<String> = items
+            .into_iter()
+            .filter_map(|item| breadcrumb_text(&item.text().collect::<String>()))
+            .collect();
+        if !crumbs.is_empty() {
+            return crumbs;
+        }
+    }
+
+    Vec::new()
+}
+
+fn find_breadcrumb_list(value: &Value) -> Option<&Value> {
+    if let Some(items) = value.as_array() {
+        return items.iter().find_map(find_breadcrumb_list);
+    }
+
+    let matches = match value.get("@type") {
+        Some(Value::String(t)) => t == "BreadcrumbList",
+        Some(Value::Array(types)) => types.iter().any(|t| t == "BreadcrumbList"),
+        _ => false,
+    };
+    if matches {
+        return Some(value);
+    }
+
+    value.get("@graph").and_then(find_breadcrumb_list)
+}
+
+fn parse_breadcrumb_list(value: &Value) -> Vec<String> {
+    let Some(items) = value.get("itemListElement").and_then(|v| v.as_array()) else {
+        return Vec::new();
+    };
+
+    let mut crumbs: Vec<(f64, String)> = items
+        .iter()
+        .enumerate()
+        .filter_map(|(index, item)| {
+            let name = item
+                .get("name")
+                .or_else(|| item.get("item").and_then(|inner| inner.get("name")))
+                .and_then(|name| name.as_str())
+                .and_then(breadcrumb_text)?;
+            let position = match item.get("position") {
+                Some(Value::Number(n)) => n.as_f64(),
+                Some(Value::String(s)) => s.trim().parse().ok(),
+                _ => None,
+            }
+            .unwrap_or(index as f64);
+            Some((position, name))
+        })
+        .collect();
+
+    crumbs.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+    crumbs.into_iter().map(|(_, name)| name).collect()
+}
+
+/// Normalize a breadcrumb label, dropping separators such as `>` or `/`.
+fn breadcrumb_text(text: &str) -> Option<String> {
+    let text = utils::normalize_whitespace(text.trim());
+    let text = text.trim_matches(|c: char| matches!(c, '>' | '/' | '»' | '›' | '|' | '→') || c.is_whitespace());
+    if text.is_empty() {
+        return None;
+    }
+    Some(utils::unescape_html_entities(text))
+}
+
+/// Separators between a page title and the site name, such as `Title | Site`.
+static SITE_NAME_SEPARATOR_REGEX: Lazy<regex::Regex> =
+    Lazy::new(|| regex::Regex::new(r"\s(?:\||-|–|—|·|•|»)\s").unwrap());
+
+/// Infer the site name for pages without `og:site_name`
+///
+/// Tries, in order: a `publisher.name` anywhere in the JSON-LD (when
+/// `use_json_ld` is set, including `@graph` entries and non-article types),
+/// then a `WebSite` entry's `name`, the trailing segment of the `<title>`
+/// after a separator (`Headline | Site`), and finally the hostname of
+/// `base_url` without its `www.` prefix.
+pub fn infer_site_name(
+    document: &Html,
+    base_url: Option<&str>,
+    use_json_ld: bool,
+) -> Option<String> {
+    let from_json_ld = if use_json_ld {
+        let script_selector = Selector::parse("script[type='application/ld+json']").unwrap();
+        let values: Vec<Value> = document
+            .select(&script_selector)
+            .filter_map(|script| {
+                let content = script.text().collect::<String>();
+                let content = content
+                    .trim()
+                    .trim_start_matches("<![CDATA[")
+                    .trim_end_matches("]]>")
+                    .trim();
+                serde_json::from_str::<Value>(content).ok()
+            })
+            .collect();
+        values
+            .iter()
+            .find_map(find_publisher_name)
+            .or_else(|| values.iter().find_map(find_website_name))
+    } else {
+        None
+    };
+
+    from_json_ld
+        .or_else(|| {
+            let title_selector = Selector::parse("title").unwrap();
+            let title = document.select(&title_selector).next()?;
+            let title = title.text().collect::<String>();
+            title_site_name(&utils::unescape_html_entities(title.trim()))
+        })
+        .or_else(|| hostname_site_name(base_url?))
+}
+
+fn find_publisher_name(value: &Value) -> Option<String> {
+    if let Some(items) = value.as_array() {
+        return items.iter().find_map(find_publisher_name);
+    }
+
+    let publisher = match value.get("publisher") {
+        Some(Value::Array(publishers)) => publishers.first(),
+        publisher => publisher,
+    };
+    publisher
+        .and_then(|publisher| publisher.get("name"))
+        .and_then(|name| name.as_str())
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .or_else(|| value.get("@graph").and_then(find_publisher_name))
+}
+
+fn find_website_name(value: &Value) -> Option<String> {
+    if let Some(items) = value.as_array() {
+        return items.iter().find_map(find_website_name);
+    }
+
+    if value.get("@type").and_then(|t| t.as_str()) == Some("WebSite") {
+        if let Some(name) = value.get("name").and_then(|name| name.as_str()) {
+            if !name.trim().is_empty() {
+                return Some(name.trim().to_string());
+            }
+        }
+    }
+
+    value.get("@graph").and_then(find_website_name)
+}
+
+/// The short trailing segment of a `Headline | Site` style title.
+fn title_site_name(title: &str) -> Option<String> {
+    const MAX_SITE_NAME_WORDS: usize = 4;
+
+    let separator = SITE_NAME_SEPARATOR_REGEX.find_iter(title).last()?;
+    let headline = title[..separator.start()].trim();
+    let site_name = title[separator.end()..].trim();
+    let words = site_name.split_whitespace().count();
+    if words == 0 || words > MAX_SITE_NAME_WORDS || site_name.len() >= headline.len() {
+        return None;
+    }
+    Some(site_name.to_string())
+}
+
+/// The hostname of a URL without `www.`, e.g. `example.com`.
+fn hostname_site_name(base_url: &str) -> Option<String> {
+    let url = url::Url::parse(base_url).ok()?;
+    let host = url.host_str()?.trim_end_matches('.').to_lowercase();
+    let host = host.strip_prefix("www.").unwrap_or(&host);
+    if host.is_empty() {
+        return None;
+    }
+    Some(host.to_string())
+}
+
+/// Remove a leading or trailing site name from a title
+///
+/// Strips `site_name` when it is separated from the rest of the title, as in
+/// `Headline | Site` or `Site - Headline` (case-insensitive). The title is
+/// returned unchanged when nothing would be left.
+pub fn strip_site_name_from_title(title: &str, site_name: &str) -> String {
+    let site_name = site_name.trim().to_lowercase();
+    if site_name.is_empty() {
+        return title.to_string();
+    }
+
+    let separators: Vec<_> = SITE_NAME_SEPARATOR_REGEX.find_iter(title).collect();
+    if let Some(last) = separators.last() {
+        let headline = title[..last.start()].trim();
+        if title[last.end()..].trim().to_lowercase() == site_name && !headline.is_empty() {
+            return headline.to_string();
+        }
+    }
+    if let Some(first) = separators.first() {
+        let headline = title[first.end()..].trim();
+        if title[..first.start()].trim().to_lowercase() == site_name && !headline.is_empty() {
+            return headline.to_string();
+        }
+    }
+    title.to_string()
+}
+
+/// Title taken from the first non-empty `<h1>`
+///
+/// Fallback for pages whose metadata and `<title>` give no title at all.
+fn get_heading_title(document: &Html) -> Option<String> {
+    static H1_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("h1").unwrap());
+    document
+        .select(&H1_SELECTOR)
+        .map(|h1| utils::normalize_whitespace(h1.text().collect::<String>().trim()))
+        .find(|text| !text.is_empty())
+}
+
 /// Extract and clean the title from the document's <title> tag
 ///
 /// Implements sophisticated heuristics to remove site names and clean up titles.
@@ -1522,6 +2205,100 @@ mod tests {
         assert_eq!(metadata.excerpt, Some("Test description".to_string()));
     }
 
+    #[test]
+    fn test_json_ld_type_list() {
+        let html = r#"
+            <html>
+                <head>
+                    <script type="application/ld+json">{ "@context": "https://schema.org", "broken": </script>
+                    <script type="application/ld+json">
+                    {
+                        "@context": "https://schema.org",
+                        "@graph": [
+                            {"@type": ["WebSite"], "name": "Example"},
+                            {
+                                "@type": ["NewsArticle", "Article"],
+                                "headline": "Test Article",
+                                "author": {"name": "John Doe"},
+                                "datePublished": "2024-05-01T08:00:00Z",
+                                "publisher": {"name": "Example News"}
+                            }
+                        ]
+                    }
+                    </script>
+                </head>
+            </html>
+        "#;
+
+        let metadata = get_json_ld(&Html::parse_document(html));
+        assert_eq!(metadata.title.as_deref(), Some("Test Article"));
+        assert_eq!(metadata.byline.as_deref(), Some("John Doe"));
+        assert_eq!(
+            metadata.published_time.as_deref(),
+            Some("2024-05-01T08:00:00Z")
+        );
+        assert_eq!(metadata.site_name.as_deref(), Some("Example News"));
+    }
+
+    #[test]
+    fn test_section_extraction() {
+        let meta_only = r#"<html><head>
+<meta property="article:section" content=" Gardening ">
+</head></html>"#;
+        assert_eq!(
+            Metadata::from_html(meta_only, None).section.as_deref(),
+            Some("Gardening")
+        );
+
+        let with_json_ld = r#"<html><head>
+<meta property="article:section" content="Gardening">
+<script type="application/ld+json">
+{"@context": "https://schema.org", "@type": "BlogPosting", "headline": "Basil", "articleSection": ["Recipes", "Herbs"]}
+</script>
+</head></html>"#;
+        assert_eq!(
+            Metadata::from_html(with_json_ld, None).section.as_deref(),
+            Some("Recipes")
+        );
+        assert_eq!(Metadata::from_html("<html></html>", None).section, None);
+    }
+
+    #[test]
+    fn test_json_ld_raw_object() {
+        let html = r#"
+            <html>
+                <head>
+                    <script type="application/ld+json">
+                    {
+                        "@context": "https://schema.org",
+                        "@graph": [
+                            {"@type": "WebSite", "name": "Example"},
+                            {
+                                "@type": "NewsArticle",
+                                "headline": "Test Article",
+                                "keywords": ["rust", "parsing"],
+                                "articleSection": "Technology",
+                                "wordCount": 1200
+                            }
+                        ]
+                    }
+                    </script>
+                </head>
+            </html>
+        "#;
+
+        let metadata = Metadata::from_html(html, None);
+        let raw = metadata.json_ld.expect("article object");
+        assert_eq!(raw["@type"], "NewsArticle");
+        assert_eq!(raw["articleSection"], "Technology");
+        assert_eq!(raw["wordCount"], 1200);
+        assert_eq!(raw["keywords"][1], "parsing");
+
+        let document = Html::parse_document(html);
+        assert_eq!(get_json_ld(&document).json_ld, Some(raw));
+        assert_eq!(Metadata::from_html("<html></html>", None).json_ld, None);
+    }
+
     #[test]
     fn test_json_ld_image_extraction() {
         let html = r#"
@@ -1790,6 +2567,341 @@ mod tests {
         assert_eq!(metadata.byline, Some("Hazel Sheffield".to_string()));
     }
 
+    #[test]
+    fn test_recipe_extraction_from_graph() {
+        let html = r#"<html><head><script type="application/ld+json">
+        {
+            "@context": "https://schema.org",
+            "@graph": [
+                {"@type": "WebPage", "name": "Bread"},
+                {
+                    "@type": ["Recipe"],
+                    "recipeIngredient": ["500 g flour", "10 g salt", "350 ml water"],
+                    "recipeInstructions": [
+                        {"@type": "HowToSection", "name": "Dough", "itemListElement": [
+                            {"@type": "HowToStep", "text": "Mix the flour &amp; salt."},
+                            {"@type": "HowToStep", "text": "Add water and knead."}
+                        ]},
+                        "Bake for 40 minutes."
+                    ]
+                }
+            ]
+        }
+        </script></head><body></body></html>"#;
+        let document = Html::parse_document(html);
+        let recipe = get_recipe(&document).expect("recipe should be found");
+        assert_eq!(
+            recipe.ingredients,
+            vec!["500 g flour", "10 g salt", "350 ml water"]
+        );
+        assert_eq!(
+            recipe.instructions,
+            vec![
+                "Mix the flour & salt.",
+                "Add water and knead.",
+                "Bake for 40 minutes."
+            ]
+        );
+    }
+
+    #[test]
+    fn test_howto_extraction_and_missing_recipe() {
+        let html = r#"<html><head><script type="application/ld+json">
+        {"@context": "https://schema.org", "@type": "HowTo",
+         "supply": [{"@type": "HowToSupply", "name": "Sandpaper"}],
+         "step": "Sand the board.\nApply the varnish."}
+        </script></head><body></body></html>"#;
+        let recipe = get_recipe(&Html::parse_document(html)).unwrap();
+        assert_eq!(recipe.ingredients, vec!["Sandpaper"]);
+        assert_eq!(
+            recipe.instructions,
+            vec!["Sand the board.", "Apply the varnish."]
+        );
+
+        let article = r#"<html><head><script type="application/ld+json">
+        {"@context": "https://schema.org", "@type": "NewsArticle", "headline": "News"}
+        </script></head><body></body></html>"#;
+        assert!(get_recipe(&Html::parse_document(article)).is_none());
+    }
+
+    #[test]
+    fn test_language_from_html_lang_attribute() {
+        let document = Html::parse_document(r#"<html lang=" ja "><head></head><body></body></html>"#);
+        assert_eq!(extract_language_from_document(&document).as_deref(), Some("ja"));
+
+        let document = Html::parse_document(
+            r#"<html><head><meta http-equiv="Content-Language" content="th"></head></html>"#,
+        );
+        assert_eq!(extract_language_from_document(&document).as_deref(), Some("th"));
+    }
+
+    #[test]
+    fn test_metadata_from_html_fixture() {
+        let html = r#"<!DOCTYPE html>
+<html lang="en-GB">
+<head>
+    <title>Ignored Title | Example Times</title>
+    <meta name="description" content="Plain meta description">
+    <meta property="og:title" content="Open Graph Title">
+    <meta property="og:description" content="Open Graph description">
+    <meta property="og:site_name" content="Example Times">
+    <meta property="og:image" content="https://cdn.example.com/lead.jpg">
+    <meta property="og:url" content="https://example.com/og-url">
+    <meta name="twitter:card" content="summary_large_image">
+    <meta name="twitter:title" content="Twitter Title">
+    <meta property="article:published_time" content="2024-03-01T09:00:00Z">
+    <link rel="canonical" href="/news/story?id=1&amp;ref=feed">
+    <link rel="shortcut icon" href="/favicon.ico">
+    <link rel="alternate" hreflang="fr" href="/fr/news/story">
+    <script type="application/ld+json">
+    {"@context": "https://schema.org", "@type": "NewsArticle",
+     "headline": "JSON-LD Headline",
+     "author": {"@type": "Person", "name": "Jane Reporter"},
+     "datePublished": "2024-03-01T08:00:00Z"}
+    </script>
+</head>
+<body><p>Body text is not needed for metadata.</p></body>
+</html>"#;
+
+        let metadata = Metadata::from_html(html, Some("https://example.com/news/"));
+        assert_eq!(metadata.title.as_deref(), Some("JSON-LD Headline"));
+        assert_eq!(metadata.byline.as_deref(), Some("Jane Reporter"));
+        assert_eq!(metadata.excerpt.as_deref(), Some("Open Graph description"));
+        assert_eq!(metadata.site_name.as_deref(), Some("Example Times"));
+        assert_eq!(metadata.published_time.as_deref(), Some("2024-03-01T08:00:00Z"));
+        assert_eq!(metadata.lang.as_deref(), Some("en-GB"));
+        assert_eq!(metadata.image.as_deref(), Some("https://cdn.example.com/lead.jpg"));
+        assert_eq!(
+            metadata.canonical_url.as_deref(),
+            Some("https://example.com/news/story?id=1&ref=feed")
+        );
+        assert_eq!(metadata.icon.as_deref(), Some("https://example.com/favicon.ico"));
+        assert_eq!(
+            metadata.alternates,
+            vec![("fr".to_string(), "https://example.com/fr/news/story".to_string())]
+        );
+        assert!(metadata.recipe.is_none());
+    }
+
+    #[test]
+    fn test_modified_time() {
+        let html = r#"<html><head>
+            <meta property="article:modified_time" content="2024-03-02T10:00:00Z">
+            <meta property="og:updated_time" content="2024-03-03T10:00:00Z">
+        </head><body></body></html>"#;
+        let metadata = Metadata::from_html(html, None);
+        assert_eq!(
+            metadata.modified_time.as_deref(),
+            Some("2024-03-02T10:00:00Z")
+        );
+
+        let html = r#"<html><head>
+            <meta property="og:updated_time" content="2024-03-03T10:00:00Z">
+            <script type="application/ld+json">
+            {"@context": "https://schema.org", "@type": "Article", "dateModified": "2024-03-04"}
+            </script>
+        </head><body></body></html>"#;
+        let metadata = Metadata::from_html(html, None);
+        assert_eq!(metadata.modified_time.as_deref(), Some("2024-03-04"));
+    }
+
+    #[cfg(feature = "dates")]
+    #[test]
+    fn test_parsed_dates() {
+        let rfc3339 = |date: Option<chrono::DateTime<chrono::FixedOffset>>| {
+            date.map(|date| date.to_rfc3339())
+        };
+
+        let html = r#"<html><head>
+            <meta property="article:published_time" content="Fri, 01 Mar 2024 09:30:00 +0100">
+            <meta property="article:modified_time" content="2024-03-02 10:00">
+        </head><body></body></html>"#;
+        let metadata = Metadata::from_html(html, None);
+        assert_eq!(
+            rfc3339(metadata.published).as_deref(),
+            Some("2024-03-01T09:30:00+01:00")
+        );
+        assert_eq!(
+            rfc3339(metadata.modified).as_deref(),
+            Some("2024-03-02T10:00:00+00:00")
+        );
+
+        // Unparseable metadata falls back to marked `<time>` elements, and
+        // the raw string is kept.
+        let html = r#"<html><head>
+            <meta property="article:published_time" content="sometime last spring">
+        </head><body><article>
+            <time datetime="2023-12-24">Comment date</time>
+            <time itemprop="datePublished" datetime="March 1, 2024">1 Mar</time>
+            <time itemprop="dateModified" datetime="2024-03-05T08:00:00-05:00">5 Mar</time>
+        </article></body></html>"#;
+        let metadata = Metadata::from_html(html, None);
+        assert_eq!(
+            metadata.published_time.as_deref(),
+            Some("sometime last spring")
+        );
+        assert_eq!(
+            rfc3339(metadata.published).as_deref(),
+            Some("2024-03-01T00:00:00+00:00")
+        );
+        assert_eq!(
+            rfc3339(metadata.modified).as_deref(),
+            Some("2024-03-05T08:00:00-05:00")
+        );
+
+        let html = r#"<html><body><p>Posted <time datetime="2024-02-29">yesterday</time></p></body></html>"#;
+        let metadata = Metadata::from_html(html, None);
+        assert_eq!(
+            rfc3339(metadata.published).as_deref(),
+            Some("2024-02-29T00:00:00+00:00")
+        );
+        assert_eq!(metadata.modified, None);
+    }
+
+    #[test]
+    fn test_metadata_from_html_without_json_ld_or_canonical_link() {
+        let html = r#"<html><head>
+            <meta name="twitter:title" content="Twitter Title">
+            <meta name="twitter:description" content="Twitter description">
+            <meta name="twitter:image" content="/card.png">
+            <meta property="og:url" content="https://example.com/og-url">
+        </head><body></body></html>"#;
+
+        let metadata = Metadata::from_html(html, None);
+        assert_eq!(metadata.title.as_deref(), Some("Twitter Title"));
+        assert_eq!(metadata.excerpt.as_deref(), Some("Twitter description"));
+        assert_eq!(metadata.image.as_deref(), Some("/card.png"));
+        assert_eq!(metadata.canonical_url.as_deref(), Some("https://example.com/og-url"));
+        assert!(metadata.icon.is_none());
+        assert!(metadata.lang.is_none());
+    }
+
+    #[test]
+    fn test_icon_prefers_highest_resolution() {
+        let html = r#"<html><head>
+            <link rel="shortcut icon" href="/favicon.ico">
+            <link rel="icon" type="image/png" sizes="16x16 32x32" href="/icon-32.png">
+            <link rel="icon" type="image/png" sizes="192x192" href="/icon-192.png">
+            <link rel="apple-touch-icon" href="/apple-touch-icon.png">
+            <link rel="stylesheet" href="/big.css">
+            <meta property="og:image" content="/og.jpg">
+        </head><body></body></html>"#;
+        let document = Html::parse_document(html);
+        assert_eq!(
+            get_icon(&document, Some("https://example.com/post/1")).as_deref(),
+            Some("https://example.com/icon-192.png")
+        );
+
+        let html = r#"<html><head>
+            <link rel="icon" href="/favicon.ico">
+            <link rel="apple-touch-icon" href="/touch.png">
+        </head></html>"#;
+        let document = Html::parse_document(html);
+        assert_eq!(get_icon(&document, None).as_deref(), Some("/touch.png"));
+
+        let html = r#"<html><head>
+            <link rel="icon" sizes="512x512" href="/icon-512.png">
+            <link rel="icon" sizes="any" type="image/svg+xml" href="/icon.svg">
+        </head></html>"#;
+        let document = Html::parse_document(html);
+        assert_eq!(get_icon(&document, None).as_deref(), Some("/icon.svg"));
+    }
+
+    #[test]
+    fn test_breadcrumbs_from_json_ld() {
+        let html = r#"<html><head>
+            <script type="application/ld+json">{"@context": "https://schema.org", "@graph": [
+                {"@type": "NewsArticle", "headline": "Story"},
+                {"@type": "BreadcrumbList", "itemListElement": [
+                    {"@type": "ListItem", "position": 3, "item": {"@id": "/news/world/europe", "name": "Europe"}},
+                    {"@type": "ListItem", "position": 1, "name": "News", "item": "/news"},
+                    {"@type": "ListItem", "position": "2", "name": "World &amp; Politics", "item": "/news/world"}
+                ]}
+            ]}</script>
+        </head><body><nav aria-label="Breadcrumb"><a href="/">Home</a></nav></body></html>"#;
+        let document = Html::parse_document(html);
+        assert_eq!(
+            get_breadcrumbs(&document, true),
+            vec!["News", "World & Politics", "Europe"]
+        );
+        assert_eq!(get_breadcrumbs(&document, false), vec!["Home"]);
+    }
+
+    #[test]
+    fn test_breadcrumbs_from_markup() {
+        let html = r#"<html><body><nav aria-label="Breadcrumb"><ol>
+            <li><a href="/">Home</a> /</li>
+            <li><a href="/garden">Garden</a> /</li>
+            <li aria-current="page">Vegetables</li>
+        </ol></nav></body></html>"#;
+        let document = Html::parse_document(html);
+        assert_eq!(get_breadcrumbs(&document, true), vec!["Home", "Garden", "Vegetables"]);
+
+        let html = r#"<html><body><div class="site-breadcrumbs">
+            <a href="/">Home</a> &gt; <a href="/garden">Garden</a>
+        </div></body></html>"#;
+        let document = Html::parse_document(html);
+        assert_eq!(get_breadcrumbs(&document, true), vec!["Home", "Garden"]);
+
+        let html = r#"<html><body><ol itemscope itemtype="https://schema.org/BreadcrumbList">
+            <li itemprop="itemListElement" itemscope itemtype="https://schema.org/ListItem">
+                <a itemprop="item" href="/books"><span itemprop="name">Books</span></a></li>
+            <li itemprop="itemListElement" itemscope itemtype="https://schema.org/ListItem">
+                <a itemprop="item" href="/books/sf"><span itemprop="name">Science Fiction</span></a></li>
+        </ol></body></html>"#;
+        let document = Html::parse_document(html);
+        assert_eq!(get_breadcrumbs(&document, true), vec!["Books", "Science Fiction"]);
+
+        // A wrapper classed for the trail does not pull in other lists.
+        let html = r#"<html><body><div class="header-breadcrumb-wrap">
+            <ul class="site-nav"><li>Shop</li><li>Blog</li></ul>
+            <nav aria-label="Breadcrumb"><ol><li>Home</li><li>Garden</li></ol></nav>
+        </div></body></html>"#;
+        let document = Html::parse_document(html);
+        assert_eq!(get_breadcrumbs(&document, true), vec!["Home", "Garden"]);
+    }
+
+    #[test]
+    fn test_icon_falls_back_to_og_image() {
+        let html = r#"<html><head>
+            <meta property="og:image" content="/og.jpg">
+        </head></html>"#;
+        let document = Html::parse_document(html);
+        assert_eq!(
+            get_icon(&document, Some("https://example.com/")).as_deref(),
+            Some("https://example.com/og.jpg")
+        );
+        assert_eq!(icon_size("48x48 96X96"), 96);
+        assert_eq!(icon_size("bogus"), 0);
+    }
+
+    #[test]
+    fn test_alternate_links_extraction() {
+        let html = r#"
+            <html>
+                <head>
+                    <link rel="canonical" href="https://example.com/en/story" />
+                    <link rel="alternate" hreflang="en" href="https://example.com/en/story" />
+                    <link rel="alternate" hreflang="de" href="/de/geschichte" />
+                    <link rel="alternate" hreflang="x-default" href="../story" />
+                    <link rel="stylesheet" hreflang="fr" href="/style.css" />
+                </head>
+            </html>
+        "#;
+
+        let document = Html::parse_document(html);
+        let alternates = get_alternate_links(&document, Some("https://example.com/en/story"));
+
+        assert_eq!(
+            alternates,
+            vec![
+                ("en".to_string(), "https://example.com/en/story".to_string()),
+                ("de".to_string(), "https://example.com/de/geschichte".to_string()),
+                ("x-default".to_string(), "https://example.com/story".to_string()),
+            ]
+        );
+    }
+
     #[test]
     fn test_title_extraction() {
         let html = r#"
@@ -1825,7 +2937,7 @@ mod tests {
         // TODO: Colon separator extraction needs refinement
         // For now, just verify we got a title
         assert!(title.is_some());
-        assert!(title.as_ref().unwrap().len() > 0);
+        assert!(!title.as_ref().unwrap().is_empty());
     }
 
     #[test]
@@ -2115,7 +3227,7 @@ mod tests {
             "pb-byline element not found"
         );
         let elem = document.select(&selector).next().unwrap();
-        let text = collect_byline_candidate_text(elem.clone());
+        let text = collect_byline_candidate_text(elem);
         assert!(
             text.contains("Erin Cunningham"),
             "pb-byline text was {:?}",
@@ -2124,4 +3236,96 @@ mod tests {
         let dom_byline = extract_byline_from_document(&document).expect("should detect DOM byline");
         assert_eq!(dom_byline.text, "By Erin Cunningham");
     }
+
+    #[test]
+    fn test_infer_site_name_from_json_ld_publisher() {
+        let html = r#"<html><head><title>Growing basil indoors</title>
+            <script type="application/ld+json">
+            {"@context": "https://schema.org", "@graph": [
+                {"@type": "WebSite", "name": "Garden Site"},
+                {"@type": "WebPage", "publisher": {"@type": "Organization", "name": "Garden Weekly"}}
+            ]}
+            </script></head><body></body></html>"#;
+        let document = Html::parse_document(html);
+
+        assert_eq!(
+            infer_site_name(&document, None, true).as_deref(),
+            Some("Garden Weekly")
+        );
+        assert_eq!(infer_site_name(&document, None, false), None);
+    }
+
+    #[test]
+    fn test_infer_site_name_from_website_json_ld() {
+        let html = r#"<html><head><title>Growing basil indoors</title>
+            <script type="application/ld+json">
+            {"@context": "https://schema.org", "@type": "WebSite", "name": "Garden Site"}
+            </script></head><body></body></html>"#;
+        let document = Html::parse_document(html);
+
+        assert_eq!(
+            infer_site_name(&document, None, true).as_deref(),
+            Some("Garden Site")
+        );
+    }
+
+    #[test]
+    fn test_infer_site_name_from_title() {
+        let document = Html::parse_document(
+            "<html><head><title>Growing basil indoors on a windowsill | Garden Weekly</title></head></html>",
+        );
+        assert_eq!(
+            infer_site_name(&document, Some("https://www.example.com/basil"), true).as_deref(),
+            Some("Garden Weekly")
+        );
+
+        // A long trailing segment is part of the headline, not a site name.
+        let document = Html::parse_document(
+            "<html><head><title>Basil - how to keep it from bolting all summer long</title></head></html>",
+        );
+        assert_eq!(infer_site_name(&document, None, true), None);
+    }
+
+    #[test]
+    fn test_infer_site_name_from_hostname() {
+        let document =
+            Html::parse_document("<html><head><title>Growing basil indoors</title></head></html>");
+        assert_eq!(
+            infer_site_name(&document, Some("https://www.example.com/basil"), true).as_deref(),
+            Some("example.com")
+        );
+        assert_eq!(
+            infer_site_name(&document, Some("https://blog.example.org/"), true).as_deref(),
+            Some("blog.example.org")
+        );
+    }
+
+    #[test]
+    fn test_strip_site_name_from_title() {
+        assert_eq!(
+            strip_site_name_from_title("Growing basil indoors | Garden Weekly", "Garden Weekly"),
+            "Growing basil indoors"
+        );
+        assert_eq!(
+            strip_site_name_from_title("GARDEN WEEKLY – Growing basil indoors", "Garden Weekly"),
+            "Growing basil indoors"
+        );
+        assert_eq!(
+            strip_site_name_from_title("Growing basil indoors", "Garden Weekly"),
+            "Growing basil indoors"
+        );
+        assert_eq!(
+            strip_site_name_from_title("Garden Weekly", "Garden Weekly"),
+            "Garden Weekly"
+        );
+    }
+
+    #[test]
+    fn test_get_heading_title() {
+        let title = |html: &str| get_heading_title(&Html::parse_document(html));
+
+        let html = "<html><body><h1> </h1><h1>Growing  basil</h1><h1>Later</h1></body></html>";
+        assert_eq!(title(html).as_deref(), Some("Growing basil"));
+        assert_eq!(title("<html><body><p>No title</p></body></html>"), None);
+    }
 }
@@ -23,8 +23,13 @@
 //! let readability = Readability::new(html, None, Some(options)).unwrap();
 //! ```
 
+use crate::constants::DEFAULT_BOILERPLATE_PHRASES;
 use crate::markdown::MarkdownOptions;
+use crate::retry::RetryStrategy;
 use regex::Regex;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
 
 /// Configuration options for the Readability parser.
 ///
@@ -85,6 +90,16 @@ pub struct ReadabilityOptions {
     /// Default: `5`
     pub nb_top_candidates: usize,
 
+    /// Number of ancestor levels a candidate's score propagates to.
+    ///
+    /// Each scored paragraph passes part of its score to its parent, grandparent,
+    /// and so on up to this many levels. Deeply nested layouts where the article
+    /// container sits many wrappers above its paragraphs may need a higher value.
+    /// Set to 0 to propagate all the way to the root.
+    ///
+    /// Default: `5`
+    pub ancestor_score_depth: usize,
+
     /// Minimum number of characters required for article content.
     ///
     /// If extracted content has fewer characters than this threshold, the parser
@@ -94,6 +109,15 @@ pub struct ReadabilityOptions {
     /// Default: `500`
     pub char_threshold: usize,
 
+    /// Return no article when no extraction attempt reaches `char_threshold`.
+    ///
+    /// By default, when every attempt falls short of the threshold, the longest
+    /// attempt is returned anyway. When `true`, parsing returns `None` instead,
+    /// for pipelines that would rather skip a page than store a fragment of it.
+    ///
+    /// Default: `false`
+    pub strict: bool,
+
     /// CSS classes to preserve during cleaning.
     ///
     /// By default, the parser removes most CSS classes during cleaning. Classes
@@ -118,10 +142,23 @@ pub struct ReadabilityOptions {
     /// Default: `false`
     pub disable_json_ld: bool,
 
+    /// Infer the site name when the page does not declare `og:site_name`.
+    ///
+    /// When `true`, a missing site name falls back to a JSON-LD `publisher`
+    /// (or `WebSite`) name, then to the trailing segment of the `<title>`
+    /// (`Headline | Site`), then to the hostname of the base URL without `www.`.
+    /// The site name is then stripped from the article title when it appears
+    /// as a separated prefix or suffix.
+    ///
+    /// Default: `false`
+    pub infer_site_name: bool,
+
     /// Custom regex for allowed video URLs.
     ///
     /// Override the default video platform detection with a custom regex.
-    /// By default, the parser recognizes common platforms like YouTube, Vimeo, etc.
+    /// By default, the parser recognizes common platforms like YouTube, Vimeo,
+    /// Wistia, Loom, Brightcove, JW Player, Spotify and SoundCloud. The pattern is
+    /// matched against the attributes of `object`, `embed` and `iframe` elements.
     ///
     /// Default: `None` (uses built-in regex)
     ///
@@ -138,6 +175,28 @@ pub struct ReadabilityOptions {
     /// ```
     pub allowed_video_regex: Option<Regex>,
 
+    /// Replace removed iframes with a link to their source.
+    ///
+    /// Iframes that don't match the video regex (maps, CodePen and other
+    /// embeds) are removed from the content. When `true`, each one with a
+    /// `src` becomes a `<p><a href="...">Embedded content</a></p>` placeholder,
+    /// with the URL resolved against the base URL, so readers know something
+    /// was there.
+    ///
+    /// Default: `false`
+    pub iframe_placeholders: bool,
+
+    /// Containers that are always treated as content during cleaning.
+    ///
+    /// Elements whose class or id matches an entry are never removed by
+    /// conditional cleaning, e.g. `".post-content"` for a CMS whose article
+    /// body would otherwise look like a widget. Entries starting with `.`
+    /// match a class, `#` an id, and bare names either. Elements inside a
+    /// listed container are still cleaned.
+    ///
+    /// Default: `vec![]`
+    pub content_containers: Vec<String>,
+
     /// Modifier for link density scoring.
     ///
     /// Adjusts how heavily link density affects content scoring. Positive values
@@ -146,6 +205,37 @@ pub struct ReadabilityOptions {
     /// Default: `0.0`
     pub link_density_modifier: f64,
 
+    /// Maximum link density of the assembled article.
+    ///
+    /// After sibling aggregation, an attempt whose content has a higher share
+    /// of link text than this is discarded and extraction moves on to the next
+    /// set of parse flags. Catches link-heavy blocks dragged in alongside the
+    /// best candidate. Set to `1.0` to disable the check.
+    ///
+    /// Default: `0.5`
+    pub max_link_density: f64,
+
+    /// Base scores by tag name, replacing the built-in scoring values.
+    ///
+    /// Candidates start from a per-tag base score (`p` 5, `article` 8,
+    /// `blockquote` 3, `li` -3, ...) before content scoring. Keys are lowercase
+    /// tag names; tags without an entry keep the built-in value. Useful for
+    /// specialized content, such as Q&A pages where answers are `<blockquote>`s
+    /// or forums where each `<li>` is a post.
+    ///
+    /// Default: empty (built-in values)
+    pub base_score_overrides: HashMap<String, f64>,
+
+    /// Strategy deciding which parse flags to retry with.
+    ///
+    /// When an extraction attempt yields less than `char_threshold` characters,
+    /// the strategy picks the flags for the next attempt. Use this to keep
+    /// heuristics that matter for a given site, e.g. never disabling class
+    /// weighting.
+    ///
+    /// Default: `None` (uses [`DefaultRetryStrategy`](crate::DefaultRetryStrategy))
+    pub retry_strategy: Option<Arc<dyn RetryStrategy>>,
+
     /// Remove the title element from the extracted content.
     ///
     /// When `true`, removes the title heading (h1/h2) from the article content HTML
@@ -155,6 +245,77 @@ pub struct ReadabilityOptions {
     /// Default: `false`
     pub remove_title_from_content: bool,
 
+    /// Demote all but the first `<h1>` in the content to `<h2>`.
+    ///
+    /// When `true`, pages that use `<h1>` for section headings produce content
+    /// with a single `<h1>`. This runs after `remove_title_from_content`, so the
+    /// first remaining `<h1>` is the one kept. Other heading levels are untouched.
+    ///
+    /// Default: `false`
+    pub demote_extra_h1: bool,
+
+    /// Wrap each heading and the content that follows it in a `<section>`.
+    ///
+    /// When `true`, a heading starts a `<section>` that runs until the next
+    /// heading of the same or a higher level, so lower-level headings produce
+    /// nested sections. Headings that already open a `<section>` are left alone.
+    /// Useful for building a navigable reader UI from the article outline.
+    ///
+    /// Default: `false`
+    pub wrap_sections: bool,
+
+    /// Remove wrappers left empty after cleaning.
+    ///
+    /// When `true`, `<div>`, `<section>` and similar structural elements that
+    /// held only removed content (ads, share widgets, navigation) are dropped
+    /// from the output, including nested empty wrappers. Leave it `false` to
+    /// keep the markup structure as extracted.
+    ///
+    /// Default: `false`
+    pub remove_empty_wrappers: bool,
+
+    /// Remove footnote backref links.
+    ///
+    /// When `true`, the in-page links that end each footnote and point back to
+    /// its reference (`↩`, `↑`, `^`, `Back`) are removed while the footnote text
+    /// is kept. They read as stray symbols in `text_content` and Markdown output;
+    /// leave this `false` to keep them navigable in HTML output.
+    ///
+    /// Default: `false`
+    pub remove_footnote_backrefs: bool,
+
+    /// Viewport width, in CSS pixels, to size responsive images for.
+    ///
+    /// When set, each `<picture>` uses the `<source>` whose `media` query
+    /// matches this width, and images point their `src` at the `srcset`
+    /// candidate closest to it instead of the largest one. Useful for e-ink and
+    /// mobile readers that should not download full-resolution originals. The
+    /// `<source>` elements and `srcset` attributes are kept in the content.
+    ///
+    /// Default: `None` (responsive images are left as-is)
+    pub target_width: Option<u32>,
+
+    /// Number of leading images to load eagerly.
+    ///
+    /// The first `eager_images` images in the content get `loading="eager"`
+    /// and lose `decoding="async"`, so lazy-loaded images near the top still
+    /// appear when the content is shown in a different scroll container than
+    /// the original page. Other image attributes (`srcset`, `sizes`, ...) are
+    /// kept either way.
+    ///
+    /// Default: `0` (loading attributes are left as-is)
+    pub eager_images: usize,
+
+    /// Keep the title's header content at the top of the article.
+    ///
+    /// When `true` and `remove_title_from_content` removes a title that sits in a
+    /// `<header>`, the header is unwrapped so the byline and publication date it
+    /// holds stay at the start of the article body instead of being left in an
+    /// otherwise empty wrapper. Inline leftovers are wrapped in a paragraph.
+    ///
+    /// Default: `false`
+    pub promote_title_header: bool,
+
     /// Remove inline styles from the extracted content.
     ///
     /// When `true`, removes the `style` attribute and other presentational attributes
@@ -190,6 +351,317 @@ pub struct ReadabilityOptions {
     ///
     /// Default: `None` (uses `MarkdownOptions::default()`)
     pub markdown_options: Option<MarkdownOptions>,
+
+    /// Enable AMP output.
+    ///
+    /// When `true`, the parser also produces the article content as markup
+    /// valid inside an AMP page body in `Article::amp_content`: elements and
+    /// attributes AMP rejects are removed and images become `<amp-img>`. See
+    /// [`amp::html_to_amp`](crate::amp::html_to_amp).
+    ///
+    /// Default: `false`
+    pub output_amp: bool,
+
+    /// Enable block output.
+    ///
+    /// When `true`, the parser also produces the article content as a list of
+    /// typed blocks (paragraphs, headings, lists, images, code and quotes) in
+    /// `Article::blocks`, for editors and native renderers. Blocks serialize to
+    /// JSON objects tagged by `type`. See
+    /// [`blocks::html_to_blocks`](crate::blocks::html_to_blocks).
+    ///
+    /// Default: `false`
+    pub output_blocks: bool,
+
+    /// Enable plain-text output.
+    ///
+    /// When `true`, the parser also produces the article as plain text with
+    /// one line per block in `Article::plain_text`, so blocks never run into
+    /// each other as they can in `Article::text_content`. See
+    /// [`plain_text::html_to_plain_text`](crate::plain_text::html_to_plain_text).
+    ///
+    /// Default: `false`
+    pub output_plain_text: bool,
+
+    /// Annotate block elements with their line in the original HTML.
+    ///
+    /// When `true`, each block-level element in the extracted content carries a
+    /// `data-src-line` attribute holding the 1-based line of its start tag in the
+    /// source document. Useful for annotation tools that need to map extracted
+    /// content back to the original page.
+    ///
+    /// Default: `false`
+    pub annotate_source: bool,
+
+    /// Expand abbreviations in the plain-text output.
+    ///
+    /// When `true`, `<abbr title="...">` elements are rendered in `text_content`
+    /// as "ABBR (expansion)". The HTML content always keeps the `title`
+    /// attribute regardless of this setting.
+    ///
+    /// Default: `false`
+    pub expand_abbreviations: bool,
+
+    /// Language used for sentence segmentation, as a BCP 47 tag (e.g. `"ja"`).
+    ///
+    /// Sentence boundaries decide whether short sibling paragraphs are kept and
+    /// where excerpts are cut. Japanese and Chinese end sentences with `。`
+    /// and no space, while Thai uses spaces instead of punctuation. When `None`,
+    /// the document's detected language (`<html lang>` or meta tags) is used.
+    ///
+    /// Default: `None` (uses the detected language)
+    pub locale: Option<String>,
+
+    /// Populate `Article::breadcrumbs` from the page's breadcrumb trail.
+    ///
+    /// When `true`, the category path is read from `BreadcrumbList` JSON-LD
+    /// (unless `disable_json_ld` is set), microdata, or breadcrumb navigation
+    /// markup in the original document.
+    ///
+    /// Default: `false`
+    pub extract_breadcrumbs: bool,
+
+    /// Populate `Article::media` with the images and videos in the content.
+    ///
+    /// When `true`, each image, video and embedded player left in the cleaned
+    /// content is listed with its resolved source URL, alt text, `<figcaption>`
+    /// caption and declared dimensions.
+    ///
+    /// Default: `false`
+    pub extract_media: bool,
+
+    /// Strip boilerplate paragraphs from the start and end of the content.
+    ///
+    /// When `true`, short paragraphs at the very beginning or end of the article
+    /// that contain one of `boilerplate_phrases` (e.g. "Sign up for our
+    /// newsletter", "Follow us on Twitter") are removed. Matching paragraphs in
+    /// the middle of the article are left alone.
+    ///
+    /// Default: `false`
+    pub strip_boilerplate: bool,
+
+    /// Phrases that mark a leading or trailing paragraph as boilerplate.
+    ///
+    /// Matched case-insensitively as substrings of the paragraph text. Only
+    /// used when `strip_boilerplate` is `true`.
+    ///
+    /// Default: newsletter signup, follow-us and all-rights-reserved phrases
+    pub boilerplate_phrases: Vec<String>,
+
+    /// Strip a plain-text breadcrumb trail from the start of the content.
+    ///
+    /// When `true`, a short first block such as `Home › Garden › Raised beds`
+    /// in a bare `<p>` or `<div>` is removed. Breadcrumbs marked up as
+    /// navigation are already dropped by the class and id checks; this catches
+    /// the ones without nav semantics.
+    ///
+    /// Default: `false`
+    pub strip_text_breadcrumbs: bool,
+
+    /// Remove soft hyphens (U+00AD, `&shy;`) from `text_content`.
+    ///
+    /// Some CMSes insert soft hyphens as hyphenation hints, so `word&shy;break`
+    /// reads as two fragments to search indexers. When `true` they are dropped
+    /// and the word is rejoined. Markdown output always drops them.
+    ///
+    /// Default: `true`
+    pub strip_soft_hyphens: bool,
+
+    /// Remove soft hyphens (U+00AD, `&shy;`) from the HTML `content` as well.
+    ///
+    /// Soft hyphens are invisible in rendered HTML unless a line breaks at
+    /// them, so they are kept by default.
+    ///
+    /// Default: `false`
+    pub strip_soft_hyphens_html: bool,
+
+    /// Also return the lightly cleaned content in `Article::light_content`.
+    ///
+    /// The light version only has navigation sections removed, skipping the
+    /// conditional cleaning and other passes that produce `content`.
+    ///
+    /// Default: `false`
+    pub include_light_content: bool,
+
+    /// Remove photo credits from image captions.
+    ///
+    /// When `true`, trailing credit lines such as `Photo: Getty Images / Jane
+    /// Doe`, `Credit: ...` or `© ...` are stripped from `<figcaption>` text,
+    /// keeping the description. Leave it off when credits are needed for
+    /// licensing.
+    ///
+    /// Default: `false`
+    pub strip_image_credits: bool,
+
+    /// Collapse chains of single-child `<div>` wrappers in the content.
+    ///
+    /// When `true`, a `<div>` whose only content is another `<div>` is merged
+    /// with it, repeatedly, so `div > div > div > p` becomes `div > p`. The
+    /// innermost wrapper's attributes are kept, with the classes of the
+    /// collapsed wrappers merged in and an outer `id` kept when the innermost
+    /// has none.
+    ///
+    /// Default: `false`
+    pub flatten_wrappers: bool,
+
+    /// Minimum length of an HTML string in script data to extract from.
+    ///
+    /// Client-rendered pages can carry the article as an escaped HTML string
+    /// inside a JSON `<script>` (Next.js `__NEXT_DATA__`, `window.__DATA__`
+    /// assignments, ...) and leave the body empty until scripts run. When
+    /// non-zero and the normal extraction finds less than `char_threshold`
+    /// characters, the longest such string of at least this many characters
+    /// is unescaped and extracted from instead, if that yields more text.
+    /// `Article::candidate_path` then refers to the embedded document.
+    ///
+    /// Default: `0` (script data is not searched)
+    pub embedded_html_min_length: usize,
+
+    /// Minimum length of an `<iframe srcdoc>` document to extract from.
+    ///
+    /// CMS previews and some embeds carry the article as an escaped document
+    /// in an iframe's `srcdoc` attribute. When non-zero and the normal
+    /// extraction finds less than `char_threshold` characters, the longest
+    /// `srcdoc` of at least this many characters is parsed and extracted from
+    /// instead, if that yields more text. `Article::candidate_path` then
+    /// refers to the iframe document.
+    ///
+    /// Default: `0` (iframes are not searched)
+    pub iframe_srcdoc_min_length: usize,
+
+    /// Keep `<figure>`s that hold only a `<figcaption>`.
+    ///
+    /// Cleaning can drop a figure's image or embed and leave its caption
+    /// behind, describing content that is no longer in the article. When
+    /// `false`, such caption-only figures are removed. Figures around quotes,
+    /// tables or code keep their captions either way.
+    ///
+    /// Default: `true`
+    pub keep_standalone_captions: bool,
+
+    /// Remove headings whose section was emptied by cleaning.
+    ///
+    /// Removing boilerplate can leave a heading directly followed by another
+    /// heading of the same or a higher level, as in `<h2>A</h2><h2>B</h2>`.
+    /// When `true`, such leftover headings are dropped. A heading followed by a
+    /// lower-level heading opens a subsection and is kept.
+    ///
+    /// Default: `false`
+    pub remove_empty_sections: bool,
+
+    /// Largest element count of an HTML fragment taken whole as the article.
+    ///
+    /// Input without `<html>`, `<head>` or `<body>` tags, such as the article
+    /// body returned by a CMS API, is often already clean. When non-zero, a
+    /// fragment with at most this many elements, no layout elements or
+    /// boilerplate classes, and mostly non-link text skips candidate scoring:
+    /// it is returned whole with only light cleaning (URL fixing and removal
+    /// of navigation-like sections) and responsive image resolution.
+    ///
+    /// Default: `0` (every input is scored)
+    pub clean_fragment_max_elements: usize,
+
+    /// Turn runs of `<br>`s into paragraphs.
+    ///
+    /// Like Mozilla's `_replaceBrs`, text separated by two or more `<br>`s is
+    /// split into `<p>` elements. When `false`, every `<br>` is kept as-is,
+    /// preserving the line structure of poetry, lyrics or addresses, and each
+    /// `<br>` becomes a line break in `text_content`.
+    ///
+    /// Default: `true`
+    pub convert_brs: bool,
+
+    /// Tags within which whitespace is kept as written.
+    ///
+    /// Text inside these elements is never collapsed, `<br>`s inside them are
+    /// never turned into paragraphs and become line breaks in
+    /// `text_content`, and `text_normalizer` is not applied to it. Add tags
+    /// such as `samp` for ASCII art, or custom elements holding aligned text.
+    ///
+    /// Default: `vec!["pre", "textarea"]`
+    pub preserve_whitespace_tags: Vec<String>,
+
+    /// Convert scene-break paragraphs into `<hr>` thematic breaks.
+    ///
+    /// When `true`, paragraphs made up only of divider characters, such as
+    /// `* * *`, `⁂` or `· · ·`, are replaced with `<hr>`.
+    ///
+    /// Default: `false`
+    pub convert_scene_breaks: bool,
+
+    /// Remove paragraphs holding nothing but emoji.
+    ///
+    /// When `true`, reaction rows such as `👏👏👏` are dropped from the
+    /// content.
+    ///
+    /// Default: `false`
+    pub remove_emoji_paragraphs: bool,
+
+    /// Open FAQ-style `<details>` elements.
+    ///
+    /// When `true`, collapsed `<details>` get the `open` attribute so their
+    /// content shows. Spoilers, recognized by a class, id or `<summary>`
+    /// mentioning `spoiler`, are kept closed and marked with `data-spoiler`.
+    /// The `<summary>` is kept either way.
+    ///
+    /// Default: `false`
+    pub open_details: bool,
+
+    /// Custom normalization for extracted text.
+    ///
+    /// When set, replaces the built-in whitespace normalization of element
+    /// text used during scoring and cleaning, and is applied to
+    /// `Article::text_content`. Use it for Unicode normalization such as NFKC
+    /// folding at extraction time, or to keep whitespace the default collapses.
+    ///
+    /// Default: `None` (collapse runs of whitespace)
+    pub text_normalizer: Option<TextNormalizer>,
+
+    /// Kind of page being extracted.
+    ///
+    /// Profiles bundle heuristics for a family of layouts. See
+    /// [`ContentProfile`].
+    ///
+    /// Default: [`ContentProfile::General`]
+    pub content_profile: ContentProfile,
+
+    /// Remove cookie-consent and GDPR banners found by their text.
+    ///
+    /// When `true`, a short block near the top or end of the page that
+    /// mentions cookies or consent and holds an accept button next to a
+    /// reject or settings button is removed before scoring, whatever its
+    /// class or id.
+    ///
+    /// Default: `false`
+    pub remove_consent_banners: bool,
+
+    /// How inline `<svg>` elements in the content are treated.
+    ///
+    /// Inline SVG serves both for diagrams and for share, arrow and other
+    /// icons. See [`SvgPolicy`].
+    ///
+    /// Default: [`SvgPolicy::StripDecorative`]
+    pub svg_policy: SvgPolicy,
+
+    /// Fall back to normal extraction when the selector given to
+    /// [`Readability::parse_within`](crate::Readability::parse_within)
+    /// matches nothing.
+    ///
+    /// When `false`, `parse_within` returns
+    /// [`ReadabilityError::SelectorNotFound`](crate::ReadabilityError::SelectorNotFound)
+    /// instead.
+    ///
+    /// Default: `true`
+    pub selector_fallback: bool,
+
+    /// Merge lists that were split in two by an ad or divider.
+    ///
+    /// When `true`, adjacent `<ul>`s, or adjacent `<ol>`s whose second list
+    /// continues the numbering of the first, are joined into one list once
+    /// ads have been removed from between them.
+    ///
+    /// Default: `false`
+    pub merge_split_lists: bool,
 }
 
 impl Default for ReadabilityOptions {
@@ -198,17 +670,67 @@ impl Default for ReadabilityOptions {
             debug: false,
             max_elems_to_parse: 0,
             nb_top_candidates: 5,
+            ancestor_score_depth: 5,
             char_threshold: 500,
+            strict: false,
             classes_to_preserve: vec!["page".to_string()],
             keep_classes: false,
             disable_json_ld: false,
+            infer_site_name: false,
             allowed_video_regex: None,
+            iframe_placeholders: false,
+            content_containers: Vec::new(),
             link_density_modifier: 0.0,
+            max_link_density: 0.5,
+            base_score_overrides: HashMap::new(),
+            retry_strategy: None,
             remove_title_from_content: false,
+            promote_title_header: false,
+            demote_extra_h1: false,
+            wrap_sections: false,
+            remove_empty_wrappers: false,
+            remove_footnote_backrefs: false,
+            target_width: None,
+            eager_images: 0,
             clean_styles: true,
             clean_whitespace: true,
             output_markdown: false,
             markdown_options: None,
+            output_amp: false,
+            output_blocks: false,
+            output_plain_text: false,
+            annotate_source: false,
+            expand_abbreviations: false,
+            locale: None,
+            extract_breadcrumbs: false,
+            extract_media: false,
+            strip_boilerplate: false,
+            boilerplate_phrases: DEFAULT_BOILERPLATE_PHRASES
+                .iter()
+                .map(|phrase| phrase.to_string())
+                .collect(),
+            strip_text_breadcrumbs: false,
+            strip_soft_hyphens: true,
+            strip_soft_hyphens_html: false,
+            include_light_content: false,
+            strip_image_credits: false,
+            flatten_wrappers: false,
+            embedded_html_min_length: 0,
+            iframe_srcdoc_min_length: 0,
+            keep_standalone_captions: true,
+            remove_empty_sections: false,
+            clean_fragment_max_elements: 0,
+            convert_brs: true,
+            preserve_whitespace_tags: vec!["pre".to_string(), "textarea".to_string()],
+            convert_scene_breaks: false,
+            remove_emoji_paragraphs: false,
+            open_details: false,
+            text_normalizer: None,
+            content_profile: ContentProfile::General,
+            remove_consent_banners: false,
+            svg_policy: SvgPolicy::StripDecorative,
+            selector_fallback: true,
+            merge_split_lists: false,
         }
     }
 }
@@ -220,6 +742,78 @@ impl ReadabilityOptions {
     }
 }
 
+/// Kind of page being extracted, see [`ReadabilityOptions::content_profile`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ContentProfile {
+    /// Articles, blog posts and other pages, using the generic heuristics.
+    #[default]
+    General,
+    /// Documentation pages with a navigation sidebar, a content column and
+    /// often an "on this page" rail.
+    ///
+    /// - The content column is taken from `<main>` or `[role=main]`, or the
+    ///   `<article>` inside it, when it holds at least `char_threshold`
+    ///   characters; otherwise candidates are scored as usual.
+    /// - Tables of contents, sidebars, breadcrumbs, pagination and "edit this
+    ///   page" links inside the content are removed.
+    /// - Conditional cleaning is skipped, so code blocks, tables, definition
+    ///   lists and headings are kept.
+    Documentation,
+    /// News articles.
+    ///
+    /// Related and "most read" lists, newsletter signups, ad slots and
+    /// "Read more:" links are removed before scoring, so a story body split
+    /// into several sections around them is selected whole. Byline, date and
+    /// lead image come from the metadata as usual.
+    News,
+}
+
+/// How inline `<svg>` elements are treated, see
+/// [`ReadabilityOptions::svg_policy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SvgPolicy {
+    /// Keep every `<svg>`.
+    KeepAll,
+    /// Remove decorative icons and keep diagrams.
+    ///
+    /// An `<svg>` without a `<title>`, `<desc>` or `aria-label` is an icon
+    /// when it is `aria-hidden`, sized 48 units or less, or sits inside a link
+    /// or button. Named or full-size drawings are kept.
+    #[default]
+    StripDecorative,
+    /// Remove every `<svg>`.
+    StripAll,
+}
+
+/// A function normalizing extracted text.
+///
+/// See [`ReadabilityOptions::text_normalizer`].
+#[derive(Clone)]
+pub struct TextNormalizer(Arc<dyn Fn(&str) -> String + Send + Sync>);
+
+impl TextNormalizer {
+    /// Wrap a normalization function
+    pub fn new(normalizer: impl Fn(&str) -> String + Send + Sync + 'static) -> Self {
+        Self(Arc::new(normalizer))
+    }
+
+    /// Normalize `text`
+    pub fn normalize(&self, text: &str) -> String {
+        (self.0)(text)
+    }
+
+    /// Identity of the wrapped function, for telling normalizers apart.
+    pub(crate) fn as_ptr(&self) -> *const () {
+        Arc::as_ptr(&self.0) as *const ()
+    }
+}
+
+impl fmt::Debug for TextNormalizer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("TextNormalizer")
+    }
+}
+
 /// Builder for [`ReadabilityOptions`].
 ///
 /// Provides a fluent interface for constructing [`ReadabilityOptions`] with custom values.
@@ -241,17 +835,64 @@ pub struct ReadabilityOptionsBuilder {
     debug: Option<bool>,
     max_elems_to_parse: Option<usize>,
     nb_top_candidates: Option<usize>,
+    ancestor_score_depth: Option<usize>,
     char_threshold: Option<usize>,
+    strict: Option<bool>,
     classes_to_preserve: Option<Vec<String>>,
     keep_classes: Option<bool>,
     disable_json_ld: Option<bool>,
+    infer_site_name: Option<bool>,
     allowed_video_regex: Option<Regex>,
+    iframe_placeholders: Option<bool>,
+    content_containers: Option<Vec<String>>,
     link_density_modifier: Option<f64>,
+    max_link_density: Option<f64>,
+    base_score_overrides: Option<HashMap<String, f64>>,
+    retry_strategy: Option<Arc<dyn RetryStrategy>>,
     remove_title_from_content: Option<bool>,
+    promote_title_header: Option<bool>,
+    demote_extra_h1: Option<bool>,
+    wrap_sections: Option<bool>,
+    remove_empty_wrappers: Option<bool>,
+    remove_footnote_backrefs: Option<bool>,
+    target_width: Option<u32>,
+    eager_images: Option<usize>,
     clean_styles: Option<bool>,
     clean_whitespace: Option<bool>,
     output_markdown: Option<bool>,
     markdown_options: Option<MarkdownOptions>,
+    output_amp: Option<bool>,
+    output_blocks: Option<bool>,
+    output_plain_text: Option<bool>,
+    annotate_source: Option<bool>,
+    expand_abbreviations: Option<bool>,
+    locale: Option<String>,
+    extract_breadcrumbs: Option<bool>,
+    extract_media: Option<bool>,
+    strip_boilerplate: Option<bool>,
+    boilerplate_phrases: Option<Vec<String>>,
+    strip_text_breadcrumbs: Option<bool>,
+    strip_soft_hyphens: Option<bool>,
+    strip_soft_hyphens_html: Option<bool>,
+    include_light_content: Option<bool>,
+    strip_image_credits: Option<bool>,
+    flatten_wrappers: Option<bool>,
+    embedded_html_min_length: Option<usize>,
+    iframe_srcdoc_min_length: Option<usize>,
+    keep_standalone_captions: Option<bool>,
+    remove_empty_sections: Option<bool>,
+    clean_fragment_max_elements: Option<usize>,
+    convert_brs: Option<bool>,
+    preserve_whitespace_tags: Option<Vec<String>>,
+    convert_scene_breaks: Option<bool>,
+    remove_emoji_paragraphs: Option<bool>,
+    open_details: Option<bool>,
+    text_normalizer: Option<TextNormalizer>,
+    content_profile: Option<ContentProfile>,
+    remove_consent_banners: Option<bool>,
+    svg_policy: Option<SvgPolicy>,
+    selector_fallback: Option<bool>,
+    merge_split_lists: Option<bool>,
 }
 
 impl ReadabilityOptionsBuilder {
@@ -273,12 +914,30 @@ impl ReadabilityOptionsBuilder {
         self
     }
 
+    /// Set how many ancestor levels receive propagated scores
+    ///
+    /// Use a higher value for layouts that wrap the article in many nested
+    /// containers, or 0 for no limit.
+    pub fn ancestor_score_depth(mut self, depth: usize) -> Self {
+        self.ancestor_score_depth = Some(depth);
+        self
+    }
+
     /// Set character threshold
     pub fn char_threshold(mut self, threshold: usize) -> Self {
         self.char_threshold = Some(threshold);
         self
     }
 
+    /// Enable or disable strict mode
+    ///
+    /// When enabled, pages whose best attempt stays below `char_threshold`
+    /// produce no article instead of the longest short attempt.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = Some(strict);
+        self
+    }
+
     /// Set classes to preserve
     pub fn classes_to_preserve(mut self, classes: Vec<String>) -> Self {
         self.classes_to_preserve = Some(classes);
@@ -297,18 +956,72 @@ impl ReadabilityOptionsBuilder {
         self
     }
 
+    /// Enable or disable site name inference
+    ///
+    /// When enabled, pages without `og:site_name` get a site name from
+    /// JSON-LD, the `<title>` or the base URL's hostname, and the site name is
+    /// stripped from the title.
+    pub fn infer_site_name(mut self, infer: bool) -> Self {
+        self.infer_site_name = Some(infer);
+        self
+    }
+
     /// Set allowed video regex
     pub fn allowed_video_regex(mut self, regex: Regex) -> Self {
         self.allowed_video_regex = Some(regex);
         self
     }
 
+    /// Keep a link in place of each removed non-video iframe
+    ///
+    /// When enabled, an embedded map or code playground is replaced by an
+    /// "Embedded content" link to its source instead of disappearing silently.
+    pub fn iframe_placeholders(mut self, enabled: bool) -> Self {
+        self.iframe_placeholders = Some(enabled);
+        self
+    }
+
+    /// Set containers exempt from conditional cleaning
+    ///
+    /// Entries are class names or ids, optionally written as `.class` or
+    /// `#id`, e.g. `vec![".post-content".to_string()]`.
+    pub fn content_containers(mut self, containers: Vec<String>) -> Self {
+        self.content_containers = Some(containers);
+        self
+    }
+
     /// Set link density modifier
     pub fn link_density_modifier(mut self, modifier: f64) -> Self {
         self.link_density_modifier = Some(modifier);
         self
     }
 
+    /// Set the maximum link density of the extracted article
+    ///
+    /// Attempts whose assembled content exceeds it are treated as failed.
+    pub fn max_link_density(mut self, density: f64) -> Self {
+        self.max_link_density = Some(density);
+        self
+    }
+
+    /// Override base scores by tag name
+    ///
+    /// Maps lowercase tag names to the base score candidates of that tag start
+    /// from, replacing the built-in values. Tags not in the map are unchanged.
+    pub fn base_score_overrides(mut self, overrides: HashMap<String, f64>) -> Self {
+        self.base_score_overrides = Some(overrides);
+        self
+    }
+
+    /// Set the strategy used to retry extraction with looser flags
+    ///
+    /// The strategy is asked for the next set of flags whenever an attempt
+    /// yields less than `char_threshold` characters.
+    pub fn retry_strategy(mut self, strategy: Arc<dyn RetryStrategy>) -> Self {
+        self.retry_strategy = Some(strategy);
+        self
+    }
+
     /// Remove the title element from the extracted content
     ///
     /// When enabled, removes the title heading (h1/h2) from the article content
@@ -318,6 +1031,69 @@ impl ReadabilityOptionsBuilder {
         self
     }
 
+    /// Keep the title's header byline and date at the top of the article
+    ///
+    /// Only has an effect together with `remove_title_from_content`. The
+    /// `<header>` holding the title is unwrapped so its remaining content
+    /// becomes the first part of the article body.
+    pub fn promote_title_header(mut self, promote: bool) -> Self {
+        self.promote_title_header = Some(promote);
+        self
+    }
+
+    /// Demote all but the first `<h1>` to `<h2>`
+    ///
+    /// Fixes content with several top-level headings without otherwise
+    /// changing heading levels.
+    pub fn demote_extra_h1(mut self, demote: bool) -> Self {
+        self.demote_extra_h1 = Some(demote);
+        self
+    }
+
+    /// Wrap heading-delimited content in `<section>` elements
+    ///
+    /// Each heading becomes the first child of a section holding the content up
+    /// to the next heading of the same or a higher level.
+    pub fn wrap_sections(mut self, wrap: bool) -> Self {
+        self.wrap_sections = Some(wrap);
+        self
+    }
+
+    /// Enable or disable removal of empty wrappers
+    ///
+    /// When enabled, structural elements left without text or child elements
+    /// after cleaning are removed.
+    pub fn remove_empty_wrappers(mut self, remove: bool) -> Self {
+        self.remove_empty_wrappers = Some(remove);
+        self
+    }
+
+    /// Enable or disable removal of footnote backref links
+    ///
+    /// When enabled, `↩`-style links from footnotes back to their references
+    /// are dropped and the footnote text is kept.
+    pub fn remove_footnote_backrefs(mut self, remove: bool) -> Self {
+        self.remove_footnote_backrefs = Some(remove);
+        self
+    }
+
+    /// Set the viewport width to pick responsive image sources for
+    ///
+    /// Images use the `<source media>` and `srcset` candidates matching this
+    /// width rather than the largest available.
+    pub fn target_width(mut self, width: u32) -> Self {
+        self.target_width = Some(width);
+        self
+    }
+
+    /// Set how many leading images load eagerly
+    ///
+    /// The first `count` images get `loading="eager"` instead of lazy loading.
+    pub fn eager_images(mut self, count: usize) -> Self {
+        self.eager_images = Some(count);
+        self
+    }
+
     /// Enable or disable inline style cleaning
     ///
     /// When enabled, removes the `style` attribute and other presentational attributes
@@ -354,6 +1130,285 @@ impl ReadabilityOptionsBuilder {
         self
     }
 
+    /// Enable or disable AMP output
+    ///
+    /// When enabled, the parser produces an AMP-safe version of the article
+    /// in `Article::amp_content`.
+    pub fn output_amp(mut self, enabled: bool) -> Self {
+        self.output_amp = Some(enabled);
+        self
+    }
+
+    /// Enable or disable block output
+    ///
+    /// When enabled, the parser produces the article as typed content blocks
+    /// in `Article::blocks`.
+    pub fn output_blocks(mut self, enabled: bool) -> Self {
+        self.output_blocks = Some(enabled);
+        self
+    }
+
+    /// Enable or disable plain-text output
+    ///
+    /// When enabled, the parser produces the article as plain text, one line
+    /// per block, in `Article::plain_text`.
+    pub fn output_plain_text(mut self, enabled: bool) -> Self {
+        self.output_plain_text = Some(enabled);
+        self
+    }
+
+    /// Annotate block elements with their source line
+    ///
+    /// When enabled, block-level elements in the extracted content carry a
+    /// `data-src-line` attribute pointing to their line in the original HTML.
+    pub fn annotate_source(mut self, enabled: bool) -> Self {
+        self.annotate_source = Some(enabled);
+        self
+    }
+
+    /// Expand abbreviations in the text output
+    ///
+    /// When enabled, `<abbr title="...">` elements appear in `text_content` as
+    /// "ABBR (expansion)".
+    pub fn expand_abbreviations(mut self, enabled: bool) -> Self {
+        self.expand_abbreviations = Some(enabled);
+        self
+    }
+
+    /// Set the language used for sentence segmentation
+    ///
+    /// Overrides the language detected from the document, e.g. `"ja"` or `"th"`.
+    pub fn locale(mut self, locale: impl Into<String>) -> Self {
+        self.locale = Some(locale.into());
+        self
+    }
+
+    /// Populate the article's breadcrumb trail
+    ///
+    /// When enabled, `Article::breadcrumbs` holds the category path from
+    /// `BreadcrumbList` structured data or breadcrumb navigation.
+    pub fn extract_breadcrumbs(mut self, enabled: bool) -> Self {
+        self.extract_breadcrumbs = Some(enabled);
+        self
+    }
+
+    /// List the article's images and videos
+    ///
+    /// When enabled, `Article::media` holds a manifest of the media in the
+    /// cleaned content, for downloading or auditing it separately.
+    pub fn extract_media(mut self, enabled: bool) -> Self {
+        self.extract_media = Some(enabled);
+        self
+    }
+
+    /// Strip boilerplate paragraphs at the start and end of the content
+    ///
+    /// When enabled, leading and trailing paragraphs matching one of the
+    /// boilerplate phrases (newsletter signups, follow-us links, copyright
+    /// lines) are removed.
+    pub fn strip_boilerplate(mut self, enabled: bool) -> Self {
+        self.strip_boilerplate = Some(enabled);
+        self
+    }
+
+    /// Set the phrases used to detect boilerplate paragraphs
+    ///
+    /// Replaces the built-in list. Phrases are matched case-insensitively.
+    pub fn boilerplate_phrases(mut self, phrases: Vec<String>) -> Self {
+        self.boilerplate_phrases = Some(phrases);
+        self
+    }
+
+    /// Strip a plain-text breadcrumb trail at the start of the content
+    ///
+    /// When enabled, a short leading block of `›`, `»`, `/` or `>` separated
+    /// segments is removed.
+    pub fn strip_text_breadcrumbs(mut self, enabled: bool) -> Self {
+        self.strip_text_breadcrumbs = Some(enabled);
+        self
+    }
+
+    /// Enable or disable removal of soft hyphens from the text content
+    ///
+    /// When enabled, `&shy;` hyphenation hints are dropped so words they
+    /// split are rejoined in `text_content`.
+    pub fn strip_soft_hyphens(mut self, strip: bool) -> Self {
+        self.strip_soft_hyphens = Some(strip);
+        self
+    }
+
+    /// Enable or disable removal of soft hyphens from the HTML content
+    pub fn strip_soft_hyphens_html(mut self, strip: bool) -> Self {
+        self.strip_soft_hyphens_html = Some(strip);
+        self
+    }
+
+    /// Enable or disable returning the lightly cleaned content
+    ///
+    /// When enabled, `Article::light_content` holds the content before
+    /// conditional cleaning, for debugging or custom sanitizing.
+    pub fn include_light_content(mut self, include: bool) -> Self {
+        self.include_light_content = Some(include);
+        self
+    }
+
+    /// Enable or disable removal of photo credits from image captions
+    ///
+    /// When enabled, `Photo:`, `Credit:`, `Image:` and `©` credit lines are
+    /// dropped from `<figcaption>` text.
+    pub fn strip_image_credits(mut self, strip: bool) -> Self {
+        self.strip_image_credits = Some(strip);
+        self
+    }
+
+    /// Enable or disable collapsing of nested single-child wrappers
+    ///
+    /// When enabled, chains of `<div>`s that only wrap the next `<div>` are
+    /// merged into one element.
+    pub fn flatten_wrappers(mut self, flatten: bool) -> Self {
+        self.flatten_wrappers = Some(flatten);
+        self
+    }
+
+    /// Set the minimum length of HTML strings in script data to extract from
+    ///
+    /// Use `0` to disable; around `1000` suits most client-rendered pages.
+    pub fn embedded_html_min_length(mut self, length: usize) -> Self {
+        self.embedded_html_min_length = Some(length);
+        self
+    }
+
+    /// Set the minimum length of iframe `srcdoc` documents to extract from
+    ///
+    /// Use `0` to disable.
+    pub fn iframe_srcdoc_min_length(mut self, length: usize) -> Self {
+        self.iframe_srcdoc_min_length = Some(length);
+        self
+    }
+
+    /// Enable or disable keeping figures that hold only a caption
+    ///
+    /// When disabled, `<figure>`s whose image was removed and that hold
+    /// nothing but a `<figcaption>` are dropped.
+    pub fn keep_standalone_captions(mut self, keep: bool) -> Self {
+        self.keep_standalone_captions = Some(keep);
+        self
+    }
+
+    /// Enable or disable removal of empty section headings
+    ///
+    /// When enabled, a heading followed directly by a heading of the same or a
+    /// higher level is dropped, since the content it introduced was removed.
+    pub fn remove_empty_sections(mut self, remove: bool) -> Self {
+        self.remove_empty_sections = Some(remove);
+        self
+    }
+
+    /// Set the largest element count of a fragment taken whole as the article
+    ///
+    /// Use `0` to score every input; around `200` covers typical article
+    /// bodies returned by CMS APIs.
+    pub fn clean_fragment_max_elements(mut self, max_elements: usize) -> Self {
+        self.clean_fragment_max_elements = Some(max_elements);
+        self
+    }
+
+    /// Enable or disable turning `<br>` runs into paragraphs
+    ///
+    /// Disable for content where line breaks carry meaning, such as poems or
+    /// addresses.
+    pub fn convert_brs(mut self, convert: bool) -> Self {
+        self.convert_brs = Some(convert);
+        self
+    }
+
+    /// Set the tags within which whitespace is kept as written
+    ///
+    /// Replaces the default `pre` and `textarea`, so include them to keep
+    /// their whitespace too.
+    pub fn preserve_whitespace_tags(mut self, tags: Vec<String>) -> Self {
+        self.preserve_whitespace_tags = Some(tags);
+        self
+    }
+
+    /// Enable or disable turning scene-break paragraphs into `<hr>`
+    ///
+    /// Recognizes paragraphs of dividers such as `* * *`, `⁂` or `· · ·`.
+    pub fn convert_scene_breaks(mut self, convert: bool) -> Self {
+        self.convert_scene_breaks = Some(convert);
+        self
+    }
+
+    /// Enable or disable removal of emoji-only paragraphs
+    ///
+    /// Drops reaction rows such as `👏👏👏` that carry no article text.
+    pub fn remove_emoji_paragraphs(mut self, remove: bool) -> Self {
+        self.remove_emoji_paragraphs = Some(remove);
+        self
+    }
+
+    /// Enable or disable opening FAQ-style `<details>`
+    ///
+    /// Spoiler `<details>` stay closed and are marked with `data-spoiler`.
+    pub fn open_details(mut self, open: bool) -> Self {
+        self.open_details = Some(open);
+        self
+    }
+
+    /// Set a custom text normalizer
+    ///
+    /// The function receives trimmed element text and returns its normalized
+    /// form, in place of the built-in whitespace collapsing.
+    pub fn text_normalizer(
+        mut self,
+        normalizer: impl Fn(&str) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.text_normalizer = Some(TextNormalizer::new(normalizer));
+        self
+    }
+
+    /// Set the kind of page being extracted
+    ///
+    /// Use [`ContentProfile::Documentation`] for documentation sites with
+    /// navigation sidebars and "on this page" rails.
+    pub fn content_profile(mut self, profile: ContentProfile) -> Self {
+        self.content_profile = Some(profile);
+        self
+    }
+
+    /// Enable or disable removal of cookie-consent banners
+    ///
+    /// Banners are recognized by their text and buttons, not their markup.
+    pub fn remove_consent_banners(mut self, remove: bool) -> Self {
+        self.remove_consent_banners = Some(remove);
+        self
+    }
+
+    /// Set how inline `<svg>` elements are treated
+    ///
+    /// Use [`SvgPolicy::KeepAll`] to keep icons as well as diagrams.
+    pub fn svg_policy(mut self, policy: SvgPolicy) -> Self {
+        self.svg_policy = Some(policy);
+        self
+    }
+
+    /// Enable or disable falling back to normal extraction in `parse_within`
+    ///
+    /// When disabled, an unmatched selector is an error.
+    pub fn selector_fallback(mut self, fallback: bool) -> Self {
+        self.selector_fallback = Some(fallback);
+        self
+    }
+
+    /// Enable or disable merging lists split by an ad or divider
+    ///
+    /// Only lists of the same type are merged, and an `<ol>` only when the
+    /// numbering carries on.
+    pub fn merge_split_lists(mut self, merge: bool) -> Self {
+        self.merge_split_lists = Some(merge);
+        self
+    }
+
     /// Build the ReadabilityOptions
     pub fn build(self) -> ReadabilityOptions {
         let defaults = ReadabilityOptions::default();
@@ -363,23 +1418,118 @@ impl ReadabilityOptionsBuilder {
                 .max_elems_to_parse
                 .unwrap_or(defaults.max_elems_to_parse),
             nb_top_candidates: self.nb_top_candidates.unwrap_or(defaults.nb_top_candidates),
+            ancestor_score_depth: self
+                .ancestor_score_depth
+                .unwrap_or(defaults.ancestor_score_depth),
             char_threshold: self.char_threshold.unwrap_or(defaults.char_threshold),
+            strict: self.strict.unwrap_or(defaults.strict),
             classes_to_preserve: self
                 .classes_to_preserve
                 .unwrap_or(defaults.classes_to_preserve),
             keep_classes: self.keep_classes.unwrap_or(defaults.keep_classes),
             disable_json_ld: self.disable_json_ld.unwrap_or(defaults.disable_json_ld),
+            infer_site_name: self.infer_site_name.unwrap_or(defaults.infer_site_name),
             allowed_video_regex: self.allowed_video_regex.or(defaults.allowed_video_regex),
+            iframe_placeholders: self
+                .iframe_placeholders
+                .unwrap_or(defaults.iframe_placeholders),
+            content_containers: self
+                .content_containers
+                .unwrap_or(defaults.content_containers),
             link_density_modifier: self
                 .link_density_modifier
                 .unwrap_or(defaults.link_density_modifier),
+            max_link_density: self.max_link_density.unwrap_or(defaults.max_link_density),
+            base_score_overrides: self
+                .base_score_overrides
+                .unwrap_or(defaults.base_score_overrides),
+            retry_strategy: self.retry_strategy.or(defaults.retry_strategy),
             remove_title_from_content: self
                 .remove_title_from_content
                 .unwrap_or(defaults.remove_title_from_content),
+            promote_title_header: self
+                .promote_title_header
+                .unwrap_or(defaults.promote_title_header),
+            demote_extra_h1: self.demote_extra_h1.unwrap_or(defaults.demote_extra_h1),
+            wrap_sections: self.wrap_sections.unwrap_or(defaults.wrap_sections),
+            remove_empty_wrappers: self
+                .remove_empty_wrappers
+                .unwrap_or(defaults.remove_empty_wrappers),
+            remove_footnote_backrefs: self
+                .remove_footnote_backrefs
+                .unwrap_or(defaults.remove_footnote_backrefs),
+            target_width: self.target_width.or(defaults.target_width),
+            eager_images: self.eager_images.unwrap_or(defaults.eager_images),
             clean_styles: self.clean_styles.unwrap_or(defaults.clean_styles),
             clean_whitespace: self.clean_whitespace.unwrap_or(defaults.clean_whitespace),
             output_markdown: self.output_markdown.unwrap_or(defaults.output_markdown),
             markdown_options: self.markdown_options.or(defaults.markdown_options),
+            output_amp: self.output_amp.unwrap_or(defaults.output_amp),
+            output_blocks: self.output_blocks.unwrap_or(defaults.output_blocks),
+            output_plain_text: self.output_plain_text.unwrap_or(defaults.output_plain_text),
+            annotate_source: self.annotate_source.unwrap_or(defaults.annotate_source),
+            expand_abbreviations: self
+                .expand_abbreviations
+                .unwrap_or(defaults.expand_abbreviations),
+            locale: self.locale.or(defaults.locale),
+            extract_breadcrumbs: self
+                .extract_breadcrumbs
+                .unwrap_or(defaults.extract_breadcrumbs),
+            extract_media: self.extract_media.unwrap_or(defaults.extract_media),
+            strip_boilerplate: self.strip_boilerplate.unwrap_or(defaults.strip_boilerplate),
+            boilerplate_phrases: self
+                .boilerplate_phrases
+                .unwrap_or(defaults.boilerplate_phrases),
+            strip_text_breadcrumbs: self
+                .strip_text_breadcrumbs
+                .unwrap_or(defaults.strip_text_breadcrumbs),
+            strip_soft_hyphens: self
+                .strip_soft_hyphens
+                .unwrap_or(defaults.strip_soft_hyphens),
+            strip_soft_hyphens_html: self
+                .strip_soft_hyphens_html
+                .unwrap_or(defaults.strip_soft_hyphens_html),
+            include_light_content: self
+                .include_light_content
+                .unwrap_or(defaults.include_light_content),
+            strip_image_credits: self
+                .strip_image_credits
+                .unwrap_or(defaults.strip_image_credits),
+            flatten_wrappers: self.flatten_wrappers.unwrap_or(defaults.flatten_wrappers),
+            embedded_html_min_length: self
+                .embedded_html_min_length
+                .unwrap_or(defaults.embedded_html_min_length),
+            iframe_srcdoc_min_length: self
+                .iframe_srcdoc_min_length
+                .unwrap_or(defaults.iframe_srcdoc_min_length),
+            keep_standalone_captions: self
+                .keep_standalone_captions
+                .unwrap_or(defaults.keep_standalone_captions),
+            remove_empty_sections: self
+                .remove_empty_sections
+                .unwrap_or(defaults.remove_empty_sections),
+            clean_fragment_max_elements: self
+                .clean_fragment_max_elements
+                .unwrap_or(defaults.clean_fragment_max_elements),
+            convert_brs: self.convert_brs.unwrap_or(defaults.convert_brs),
+            preserve_whitespace_tags: self
+                .preserve_whitespace_tags
+                .unwrap_or(defaults.preserve_whitespace_tags),
+            convert_scene_breaks: self
+                .convert_scene_breaks
+                .unwrap_or(defaults.convert_scene_breaks),
+            remove_emoji_paragraphs: self
+                .remove_emoji_paragraphs
+                .unwrap_or(defaults.remove_emoji_paragraphs),
+            open_details: self.open_details.unwrap_or(defaults.open_details),
+            text_normalizer: self.text_normalizer.or(defaults.text_normalizer),
+            content_profile: self.content_profile.unwrap_or(defaults.content_profile),
+            remove_consent_banners: self
+                .remove_consent_banners
+                .unwrap_or(defaults.remove_consent_banners),
+            svg_policy: self.svg_policy.unwrap_or(defaults.svg_policy),
+            selector_fallback: self.selector_fallback.unwrap_or(defaults.selector_fallback),
+            merge_split_lists: self.merge_split_lists.unwrap_or(defaults.merge_split_lists),
         }
     }
 }
@@ -0,0 +1,144 @@
+//! Conversion of article HTML to readable plain text.
+//!
+//! [`html_to_plain_text`] flattens cleaned article content to text with one
+//! line per block: paragraphs, headings, list items and other block-level
+//! elements each start a new line, while inline runs such as links and
+//! emphasis are joined with the text around them. Unlike
+//! `Article::text_content`, which keeps the DOM's text as written, blocks
+//! never run into each other.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use readabilityrs::plain_text::html_to_plain_text;
+//!
+//! let html = "<h2>Herons</h2><p>They hunt\n   <em>at dawn</em>.</p><ul><li>Wait</li><li>Strike</li></ul>";
+//! assert_eq!(html_to_plain_text(html), "Herons\nThey hunt at dawn.\nWait\nStrike");
+//! ```
+
+use crate::{dom_utils, utils};
+use ego_tree::iter::Edge;
+use scraper::{ElementRef, Html};
+
+/// Zero-width characters dropped from the text.
+const ZERO_WIDTH_CHARS: [char; 4] = ['\u{200B}', '\u{200C}', '\u{200D}', '\u{FEFF}'];
+
+/// Convert article HTML to plain text, one line per block.
+///
+/// - Block-level elements and `<br>` end the current line.
+/// - Within a line, whitespace runs collapse to a single space and the line
+///   is trimmed; empty lines are left out.
+/// - `<pre>` keeps its text verbatim as a block of its own lines.
+/// - Zero-width spaces, joiners and non-joiners are dropped.
+pub fn html_to_plain_text(html: &str) -> String {
+    let doc = Html::parse_fragment(html);
+    let mut lines: Vec<String> = Vec::new();
+    let mut line = String::new();
+    let mut pre_depth = 0;
+
+    for edge in doc.root_element().traverse() {
+        match edge {
+            Edge::Open(node) => {
+                if let Some(text) = node.value().as_text() {
+                    line.push_str(text);
+                    continue;
+                }
+                let Some(element) = ElementRef::wrap(node) else {
+                    continue;
+                };
+                match element.value().name() {
+                    "br" if pre_depth == 0 => push_line(&mut lines, &mut line),
+                    "br" => line.push('\n'),
+                    "pre" => {
+                        if pre_depth == 0 {
+                            push_line(&mut lines, &mut line);
+                        }
+                        pre_depth += 1;
+                    }
+                    _ if pre_depth == 0 && !dom_utils::is_phrasing_content(element) => {
+                        push_line(&mut lines, &mut line)
+                    }
+                    _ => {}
+                }
+            }
+            Edge::Close(node) => {
+                let Some(element) = ElementRef::wrap(node) else {
+                    continue;
+                };
+                if element.value().name() == "pre" {
+                    pre_depth -= 1;
+                    if pre_depth == 0 {
+                        let code = strip_zero_width(&line);
+                        let code = code.trim_matches('\n');
+                        if !code.trim().is_empty() {
+                            lines.push(code.to_string());
+                        }
+                        line.clear();
+                    }
+                } else if pre_depth == 0 && !dom_utils::is_phrasing_content(element) {
+                    push_line(&mut lines, &mut line);
+                }
+            }
+        }
+    }
+    push_line(&mut lines, &mut line);
+
+    lines.join("\n")
+}
+
+/// Collapse and push the current inline run as a line, if it has text.
+fn push_line(lines: &mut Vec<String>, line: &mut String) {
+    let text = utils::replace_nbsp(&strip_zero_width(line))
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    if !text.is_empty() {
+        lines.push(text);
+    }
+    line.clear();
+}
+
+fn strip_zero_width(text: &str) -> String {
+    text.chars()
+        .filter(|c| !ZERO_WIDTH_CHARS.contains(c))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_html_to_plain_text() {
+        let html = "<div><h1>Herons</h1>
+<p>Grey&nbsp;herons   hunt <a href=\"/dawn\">at\u{200B} dawn</a>,<br>standing still.</p>
+<p>Nests<span> in</span> <strong>pines</strong>.</p><p>Se\u{200D}ason</p>
+<pre><code>fn main() {
+    wait();
+}
+</code></pre>
+<blockquote><p>Patience.</p></blockquote>
+<table><tr><td>Egret</td><td>White</td></tr></table></div>";
+
+        assert_eq!(
+            html_to_plain_text(html),
+            "Herons
+Grey herons hunt at dawn,
+standing still.
+Nests in pines.
+Season
+fn main() {
+    wait();
+}
+Patience.
+Egret
+White"
+        );
+    }
+
+    #[test]
+    fn test_html_to_plain_text_separates_adjacent_blocks() {
+        assert_eq!(html_to_plain_text("<p>One</p><p>Two</p>"), "One\nTwo");
+        assert_eq!(html_to_plain_text("<p> </p>"), "");
+    }
+}
@@ -3,21 +3,90 @@
 //! This module implements Mozilla's _prepArticle pipeline, which cleans
 //! the extracted article content by removing unwanted elements.
 
+use crate::article::{MediaItem, MediaKind};
+use crate::cleaner;
+use crate::constants::PHRASING_ELEMS;
+use crate::options::SvgPolicy;
+use ego_tree::NodeId;
 use once_cell::sync::Lazy;
 use regex::Regex;
-use scraper::{Html, Selector};
+use scraper::node::Attributes;
+use scraper::{ElementRef, Html, Selector};
+use std::collections::HashSet;
 
 /// Remove nav-heavy wrappers by descending into content-like children.
 /// Note: "widget" is excluded from this pattern since page builders use it for content.
 fn unwrap_nav_wrappers(html: &str) -> String {
-    static WRAPPER_REGEX: Lazy<Regex> = Lazy::new(|| {
+    const KEYWORDS: [&str; 5] = ["navbar", "nav", "menu", "sidebar", "header"];
+    remove_elements_where(html, |el| {
+        el.value().name() == "div" && attr_contains_any(el.value().attr("class"), &KEYWORDS)
+    })
+}
+
+/// Detach every element matching `predicate`, with its subtree.
+///
+/// Matching on the parsed DOM rather than on the markup means attribute
+/// quoting and entity encoding in the source make no difference. Returns the
+/// input unchanged when nothing matches.
+fn remove_elements_where(html: &str, predicate: impl Fn(ElementRef) -> bool) -> String {
+    let mut doc = Html::parse_fragment(html);
+    let matched: Vec<NodeId> = doc
+        .root_element()
+        .descendants()
+        .filter_map(ElementRef::wrap)
+        .filter(|el| predicate(*el) && !holds_article_text(*el))
+        .map(|el| el.id())
+        .collect();
+    if matched.is_empty() {
+        return html.to_string();
+    }
+
+    for id in matched {
+        if let Some(mut node) = doc.tree.get_mut(id) {
+            node.detach();
+        }
+    }
+    doc.root_element().inner_html()
+}
+
+/// Remove the navigation documentation sites place inside the content column
+///
+/// Tables of contents, sidebars, breadcrumbs, previous/next pagination,
+/// "edit this page" links and page feedback widgets, recognized by their
+/// class or id. Elements holding more prose than such a widget would are
+/// kept.
+pub fn remove_documentation_chrome(html: &str) -> String {
+    static DOCS_CHROME_REGEX: Lazy<Regex> = Lazy::new(|| {
         Regex::new(
-            r#"(?is)<div[^>]+class="[^"]*(?:navbar|nav|menu|sidebar|header)[^"]*"[^>]*>.*?</div>"#,
+            r"(?i)(?:^|[\s_-])(?:toc|table-?of-?contents|on-?this-?page|sidebar|breadcrumbs?|pagination(?:-nav)?|pager|edit-?(?:this-?)?page|page-?feedback|was-?this-?helpful)(?:$|[\s_-])",
         )
         .unwrap()
     });
 
-    WRAPPER_REGEX.replace_all(html, "").to_string()
+    remove_elements_where(html, |el| {
+        let elem = el.value();
+        DOCS_CHROME_REGEX.is_match(elem.attr("class").unwrap_or(""))
+            || DOCS_CHROME_REGEX.is_match(elem.id().unwrap_or(""))
+    })
+}
+
+/// Check whether an element holds more prose than a widget would.
+///
+/// Class and id keywords are matched as substrings, so an article section
+/// with an id like `sharing-your-work` or a wrapper classed `story-header`
+/// can match; those are kept.
+pub(crate) fn holds_article_text(element: ElementRef) -> bool {
+    const WIDGET_MAX_TEXT_LENGTH: usize = 500;
+    crate::dom_utils::get_inner_text(element, true).len() > WIDGET_MAX_TEXT_LENGTH
+        && crate::dom_utils::get_link_density(element) < 0.5
+}
+
+/// Check whether an attribute value contains any of `keywords`, ignoring case.
+fn attr_contains_any(value: Option<&str>, keywords: &[&str]) -> bool {
+    value.is_some_and(|value| {
+        let value = value.to_lowercase();
+        keywords.iter().any(|keyword| value.contains(keyword))
+    })
 }
 
 /// Remove the title element from the article content if it matches the extracted title.
@@ -73,6 +142,96 @@ pub fn remove_title_from_content(html: &str, title: &str) -> String {
     html.to_string()
 }
 
+/// Unwrap the `<header>` around the title heading so its byline and date stay
+/// at the top of the article body.
+///
+/// Finds the first h1 or h2 matching `title` and, if it sits inside a `<header>`,
+/// replaces the header with its own content. Inline leftovers (e.g. a byline
+/// `<span>` followed by a `<time>`) are wrapped in a `<p>` so they read as the
+/// article's first line once the title is removed. Meant to run right before
+/// [`remove_title_from_content`].
+///
+/// # Arguments
+/// * `html` - The article HTML content
+/// * `title` - The extracted article title to match against
+///
+/// # Returns
+/// The HTML with the title's header unwrapped, or the original HTML if the
+/// title is not inside a header
+pub fn promote_title_header(html: &str, title: &str) -> String {
+    static HEADING_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("h1, h2").unwrap());
+
+    let normalized_title = normalize_text(title);
+    if normalized_title.is_empty() {
+        return html.to_string();
+    }
+
+    let doc = Html::parse_fragment(html);
+    let Some(heading) = doc.select(&HEADING_SELECTOR).find(|heading| {
+        titles_match(
+            &normalized_title,
+            &normalize_text(&heading.text().collect::<String>()),
+        )
+    }) else {
+        return html.to_string();
+    };
+
+    let Some(header) = heading
+        .ancestors()
+        .filter_map(ElementRef::wrap)
+        .take_while(|el| !matches!(el.value().name(), "article" | "body" | "html"))
+        .find(|el| el.value().name() == "header")
+    else {
+        return html.to_string();
+    };
+
+    // The header is located in the original string by its text rather than
+    // its serialization, which may escape attributes differently.
+    static HEADER_REGEX: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"(?is)<header\b[^>]*>.*?</header>").unwrap());
+    let header_text = normalize_text(&header.text().collect::<String>());
+    let Some(header_match) = HEADER_REGEX.find_iter(html).find(|m| {
+        let fragment = Html::parse_fragment(m.as_str());
+        normalize_text(&fragment.root_element().text().collect::<String>()) == header_text
+    }) else {
+        return html.to_string();
+    };
+
+    let inner_html = header.inner_html();
+    let heading_html = heading.html();
+    let Some(heading_pos) = inner_html.find(&heading_html) else {
+        return html.to_string();
+    };
+
+    let rest = format!(
+        "{}{}",
+        &inner_html[..heading_pos],
+        &inner_html[heading_pos + heading_html.len()..]
+    );
+    let rest_is_inline = header.descendants().filter_map(ElementRef::wrap).all(|el| {
+        el.id() == header.id()
+            || el.id() == heading.id()
+            || el.ancestors().any(|a| a.id() == heading.id())
+            || matches!(el.value().name(), "a" | "del" | "ins")
+            || PHRASING_ELEMS.contains(&el.value().name().to_ascii_uppercase().as_str())
+    });
+
+    let mut replacement = heading_html;
+    if rest.trim().is_empty() {
+        // Nothing besides the title; title removal cleans it up.
+    } else if rest_is_inline {
+        replacement.push_str(&format!("\n<p>{}</p>", rest.trim()));
+    } else {
+        replacement.push_str(&rest);
+    }
+
+    let mut result = String::with_capacity(html.len());
+    result.push_str(&html[..header_match.start()]);
+    result.push_str(&replacement);
+    result.push_str(&html[header_match.end()..]);
+    result
+}
+
 /// Remove a heading element using regex when direct string matching fails.
 /// This handles cases where scraper's serialized HTML differs from the original.
 fn remove_heading_by_regex(html: &str, tag: &str, text: &str) -> String {
@@ -144,6 +303,548 @@ fn cleanup_after_title_removal(html: &str) -> String {
     result
 }
 
+/// Demote every `<h1>` after the first one to `<h2>`.
+///
+/// Poorly structured pages use `<h1>` for section headings, leaving the output
+/// with several top-level headings. Keeping only the first one yields a
+/// single-h1 document; attributes and content of demoted headings are kept.
+pub fn demote_extra_h1s(html: &str) -> String {
+    static H1_TAG_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)<(/?)h1\b").unwrap());
+
+    let mut opens = 0;
+    let mut closes = 0;
+    H1_TAG_REGEX
+        .replace_all(html, |caps: &regex::Captures| {
+            let count = if caps[1].is_empty() {
+                opens += 1;
+                opens
+            } else {
+                closes += 1;
+                closes
+            };
+            if count == 1 {
+                caps[0].to_string()
+            } else {
+                format!("<{}h2", &caps[1])
+            }
+        })
+        .to_string()
+}
+
+static ANY_HEADING_SELECTOR: Lazy<Selector> =
+    Lazy::new(|| Selector::parse("h1, h2, h3, h4, h5, h6").unwrap());
+
+/// Wrap each heading and the content after it in a `<section>`.
+///
+/// A heading opens a section that closes before the next heading of the same or
+/// a higher level, so `<h3>`s under an `<h2>` end up in nested sections.
+/// Sectioning happens among the children of whichever element holds the
+/// headings. A heading that is already the first element of a `<section>` is
+/// treated as that section's heading, so running this twice changes nothing.
+pub fn wrap_heading_sections(html: &str) -> String {
+    let doc = Html::parse_fragment(html);
+    if doc.select(&ANY_HEADING_SELECTOR).next().is_none() {
+        return html.to_string();
+    }
+
+    let mut result = String::new();
+    push_sectioned_children(doc.root_element(), &mut result);
+    result
+}
+
+fn heading_level(element: ElementRef) -> Option<u8> {
+    match element.value().name() {
+        "h1" => Some(1),
+        "h2" => Some(2),
+        "h3" => Some(3),
+        "h4" => Some(4),
+        "h5" => Some(5),
+        "h6" => Some(6),
+        _ => None,
+    }
+}
+
+fn push_sectioned_children(parent: ElementRef, out: &mut String) {
+    use scraper::node::Node;
+
+    // A section's leading heading belongs to it already.
+    let own_heading = (parent.value().name() == "section")
+        .then(|| parent.children().find_map(ElementRef::wrap))
+        .flatten()
+        .filter(|first| heading_level(*first).is_some())
+        .map(|heading| heading.id());
+
+    let mut open_levels: Vec<u8> = Vec::new();
+    for child in parent.children() {
+        match child.value() {
+            Node::Element(_) => {
+                let Some(element) = ElementRef::wrap(child) else {
+                    continue;
+                };
+                match heading_level(element) {
+                    Some(level) if Some(element.id()) != own_heading => {
+                        while open_levels.last().is_some_and(|open| *open >= level) {
+                            open_levels.pop();
+                            out.push_str("</section>");
+                        }
+                        open_levels.push(level);
+                        out.push_str("<section>");
+                        out.push_str(&element.html());
+                    }
+                    Some(_) => out.push_str(&element.html()),
+                    None if element.select(&ANY_HEADING_SELECTOR).next().is_some() => {
+                        // Descend into containers that hold headings, keeping
+                        // their start and end tags as serialized.
+                        let outer = element.html();
+                        let inner = element.inner_html();
+                        let end_tag_len = element.value().name().len() + 3;
+                        let start_len = outer.len() - inner.len() - end_tag_len;
+                        out.push_str(&outer[..start_len]);
+                        push_sectioned_children(element, out);
+                        out.push_str(&outer[start_len + inner.len()..]);
+                    }
+                    None => out.push_str(&element.html()),
+                }
+            }
+            Node::Text(text) => out.push_str(&escape_text(&text.text)),
+            Node::Comment(comment) => {
+                out.push_str("<!--");
+                out.push_str(&comment.comment);
+                out.push_str("-->");
+            }
+            _ => {}
+        }
+    }
+
+    for _ in open_levels {
+        out.push_str("</section>");
+    }
+}
+
+/// Remove headings of sections left empty by cleaning
+///
+/// A heading directly followed by a heading of the same or a higher level, with
+/// only whitespace, comments, `<br>`s or empty wrappers between them, introduces
+/// no content: whatever it headed was removed. `<h2>A</h2><h2>B</h2>` becomes
+/// `<h2>B</h2>`, while `<h2>A</h2><h3>B</h3>` is kept since `B` is a subsection
+/// of `A`.
+pub fn remove_empty_sections(html: &str) -> String {
+    use scraper::node::Node;
+
+    fn is_blank(node: ego_tree::NodeRef<Node>) -> bool {
+        match node.value() {
+            Node::Text(text) => text.trim().is_empty(),
+            Node::Element(element) => {
+                element.name() == "br"
+                    || (matches!(element.name(), "div" | "span" | "p" | "section")
+                        && node.children().all(is_blank))
+            }
+            _ => true,
+        }
+    }
+
+    let mut doc = Html::parse_fragment(html);
+    let empty: Vec<NodeId> = doc
+        .select(&ANY_HEADING_SELECTOR)
+        .filter(|heading| {
+            let Some(level) = heading_level(*heading) else {
+                return false;
+            };
+            heading
+                .next_siblings()
+                .find(|sibling| !is_blank(*sibling))
+                .and_then(ElementRef::wrap)
+                .and_then(heading_level)
+                .is_some_and(|next_level| next_level <= level)
+        })
+        .map(|heading| heading.id())
+        .collect();
+    if empty.is_empty() {
+        return html.to_string();
+    }
+
+    for id in empty {
+        if let Some(mut node) = doc.tree.get_mut(id) {
+            node.detach();
+        }
+    }
+    doc.root_element().inner_html()
+}
+
+/// Escape a text node the way scraper serializes it.
+fn escape_text(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('\u{a0}', "&nbsp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Paragraphs longer than this are treated as real content even if they
+/// contain a boilerplate phrase.
+const BOILERPLATE_MAX_LENGTH: usize = 200;
+
+/// Remove boilerplate paragraphs from the start and end of the article content.
+///
+/// Walks the paragraphs in document order and removes the leading run, then the
+/// trailing run, of short paragraphs whose text contains one of `phrases`
+/// (case-insensitive). Stripping stops at the first paragraph that is not
+/// boilerplate, so matching paragraphs in the middle of the article are kept.
+///
+/// # Arguments
+/// * `html` - The article HTML content
+/// * `phrases` - Phrases that mark a paragraph as boilerplate
+///
+/// # Returns
+/// The HTML with the leading and trailing boilerplate paragraphs removed
+pub fn strip_boilerplate_paragraphs(html: &str, phrases: &[String]) -> String {
+    static P_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("p").unwrap());
+
+    let phrases: Vec<String> = phrases
+        .iter()
+        .map(|phrase| normalize_text(phrase))
+        .filter(|phrase| !phrase.is_empty())
+        .collect();
+    if phrases.is_empty() {
+        return html.to_string();
+    }
+
+    let doc = Html::parse_fragment(html);
+    let paragraphs: Vec<(String, bool)> = doc
+        .select(&P_SELECTOR)
+        .filter_map(|p| {
+            let text = normalize_text(&p.text().collect::<String>());
+            if text.is_empty() {
+                return None;
+            }
+            let is_boilerplate = text.chars().count() <= BOILERPLATE_MAX_LENGTH
+                && phrases.iter().any(|phrase| text.contains(phrase.as_str()));
+            Some((p.html(), is_boilerplate))
+        })
+        .collect();
+
+    let leading = paragraphs.iter().take_while(|(_, b)| *b).count();
+    let trailing = paragraphs[leading..]
+        .iter()
+        .rev()
+        .take_while(|(_, b)| *b)
+        .count();
+    if leading == 0 && trailing == 0 {
+        return html.to_string();
+    }
+
+    let mut result = html.to_string();
+    for (paragraph_html, _) in paragraphs[..leading].iter() {
+        if let Some(pos) = result.find(paragraph_html.as_str()) {
+            result.replace_range(pos..pos + paragraph_html.len(), "");
+        }
+    }
+    for (paragraph_html, _) in paragraphs[paragraphs.len() - trailing..].iter().rev() {
+        if let Some(pos) = result.rfind(paragraph_html.as_str()) {
+            result.replace_range(pos..pos + paragraph_html.len(), "");
+        }
+    }
+
+    cleanup_after_title_removal(&result)
+}
+
+/// Breadcrumb trails longer than this are treated as real content.
+const BREADCRUMB_MAX_LENGTH: usize = 120;
+/// Breadcrumb segments longer than this are treated as real content.
+const BREADCRUMB_MAX_SEGMENT_LENGTH: usize = 40;
+
+/// Remove a plain-text breadcrumb trail from the start of the article content.
+///
+/// Some sites render breadcrumbs as `Home › Section › Article` in a bare `<p>`
+/// or `<div>`, which the navigation class and id checks never see. Only the
+/// first block of text is considered: it is removed when it is short and
+/// splits on `›`, `»`, `/` or `>` separators into at least three short
+/// segments, or two for a `›` or `»` trail with a link. A heading or any
+/// other content first means there is no breadcrumb to strip.
+///
+/// # Arguments
+/// * `html` - The article HTML content
+///
+/// # Returns
+/// The HTML with the leading breadcrumb block removed
+pub fn strip_leading_breadcrumbs(html: &str) -> String {
+    static SEPARATOR_REGEX: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"\s*[›»]\s*|\s+[/>]\s+").unwrap());
+
+    let mut doc = Html::parse_fragment(html);
+    let Some(first_block) = doc
+        .root_element()
+        .descendants()
+        .filter_map(ElementRef::wrap)
+        .find(|el| {
+            !crate::dom_utils::get_inner_text(*el, true).is_empty()
+                && !crate::dom_utils::has_child_block_element(*el)
+        })
+    else {
+        return html.to_string();
+    };
+    if !matches!(first_block.value().name(), "p" | "div") {
+        return html.to_string();
+    }
+
+    let has_descendant = |tag: &str| {
+        first_block
+            .descendants()
+            .filter_map(ElementRef::wrap)
+            .any(|el| el.value().name() == tag)
+    };
+    let text = crate::dom_utils::get_inner_text(first_block, true);
+    if text.chars().count() > BREADCRUMB_MAX_LENGTH || has_descendant("img") {
+        return html.to_string();
+    }
+
+    let segments: Vec<&str> = SEPARATOR_REGEX
+        .split(&text)
+        .map(str::trim)
+        .filter(|segment| !segment.is_empty())
+        .collect();
+    // Two segments are only enough for an unambiguous `›`/`»` trail with a
+    // link, so bylines like `By Jane / May 1` are kept.
+    let min_segments = if has_descendant("a") && text.contains(['›', '»']) {
+        2
+    } else {
+        3
+    };
+    let is_breadcrumb = segments.len() >= min_segments
+        && segments
+            .iter()
+            .all(|segment| segment.chars().count() <= BREADCRUMB_MAX_SEGMENT_LENGTH);
+    if !is_breadcrumb {
+        return html.to_string();
+    }
+
+    let id = first_block.id();
+    if let Some(mut node) = doc.tree.get_mut(id) {
+        node.detach();
+    }
+    cleanup_after_title_removal(&doc.root_element().inner_html())
+}
+
+/// Remove footnote backref links, keeping the footnote text.
+///
+/// Footnote lists end each note with a link back to the reference in the body,
+/// such as `<a href="#ref1">↩</a>`, which is noise once the content is read as
+/// text or Markdown. A backref is an in-page link (`href="#..."`) whose only
+/// text is `↩`, `↑`, `^` or `Back` and that sits in a list item or in an
+/// element whose class or id names a footnote, note, citation or reference, so
+/// in-page links in the body text are left alone. Inline wrappers that hold
+/// nothing but the backref, like Wikipedia's
+/// `<span class="mw-cite-backlink"><b>...</b></span>`, are removed with it.
+pub fn remove_footnote_backrefs(html: &str) -> String {
+    const BACKREF_TEXTS: [&str; 5] = ["↩", "↩\u{fe0e}", "↑", "^", "back"];
+    const WRAPPER_TAGS: [&str; 6] = ["b", "strong", "i", "em", "sup", "span"];
+    static IN_PAGE_LINK_SELECTOR: Lazy<Selector> =
+        Lazy::new(|| Selector::parse(r##"a[href^="#"]"##).unwrap());
+    static FOOTNOTE_REGEX: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"(?i)foot|note|cite|ref").unwrap());
+
+    let in_footnote = |link: ElementRef| {
+        link.ancestors()
+            .filter_map(ElementRef::wrap)
+            .any(|ancestor| {
+                let elem = ancestor.value();
+                elem.name() == "li"
+                    || FOOTNOTE_REGEX.is_match(elem.attr("class").unwrap_or(""))
+                    || FOOTNOTE_REGEX.is_match(elem.id().unwrap_or(""))
+            })
+    };
+
+    let mut doc = Html::parse_fragment(html);
+    let backrefs: Vec<NodeId> = doc
+        .select(&IN_PAGE_LINK_SELECTOR)
+        .filter(|link| {
+            let text = link.text().collect::<String>();
+            BACKREF_TEXTS.contains(&text.trim().to_lowercase().as_str()) && in_footnote(*link)
+        })
+        .map(|link| {
+            let mut target = link;
+            while let Some(parent) = target.parent().and_then(ElementRef::wrap) {
+                let only_child = parent.children().all(|child| {
+                    child.id() == target.id()
+                        || child.value().as_text().is_some_and(|t| t.trim().is_empty())
+                });
+                if !WRAPPER_TAGS.contains(&parent.value().name()) || !only_child {
+                    break;
+                }
+                target = parent;
+            }
+            target.id()
+        })
+        .collect();
+    if backrefs.is_empty() {
+        return html.to_string();
+    }
+
+    for id in backrefs {
+        if let Some(mut node) = doc.tree.get_mut(id) {
+            node.detach();
+        }
+    }
+    doc.root_element().inner_html()
+}
+
+/// Strip photo credits from image captions, keeping the description.
+///
+/// A caption such as `A heron at dawn. Photo: Getty Images / Jane Doe` loses
+/// everything from the first `Photo:`, `Photo by`, `Credit:`, `Image:`, `©`
+/// or `(c)` marker on, along with the separator before it. Elements inside a
+/// `<figcaption>` whose class or id names a credit, copyright or attribution
+/// are removed whatever their text. A caption left empty is removed.
+///
+/// # Arguments
+/// * `html` - The article HTML content
+///
+/// # Returns
+/// The HTML with credit lines removed from `<figcaption>` elements
+pub fn strip_image_credits(html: &str) -> String {
+    use scraper::node::Node;
+
+    static FIGCAPTION_SELECTOR: Lazy<Selector> =
+        Lazy::new(|| Selector::parse("figcaption").unwrap());
+    static CREDIT_CLASS_REGEX: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"(?i)credit|copyright|attribution|photographer").unwrap());
+    static CREDIT_REGEX: Lazy<Regex> = Lazy::new(|| {
+        Regex::new(
+            r"(?i)(?:^|[\s|/•·—–(-])(?:(?:(?:photo(?:graph)?|image|picture|illustration)s?\s+)?credits?\s*:|(?:photo(?:graph)?|image|picture|illustration)s?(?:\s+by\b|\s*:)|©|\(c\)\s)",
+        )
+        .unwrap()
+    });
+    const SEPARATORS: &[char] = &['|', '/', '•', '·', '—', '–', '-', ',', ';', ':', '('];
+
+    if !html.contains("<figcaption") {
+        return html.to_string();
+    }
+
+    let mut doc = Html::parse_fragment(html);
+    let captions: Vec<NodeId> = doc.select(&FIGCAPTION_SELECTOR).map(|el| el.id()).collect();
+    if captions.is_empty() {
+        return html.to_string();
+    }
+
+    for caption_id in captions {
+        let Some(caption) = doc.tree.get(caption_id).and_then(ElementRef::wrap) else {
+            continue;
+        };
+        let credit_elements: Vec<NodeId> = caption
+            .descendants()
+            .filter_map(ElementRef::wrap)
+            .filter(|el| {
+                let elem = el.value();
+                CREDIT_CLASS_REGEX.is_match(elem.attr("class").unwrap_or(""))
+                    || CREDIT_CLASS_REGEX.is_match(elem.id().unwrap_or(""))
+            })
+            .map(|el| el.id())
+            .collect();
+        let mut changed = !credit_elements.is_empty();
+        for id in credit_elements {
+            if let Some(mut node) = doc.tree.get_mut(id) {
+                node.detach();
+            }
+        }
+
+        // Locate the credit marker in the caption text and the text node it starts in.
+        let Some(caption) = doc.tree.get(caption_id) else {
+            continue;
+        };
+        let text_nodes: Vec<(NodeId, &str)> = caption
+            .descendants()
+            .filter_map(|node| node.value().as_text().map(|text| (node.id(), &text.text[..])))
+            .collect();
+        let text: String = text_nodes.iter().map(|(_, text)| *text).collect();
+        let cut = CREDIT_REGEX.find(&text).and_then(|found| {
+            let mut start = 0;
+            text_nodes.iter().find_map(|(id, node_text)| {
+                let end = start + node_text.len();
+                let hit = (found.start() < end)
+                    .then(|| (*id, node_text[..found.start() - start].to_string()));
+                start = end;
+                hit
+            })
+        });
+
+        if let Some((text_id, kept)) = cut {
+            changed = true;
+            // Drop everything after the marker, up to the caption itself.
+            let mut following = Vec::new();
+            let mut current = doc.tree.get(text_id);
+            while let Some(node) = current.filter(|node| node.id() != caption_id) {
+                following.extend(node.next_siblings().map(|sibling| sibling.id()));
+                current = node.parent();
+            }
+            for id in following {
+                if let Some(mut node) = doc.tree.get_mut(id) {
+                    node.detach();
+                }
+            }
+            if let Some(mut node) = doc.tree.get_mut(text_id) {
+                if let Node::Text(text) = node.value() {
+                    text.text = kept.as_str().into();
+                }
+            }
+        }
+
+        // Trim the separator and spacing the credit leaves behind.
+        if changed {
+            let text_ids: Vec<NodeId> = doc
+                .tree
+                .get(caption_id)
+                .into_iter()
+                .flat_map(|caption| caption.descendants())
+                .filter(|node| node.value().is_text())
+                .map(|node| node.id())
+                .collect();
+            for id in text_ids.into_iter().rev() {
+                let Some(mut node) = doc.tree.get_mut(id) else {
+                    continue;
+                };
+                let Node::Text(text) = node.value() else {
+                    continue;
+                };
+                let trimmed = text
+                    .text
+                    .trim_end_matches(|c: char| c.is_whitespace() || SEPARATORS.contains(&c))
+                    .to_string();
+                text.text = trimmed.as_str().into();
+                if !trimmed.is_empty() {
+                    break;
+                }
+            }
+        }
+
+        let is_empty = changed
+            && doc
+                .tree
+                .get(caption_id)
+                .and_then(ElementRef::wrap)
+                .is_some_and(|caption| caption.text().all(|text| text.trim().is_empty()));
+        if is_empty {
+            if let Some(mut node) = doc.tree.get_mut(caption_id) {
+                node.detach();
+            }
+        }
+    }
+
+    doc.root_element().inner_html()
+}
+
+/// Check if an element holds an `<h1>` or `<h2>` whose text matches `title`.
+pub fn contains_title_heading(element: ElementRef, title: &str) -> bool {
+    static HEADING_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("h1, h2").unwrap());
+
+    let normalized_title = normalize_text(title);
+    !normalized_title.is_empty()
+        && element.select(&HEADING_SELECTOR).any(|heading| {
+            titles_match(
+                &normalized_title,
+                &normalize_text(&heading.text().collect::<String>()),
+            )
+        })
+}
+
 /// Normalize text for title comparison: lowercase, collapse whitespace, trim
 fn normalize_text(text: &str) -> String {
     static WHITESPACE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s+").unwrap());
@@ -182,7 +883,15 @@ fn titles_match(title1: &str, title2: &str) -> bool {
 /// * `html` - The raw extracted article HTML
 /// * `clean_styles_opt` - Whether to remove inline styles (implements Mozilla's _cleanStyles)
 /// * `clean_whitespace_opt` - Whether to normalize whitespace and remove empty paragraphs
-pub fn prep_article(html: &str, clean_styles_opt: bool, clean_whitespace_opt: bool) -> String {
+/// * `video_regex` - Embeds matching this pattern are kept as allowed videos
+/// * `preserve_tags` - Tags whose whitespace is left as written
+pub fn prep_article(
+    html: &str,
+    clean_styles_opt: bool,
+    clean_whitespace_opt: bool,
+    video_regex: &Regex,
+    preserve_tags: &[String],
+) -> String {
     let mut html = html.to_string();
 
     // Unwrap nav wrappers before removing elements
@@ -195,7 +904,7 @@ pub fn prep_article(html: &str, clean_styles_opt: bool, clean_whitespace_opt: bo
     }
 
     // Step 2: Remove unwanted elements
-    html = remove_unwanted_elements(&html);
+    html = remove_unwanted_elements(&html, video_regex);
 
     // Step 3: Remove share buttons and social widgets
     html = remove_share_elements(&html);
@@ -207,12 +916,623 @@ pub fn prep_article(html: &str, clean_styles_opt: bool, clean_whitespace_opt: bo
     if clean_whitespace_opt {
         html = remove_empty_paragraphs(&html);
         // Step 5: Clean up excessive whitespace and empty lines
-        html = normalize_whitespace(&html);
+        html = normalize_whitespace(&html, preserve_tags);
     }
 
     html
 }
 
+/// Merge adjacent lists that were one list split by an ad or divider
+///
+/// Two `<ul>`s, or two `<ol>`s, separated only by whitespace and comments
+/// are joined when their `type` matches. An `<ol>` is only joined to the list
+/// before it when its `start` carries on that list's numbering, as in
+/// `<ol><li>..</li><li>..</li></ol><ol start="3">`.
+pub fn merge_split_lists(html: &str) -> String {
+    use scraper::node::Node;
+    static LIST_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("ul, ol").unwrap());
+
+    if !html.contains("<ul") && !html.contains("<ol") {
+        return html.to_string();
+    }
+
+    let item_count = |list: ElementRef| {
+        list.children()
+            .filter_map(ElementRef::wrap)
+            .filter(|child| child.value().name() == "li")
+            .count()
+    };
+    let start = |list: ElementRef| {
+        list.value()
+            .attr("start")
+            .and_then(|start| start.trim().parse::<i64>().ok())
+    };
+    let continues = |first: ElementRef, second: ElementRef| {
+        let (a, b) = (first.value(), second.value());
+        if a.name() != b.name() || a.attr("type") != b.attr("type") {
+            return false;
+        }
+        a.name() == "ul"
+            || start(second) == Some(start(first).unwrap_or(1) + item_count(first) as i64)
+    };
+
+    let mut doc = Html::parse_fragment(html);
+    let mut merged = false;
+    loop {
+        let pair = doc.select(&LIST_SELECTOR).find_map(|list| {
+            let next = list.next_siblings().find(|node| match node.value() {
+                Node::Text(text) => !text.trim().is_empty(),
+                Node::Comment(_) => false,
+                _ => true,
+            })?;
+            let next = ElementRef::wrap(next)?;
+            continues(list, next).then(|| (list.id(), next.id()))
+        });
+        let Some((first, second)) = pair else {
+            break;
+        };
+        if let Some(mut node) = doc.tree.get_mut(first) {
+            node.reparent_from_id_append(second);
+        }
+        if let Some(mut node) = doc.tree.get_mut(second) {
+            node.detach();
+        }
+        merged = true;
+    }
+
+    if !merged {
+        return html.to_string();
+    }
+    doc.root_element().inner_html()
+}
+
+/// Remove structural wrappers left empty by earlier cleaning passes
+///
+/// Removing ads, share widgets and navigation often leaves behind the `<div>`s
+/// and `<section>`s that held them. Wrappers containing nothing but whitespace
+/// and comments are removed, repeating until nested empties are gone.
+/// Wrappers holding text or any element (images, embeds, line breaks) are kept.
+pub fn remove_empty_wrappers(html: &str) -> String {
+    const WRAPPER_TAGS: [&str; 10] = [
+        "div", "section", "article", "header", "footer", "aside", "hgroup", "main", "nav", "figure",
+    ];
+    // Comment bodies are matched without `--` so a match cannot backtrack
+    // past a comment's own `-->` and swallow the markup after it.
+    static EMPTY_WRAPPER_REGEXES: Lazy<Vec<Regex>> = Lazy::new(|| {
+        WRAPPER_TAGS
+            .iter()
+            .map(|tag| {
+                Regex::new(&format!(
+                    r"(?is)<{tag}\b[^>]*>(?:\s|&nbsp;|&#160;|<!--(?:[^-]|-[^-])*-->)*</{tag}>"
+                ))
+                .unwrap()
+            })
+            .collect()
+    });
+
+    let linked_ids = linked_fragment_ids(html);
+    let mut result = html.to_string();
+    loop {
+        let prev_len = result.len();
+        for regex in EMPTY_WRAPPER_REGEXES.iter() {
+            result = remove_unlinked(regex, &result, &linked_ids);
+        }
+        if result.len() == prev_len {
+            break;
+        }
+    }
+    result
+}
+
+/// Remove `<figure>`s left holding nothing but their `<figcaption>`
+///
+/// Figures whose image or embed was dropped during cleaning keep a caption
+/// describing content that is no longer there. Figures around quotes, tables
+/// or code keep their captions.
+pub fn remove_standalone_captions(html: &str) -> String {
+    use scraper::node::Node;
+    static FIGURE_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("figure").unwrap());
+
+    if !html.contains("<figcaption") {
+        return html.to_string();
+    }
+    let mut doc = Html::parse_fragment(html);
+    let standalone: Vec<NodeId> = doc
+        .select(&FIGURE_SELECTOR)
+        .filter(|figure| {
+            figure.children().all(|child| match child.value() {
+                Node::Element(element) => element.name() == "figcaption",
+                Node::Text(text) => text.trim().is_empty(),
+                _ => true,
+            })
+        })
+        .map(|figure| figure.id())
+        .collect();
+    if standalone.is_empty() {
+        return html.to_string();
+    }
+
+    for id in standalone {
+        if let Some(mut node) = doc.tree.get_mut(id) {
+            node.detach();
+        }
+    }
+    doc.root_element().inner_html()
+}
+
+/// Replace each `<p>` whose text satisfies `predicate` with `replacement`.
+///
+/// Paragraphs holding images or other media are left alone.
+fn replace_paragraphs_where(html: &str, predicate: fn(&str) -> bool, replacement: &str) -> String {
+    static PARAGRAPH_REGEX: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"(?is)<p\b[^>]*>(.*?)</p>").unwrap());
+    static TAG_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"<[^>]*>").unwrap());
+    static MEDIA_REGEX: Lazy<Regex> = Lazy::new(|| {
+        Regex::new(r"(?i)<(?:img|picture|svg|video|audio|iframe|object|embed)\b").unwrap()
+    });
+
+    if !html.contains("<p") {
+        return html.to_string();
+    }
+    PARAGRAPH_REGEX
+        .replace_all(html, |caps: &regex::Captures| {
+            let inner = &caps[1];
+            let text = crate::utils::unescape_html_entities(&TAG_REGEX.replace_all(inner, ""));
+            if !MEDIA_REGEX.is_match(inner) && predicate(&text) {
+                replacement.to_string()
+            } else {
+                caps[0].to_string()
+            }
+        })
+        .into_owned()
+}
+
+/// Convert scene-break paragraphs into `<hr>` thematic breaks
+///
+/// Fiction and long-form pieces mark section breaks with a paragraph of
+/// dividers such as `* * *`, `⁂` or `· · ·`. A paragraph made up only of
+/// divider characters and whitespace becomes an `<hr>`.
+pub fn convert_scene_breaks(html: &str) -> String {
+    replace_paragraphs_where(html, is_scene_break, "<hr>")
+}
+
+fn is_scene_break(text: &str) -> bool {
+    const DIVIDERS: &[char] = &[
+        '*', '∗', '⁂', '·', '•', '∙', '~', '#', '§', '✻', '❦', '❧', '◆', '◇', '—', '–', '-', '_',
+        '=', '+',
+    ];
+    let mut dividers = text.chars().filter(|c| !c.is_whitespace()).peekable();
+    dividers.peek().is_some() && dividers.all(|c| DIVIDERS.contains(&c))
+}
+
+/// Remove paragraphs holding nothing but emoji
+///
+/// Reaction rows such as `👏👏👏` or `🔥 😂` carry no article text. A
+/// paragraph is removed when its text is only emoji and whitespace.
+pub fn remove_emoji_paragraphs(html: &str) -> String {
+    replace_paragraphs_where(html, is_emoji_only, "")
+}
+
+fn is_emoji_only(text: &str) -> bool {
+    // Joiners, variation selectors, keycaps and tag characters only modify
+    // the pictograph they follow.
+    let is_modifier = |c: char| {
+        matches!(
+            c as u32,
+            0x200D | 0x20E3 | 0xFE0E | 0xFE0F | 0xE0020..=0xE007F
+        )
+    };
+    let is_pictograph = |c: char| {
+        matches!(
+            c as u32,
+            0x1F000..=0x1FAFF | 0x2300..=0x23FF | 0x2600..=0x27BF | 0x2B00..=0x2BFF
+        )
+    };
+    let mut has_pictograph = false;
+    for c in text.chars().filter(|c| !c.is_whitespace()) {
+        if is_pictograph(c) {
+            has_pictograph = true;
+        } else if !is_modifier(c) {
+            return false;
+        }
+    }
+    has_pictograph
+}
+
+/// Collapse chains of single-child `<div>` wrappers
+///
+/// Framework markup nests content in layers of `<div>`s that hold nothing but
+/// the next `<div>`. Each chain is replaced by its innermost wrapper, which
+/// takes the outer wrappers' classes and, when it has none, the first outer
+/// `id` so in-page links still resolve.
+pub fn flatten_wrappers(html: &str) -> String {
+    use scraper::node::Node;
+    static DIV_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("div").unwrap());
+
+    let mut doc = Html::parse_fragment(html);
+    let mut chains: Vec<(NodeId, NodeId, Vec<NodeId>)> = Vec::new();
+    for div in doc.select(&DIV_SELECTOR) {
+        if cleaner::wrapped_div(div).is_none()
+            || div
+                .parent()
+                .and_then(ElementRef::wrap)
+                .and_then(cleaner::wrapped_div)
+                .is_some()
+        {
+            continue;
+        }
+
+        let mut members = vec![div.id()];
+        let mut innermost = div;
+        while let Some(child) = cleaner::wrapped_div(innermost) {
+            innermost = child;
+            members.push(child.id());
+        }
+        members.pop();
+        chains.push((div.id(), innermost.id(), members));
+    }
+    if chains.is_empty() {
+        return html.to_string();
+    }
+
+    for (outer, innermost, wrappers) in chains {
+        let wrapper_attrs: Attributes = wrappers
+            .iter()
+            .filter_map(|id| doc.tree.get(*id))
+            .filter_map(|node| node.value().as_element())
+            .flat_map(|element| element.attrs.clone())
+            .collect();
+        if let Some(mut node) = doc.tree.get_mut(innermost) {
+            if let Node::Element(element) = node.value() {
+                merge_wrapper_attrs(&mut element.attrs, wrapper_attrs);
+            }
+        }
+        if let Some(mut node) = doc.tree.get_mut(outer) {
+            node.insert_id_before(innermost);
+            node.detach();
+        }
+    }
+    doc.root_element().inner_html()
+}
+
+/// Merge the classes of collapsed wrappers into `attrs`, and their first `id`
+/// when `attrs` has none.
+pub(crate) fn merge_wrapper_attrs(attrs: &mut Attributes, wrapper_attrs: Attributes) {
+    let mut classes: Vec<String> = Vec::new();
+    let mut class_name = None;
+    for (name, value) in wrapper_attrs.iter().chain(attrs.iter()) {
+        if name.local.as_ref() == "class" {
+            for class in value.split_whitespace() {
+                if !classes.iter().any(|seen| seen == class) {
+                    classes.push(class.to_string());
+                }
+            }
+            class_name.get_or_insert_with(|| name.clone());
+        }
+    }
+    if let Some(class_name) = class_name {
+        let value = classes.join(" ");
+        match attrs
+            .iter_mut()
+            .find(|(name, _)| name.local.as_ref() == "class")
+        {
+            Some(attr) => attr.1 = value.as_str().into(),
+            None => attrs.push((class_name, value.as_str().into())),
+        }
+    }
+
+    if !attrs.iter().any(|(name, _)| name.local.as_ref() == "id") {
+        if let Some(id) = wrapper_attrs
+            .into_iter()
+            .find(|(name, _)| name.local.as_ref() == "id")
+        {
+            attrs.push(id);
+        }
+    }
+    // Attribute lookups binary-search the list.
+    attrs.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+}
+
+/// Load the first `count` images eagerly
+///
+/// Pages mark images `loading="lazy"` for their own scroll container; in a
+/// reader view the lazy images at the top may never be loaded. The first
+/// `count` `<img>` elements get `loading="eager"` and lose `decoding="async"`,
+/// so they load and paint with the article. `sizes`, `srcset` and other
+/// attributes are left untouched.
+pub fn make_leading_images_eager(html: &str, count: usize) -> String {
+    static IMG_TAG_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)<img\b[^>]*>").unwrap());
+    static LOADING_ATTR_REGEX: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r#"(?i)\s+loading\s*=\s*(?:"[^"]*"|'[^']*'|[^\s>]+)"#).unwrap());
+    static DECODING_ASYNC_REGEX: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r#"(?i)\s+decoding\s*=\s*["']?async["']?"#).unwrap());
+
+    let mut seen = 0;
+    IMG_TAG_REGEX
+        .replace_all(html, |caps: &regex::Captures| {
+            seen += 1;
+            if seen > count {
+                return caps[0].to_string();
+            }
+            let tag = LOADING_ATTR_REGEX.replace_all(&caps[0], "");
+            let tag = DECODING_ASYNC_REGEX.replace_all(&tag, "");
+            format!(r#"<img loading="eager"{}"#, &tag[4..])
+        })
+        .to_string()
+}
+
+/// Open FAQ-style `<details>` and keep spoilers closed
+///
+/// Collapsed answers in FAQs are part of the article and are opened so a
+/// reader sees them. A `<details>` whose class or id mentions `spoiler`, or
+/// whose `<summary>` text does, is a spoiler instead: it loses any `open`
+/// attribute and is marked with `data-spoiler` so renderers can style it.
+/// The `<summary>` is kept in both cases.
+pub fn open_details(html: &str) -> String {
+    static DETAILS_REGEX: Lazy<Regex> = Lazy::new(|| {
+        Regex::new(r"(?is)<details\b([^>]*)>(\s*<summary\b[^>]*>(.*?)</summary>)?").unwrap()
+    });
+    static ATTR_REGEX: Lazy<Regex> = Lazy::new(|| {
+        Regex::new(r#"\s+([^\s=/>]+)(?:\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s>]+)))?"#).unwrap()
+    });
+    static SPOILER_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)spoiler").unwrap());
+
+    if !html.contains("<details") {
+        return html.to_string();
+    }
+    DETAILS_REGEX
+        .replace_all(html, |caps: &regex::Captures| {
+            let mut attrs = String::new();
+            let mut is_spoiler = caps
+                .get(3)
+                .is_some_and(|summary| SPOILER_REGEX.is_match(summary.as_str()));
+            for attr in ATTR_REGEX.captures_iter(&caps[1]) {
+                let name = attr[1].to_ascii_lowercase();
+                let value = attr.get(2).or(attr.get(3)).or(attr.get(4));
+                if name == "class" || name == "id" {
+                    is_spoiler |= value.is_some_and(|value| SPOILER_REGEX.is_match(value.as_str()));
+                }
+                if name != "open" && name != "data-spoiler" {
+                    attrs.push_str(&attr[0]);
+                }
+            }
+            let marker = if is_spoiler { " data-spoiler" } else { " open" };
+            let summary = caps.get(2).map_or("", |summary| summary.as_str());
+            format!("<details{attrs}{marker}>{summary}")
+        })
+        .to_string()
+}
+
+/// Largest width or height of an `<svg>` drawn as an icon.
+const ICON_SVG_MAX_SIZE: f64 = 48.0;
+
+/// Remove inline `<svg>` elements according to `policy`
+///
+/// See [`SvgPolicy`] for what counts as a decorative icon. The size of an
+/// `<svg>` comes from its `width` and `height` attributes, or its `viewBox`
+/// when those are missing or relative.
+pub fn remove_svgs(html: &str, policy: SvgPolicy) -> String {
+    if policy == SvgPolicy::KeepAll || !html.contains("<svg") {
+        return html.to_string();
+    }
+    remove_elements_where(html, |el| {
+        el.value().name() == "svg" && (policy == SvgPolicy::StripAll || is_decorative_svg(el))
+    })
+}
+
+fn is_decorative_svg(svg: ElementRef) -> bool {
+    let elem = svg.value();
+    let is_named = elem
+        .attr("aria-label")
+        .is_some_and(|label| !label.trim().is_empty())
+        || svg
+            .children()
+            .filter_map(ElementRef::wrap)
+            .any(|child| matches!(child.value().name(), "title" | "desc"));
+    if is_named {
+        return false;
+    }
+
+    let length = |value: Option<&str>| {
+        let value = value?.trim().trim_end_matches("px");
+        value.parse::<f64>().ok()
+    };
+    let view_box: Vec<f64> = elem
+        .attr("viewBox")
+        .unwrap_or("")
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter_map(|value| value.parse().ok())
+        .collect();
+    let width = length(elem.attr("width")).or(view_box.get(2).copied());
+    let height = length(elem.attr("height")).or(view_box.get(3).copied());
+    let is_icon_sized = match (width, height) {
+        (Some(width), Some(height)) => width.max(height) <= ICON_SVG_MAX_SIZE,
+        (Some(size), None) | (None, Some(size)) => size <= ICON_SVG_MAX_SIZE,
+        (None, None) => false,
+    };
+
+    elem.attr("aria-hidden") == Some("true")
+        || is_icon_sized
+        || svg
+            .ancestors()
+            .filter_map(ElementRef::wrap)
+            .any(|ancestor| matches!(ancestor.value().name(), "a" | "button"))
+}
+
+/// Remove the "Advertisement" and "Loading…" labels left where an ad was
+///
+/// Removes elements whose whole text matches `REGEXPS.ad_words` or
+/// `REGEXPS.loading_words` and that stand on their own, i.e. are not set in a
+/// line of other text. Headings, list items and table cells are kept, as are
+/// elements holding media.
+pub fn remove_ad_labels(html: &str) -> String {
+    use crate::constants::REGEXPS;
+    static MEDIA_SELECTOR: Lazy<Selector> =
+        Lazy::new(|| Selector::parse("img, picture, video, audio, iframe, object, embed").unwrap());
+
+    remove_elements_where(html, |el| {
+        if matches!(
+            el.value().name(),
+            "html" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "li" | "td" | "th" | "option"
+        ) {
+            return false;
+        }
+        let text = el.text().collect::<String>();
+        let text = text.trim();
+        (REGEXPS.ad_words.is_match(text) || REGEXPS.loading_words.is_match(text))
+            && el.select(&MEDIA_SELECTOR).next().is_none()
+            && stands_alone(el)
+    })
+}
+
+/// Whether `element` is not set in a line of other text
+///
+/// True when neither of its nearest siblings, skipping whitespace and
+/// comments, is text or an inline element holding text.
+fn stands_alone(element: ElementRef) -> bool {
+    use scraper::node::Node;
+
+    let is_inline_text = |node: ego_tree::NodeRef<Node>| match node.value() {
+        Node::Text(text) => !text.trim().is_empty(),
+        Node::Element(_) => ElementRef::wrap(node).is_some_and(|el| {
+            crate::dom_utils::is_phrasing_content(el)
+                && !el.text().collect::<String>().trim().is_empty()
+        }),
+        _ => false,
+    };
+    let is_blank = |node: &ego_tree::NodeRef<Node>| match node.value() {
+        Node::Text(text) => text.trim().is_empty(),
+        Node::Comment(_) => true,
+        _ => false,
+    };
+
+    let previous = element.prev_siblings().find(|node| !is_blank(node));
+    let next = element.next_siblings().find(|node| !is_blank(node));
+    !previous.is_some_and(is_inline_text) && !next.is_some_and(is_inline_text)
+}
+
+/// Remove the byline from the content
+///
+/// The first element that [`is_valid_byline`](crate::scoring::is_valid_byline)
+/// accepts and whose text contains `byline` is removed, together with
+/// wrappers holding nothing else, so the author shown with the article is not
+/// repeated in its body. A byline set in a line of other text, such as
+/// `By Jane Doe · May 1`, is kept.
+pub fn remove_byline(html: &str, byline: &str) -> String {
+    let byline = crate::utils::normalize_whitespace(byline.trim()).to_lowercase();
+    if byline.is_empty() {
+        return html.to_string();
+    }
+    let text_of = |element: ElementRef| {
+        crate::utils::normalize_whitespace(element.text().collect::<String>().trim())
+    };
+
+    let mut doc = Html::parse_fragment(html);
+    let root_id = doc.root_element().id();
+    let target = doc
+        .root_element()
+        .descendants()
+        .skip(1)
+        .filter_map(ElementRef::wrap)
+        .find(|el| {
+            let match_string = format!(
+                "{} {}",
+                el.value().attr("class").unwrap_or(""),
+                el.value().id().unwrap_or("")
+            );
+            crate::scoring::is_valid_byline(*el, &match_string)
+                && text_of(*el).to_lowercase().contains(&byline)
+        })
+        .map(|el| {
+            let text = text_of(el);
+            let mut target = el;
+            while let Some(parent) = target.parent().and_then(ElementRef::wrap) {
+                if parent.id() == root_id || text_of(parent) != text {
+                    break;
+                }
+                target = parent;
+            }
+            target
+        })
+        .filter(|target| stands_alone(*target))
+        .map(|target| target.id());
+
+    let Some(id) = target else {
+        return html.to_string();
+    };
+    if let Some(mut node) = doc.tree.get_mut(id) {
+        node.detach();
+    }
+    doc.root_element().inner_html()
+}
+
+/// List the images and videos in the content
+///
+/// Walks `<img>`, `<video>` and `<iframe>` elements in document order. Video
+/// sources fall back to the first `<source src>` child. Sources are resolved
+/// against `base_url`, captions come from the enclosing `<figure>`'s
+/// `<figcaption>`, and dimensions from the `width`/`height` attributes.
+/// Elements without a source are skipped.
+pub fn collect_media(html: &str, base_url: Option<&str>) -> Vec<MediaItem> {
+    static MEDIA_SELECTOR: Lazy<Selector> =
+        Lazy::new(|| Selector::parse("img, video, iframe").unwrap());
+    static SOURCE_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("source[src]").unwrap());
+    static FIGCAPTION_SELECTOR: Lazy<Selector> =
+        Lazy::new(|| Selector::parse("figcaption").unwrap());
+
+    let base = base_url.and_then(|url| url::Url::parse(url).ok());
+    let doc = Html::parse_fragment(html);
+    let dimension = |element: ElementRef, name: &str| {
+        element
+            .value()
+            .attr(name)
+            .and_then(|value| value.trim().trim_end_matches("px").parse::<u32>().ok())
+    };
+
+    doc.select(&MEDIA_SELECTOR)
+        .filter_map(|element| {
+            let src = element.value().attr("src").or_else(|| {
+                element
+                    .select(&SOURCE_SELECTOR)
+                    .next()
+                    .and_then(|source| source.value().attr("src"))
+            });
+            let src = src.map(str::trim).filter(|src| !src.is_empty())?;
+            let src = match &base {
+                Some(base) => base
+                    .join(src)
+                    .map(|url| url.to_string())
+                    .unwrap_or_else(|_| src.to_string()),
+                None => src.to_string(),
+            };
+
+            let caption = element
+                .ancestors()
+                .filter_map(ElementRef::wrap)
+                .find(|ancestor| ancestor.value().name() == "figure")
+                .and_then(|figure| figure.select(&FIGCAPTION_SELECTOR).next())
+                .map(|caption| crate::dom_utils::get_inner_text(caption, true))
+                .filter(|caption| !caption.is_empty());
+
+            Some(MediaItem {
+                kind: if element.value().name() == "img" {
+                    MediaKind::Image
+                } else {
+                    MediaKind::Video
+                },
+                src,
+                alt: element
+                    .value()
+                    .attr("alt")
+                    .map(|alt| alt.trim().to_string()),
+                caption,
+                width: dimension(element, "width"),
+                height: dimension(element, "height"),
+            })
+        })
+        .collect()
+}
+
 /// Clean inline styles from HTML elements
 ///
 /// This implements Mozilla's _cleanStyles() function which removes the `style`
@@ -221,6 +1541,9 @@ pub fn prep_article(html: &str, clean_styles_opt: bool, clean_whitespace_opt: bo
 ///
 /// Presentational attributes removed: style, align, background, bgcolor, border,
 /// cellpadding, cellspacing, frame, hspace, rules, valign, vspace
+///
+/// Accessibility attributes (`role`, `aria-*`) are kept; `aria-hidden` content is
+/// already dropped by the visibility checks during extraction.
 fn clean_styles(html: &str) -> String {
     // Simple and fast: just remove style attributes with pre-compiled regexes
     static STYLE_DOUBLE: Lazy<Regex> =
@@ -248,24 +1571,49 @@ fn clean_styles(html: &str) -> String {
 /// This function:
 /// - Removes excessive blank lines (more than 2 consecutive newlines)
 /// - Collapses multiple spaces into single spaces
-fn normalize_whitespace(html: &str) -> String {
+///
+/// Elements named in `preserve_tags` are copied unchanged.
+fn normalize_whitespace(html: &str, preserve_tags: &[String]) -> String {
     // Multiple consecutive newlines -> 2 newlines (fast single pass)
     static MULTI_NEWLINE: Lazy<Regex> =
         Lazy::new(|| Regex::new(r"\n{3,}").unwrap());
     // Multiple spaces -> single space
     static MULTI_SPACE: Lazy<Regex> =
         Lazy::new(|| Regex::new(r" {2,}").unwrap());
+    let normalize = |text: &str| {
+        let result = MULTI_NEWLINE.replace_all(text, "\n\n");
+        MULTI_SPACE.replace_all(&result, " ").into_owned()
+    };
 
-    let result = MULTI_NEWLINE.replace_all(html, "\n\n");
-    let result = MULTI_SPACE.replace_all(&result, " ");
-    result.to_string()
+    if preserve_tags.is_empty() {
+        return normalize(html);
+    }
+    let preserved = preserve_tags
+        .iter()
+        .map(|tag| {
+            let tag = regex::escape(tag);
+            format!(r"<{tag}\b[^>]*>.*?</{tag}\s*>")
+        })
+        .collect::<Vec<_>>();
+    let preserved_regex = Regex::new(&format!("(?is){}", preserved.join("|"))).unwrap();
+
+    let mut result = String::with_capacity(html.len());
+    let mut last = 0;
+    for element in preserved_regex.find_iter(html) {
+        result.push_str(&normalize(&html[last..element.start()]));
+        result.push_str(element.as_str());
+        last = element.end();
+    }
+    result.push_str(&normalize(&html[last..]));
+    result
 }
 
 /// Remove unwanted elements that are never part of article content
 ///
 /// Removes: forms, fieldsets, footer, aside, object, embed, iframe,
-/// input, textarea, select, button
-fn remove_unwanted_elements(html: &str) -> String {
+/// input, textarea, select, button. Objects, embeds and iframes pointing at an
+/// allowed video host (per `video_regex`) are kept, as in Mozilla's _clean.
+fn remove_unwanted_elements(html: &str, video_regex: &Regex) -> String {
     let mut result = html.to_string();
     let tags = vec![
         ("form", r"(?is)<form\b[^>]*?>.*?</form>"),
@@ -288,72 +1636,149 @@ fn remove_unwanted_elements(html: &str) -> String {
         ("link", r"(?is)<link\b[^>]*?>.*?</link>|<link\b[^>]*?/?>"),
     ];
 
-    for (_name, pattern) in tags {
+    for (name, pattern) in tags {
         let re = Regex::new(pattern).unwrap();
-        result = re.replace_all(&result, "").to_string();
+        let is_embed = matches!(name, "object" | "embed" | "iframe");
+        result = re
+            .replace_all(&result, |caps: &regex::Captures| {
+                // Serialized attribute values escape `/` as `&#x2f;`.
+                if is_embed && video_regex.is_match(&caps[0].replace("&#x2f;", "/")) {
+                    caps[0].to_string()
+                } else {
+                    String::new()
+                }
+            })
+            .to_string();
     }
 
     result
 }
 
+/// Replace non-video iframes with a link to their source
+///
+/// `remove_unwanted_elements` drops iframes that aren't allowed videos, which
+/// loses embedded maps and code playgrounds without a trace. Each such iframe
+/// with a `src` becomes `<p><a href="...">Embedded content</a></p>`, resolved
+/// against `base_url`; iframes without a source are left for removal. Only
+/// `http` and `https` sources are linked, and iframes pointing anywhere else
+/// (`javascript:`, `data:`, ...) are dropped. A relative source is kept as
+/// written when there is no base URL to resolve it against.
+pub fn replace_iframes_with_links(
+    html: &str,
+    base_url: Option<&str>,
+    video_regex: &Regex,
+) -> String {
+    static IFRAME_REGEX: Lazy<Regex> = Lazy::new(|| {
+        Regex::new(r#"(?is)<iframe\b((?:[^>"']|"[^"]*"|'[^']*')*)>.*?</iframe>"#).unwrap()
+    });
+    static SRC_ATTR_REGEX: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r#"(?i)\ssrc\s*=\s*(?:"([^"]*)"|'([^']*)')"#).unwrap());
+
+    let base = base_url.and_then(|url| url::Url::parse(url).ok());
+    IFRAME_REGEX
+        .replace_all(html, |caps: &regex::Captures| {
+            let src = SRC_ATTR_REGEX
+                .captures(&caps[1])
+                .and_then(|src| src.get(1).or_else(|| src.get(2)))
+                .map(|src| crate::utils::unescape_html_entities(src.as_str().trim()))
+                .filter(|src| !src.is_empty());
+            let Some(src) = src else {
+                return caps[0].to_string();
+            };
+            if video_regex.is_match(&src) {
+                return caps[0].to_string();
+            }
+
+            let resolved = match &base {
+                Some(base) => base.join(&src),
+                None => url::Url::parse(&src),
+            };
+            let href = match resolved {
+                Ok(url) if matches!(url.scheme(), "http" | "https") => url.to_string(),
+                Err(url::ParseError::RelativeUrlWithoutBase) => src,
+                _ => return String::new(),
+            };
+            format!(
+                r#"<p><a href="{}">Embedded content</a></p>"#,
+                href.replace('&', "&amp;").replace('"', "&quot;")
+            )
+        })
+        .to_string()
+}
+
 /// Remove share buttons and social widgets
 ///
 /// Removes elements with "share" or "social" in their class/id
 fn remove_share_elements(html: &str) -> String {
-    let mut result = html.to_string();
-    let tags = vec!["div", "span", "aside", "section"];
-    let keywords = vec!["share", "social", "sharedaddy"];
-
-    for tag in &tags {
-        for keyword in &keywords {
-            let class_pattern =
-                format!(r#"(?is)<{tag}\b[^>]*?class="[^"]*?{keyword}[^"]*?"[^>]*?>.*?</{tag}>"#);
-            let re = Regex::new(&class_pattern).unwrap();
-            result = re.replace_all(&result, "").to_string();
-
-            let id_pattern =
-                format!(r#"(?is)<{tag}\b[^>]*?id="[^"]*?{keyword}[^"]*?"[^>]*?>.*?</{tag}>"#);
-            let re = Regex::new(&id_pattern).unwrap();
-            result = re.replace_all(&result, "").to_string();
-        }
-    }
-
-    result
+    const TAGS: [&str; 4] = ["div", "span", "aside", "section"];
+    const KEYWORDS: [&str; 3] = ["share", "social", "sharedaddy"];
+
+    remove_elements_where(html, |el| {
+        let elem = el.value();
+        TAGS.contains(&elem.name())
+            && (attr_contains_any(elem.attr("class"), &KEYWORDS)
+                || attr_contains_any(elem.id(), &KEYWORDS))
+    })
 }
 
 /// Remove navigation lists and menu sections
 fn remove_navigation_elements(html: &str) -> String {
-    let mut result = html.to_string();
-
-    static NAV_REGEX: Lazy<Regex> =
-        Lazy::new(|| Regex::new(r"(?is)<nav\b[^>]*?>.*?</nav>").unwrap());
-    result = NAV_REGEX.replace_all(&result, "").to_string();
-
-    let tags = vec!["div", "section", "ul", "ol"];
-    let keywords = vec!["nav", "navbar", "menu", "breadcrumbs"];
+    const TAGS: [&str; 4] = ["div", "section", "ul", "ol"];
+    const KEYWORDS: [&str; 4] = ["nav", "navbar", "menu", "breadcrumbs"];
+
+    remove_elements_where(html, |el| {
+        let elem = el.value();
+        elem.name() == "nav"
+            || (TAGS.contains(&elem.name())
+                && (attr_contains_any(elem.attr("class"), &KEYWORDS)
+                    || attr_contains_any(elem.id(), &KEYWORDS)))
+    })
+}
 
-    for tag in &tags {
-        for keyword in &keywords {
-            let class_pattern =
-                format!(r#"(?is)<{tag}\b[^>]*?class="[^"]*?{keyword}[^"]*?"[^>]*?>.*?</{tag}>"#);
-            let re = Regex::new(&class_pattern).unwrap();
-            result = re.replace_all(&result, "").to_string();
+/// Fragment ids that in-content `<a href="#id">` links point at
+fn linked_fragment_ids(html: &str) -> HashSet<String> {
+    static FRAGMENT_HREF_REGEX: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r##"(?i)<a\b[^>]*?\shref=(?:"#([^"]+)"|'#([^']+)')"##).unwrap());
+    FRAGMENT_HREF_REGEX
+        .captures_iter(html)
+        .filter_map(|caps| caps.get(1).or_else(|| caps.get(2)))
+        .map(|id| id.as_str().to_string())
+        .collect()
+}
 
-            let id_pattern =
-                format!(r#"(?is)<{tag}\b[^>]*?id="[^"]*?{keyword}[^"]*?"[^>]*?>.*?</{tag}>"#);
-            let re = Regex::new(&id_pattern).unwrap();
-            result = re.replace_all(&result, "").to_string();
-        }
+/// Remove the matches of `regex`, keeping those that carry a linked id
+///
+/// An empty element can still be the target of a footnote or other in-page
+/// link; removing it would leave the link pointing nowhere.
+fn remove_unlinked(pattern: &Regex, html: &str, linked_ids: &HashSet<String>) -> String {
+    static ID_ATTR_REGEX: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r#"\sid=(?:"([^"]*)"|'([^']*)')"#).unwrap());
+    if linked_ids.is_empty() {
+        return pattern.replace_all(html, "").to_string();
     }
-
-    result
+    pattern
+        .replace_all(html, |caps: &regex::Captures| {
+            let linked = ID_ATTR_REGEX
+                .captures_iter(&caps[0])
+                .filter_map(|caps| caps.get(1).or_else(|| caps.get(2)))
+                .any(|id| linked_ids.contains(id.as_str()));
+            if linked {
+                caps[0].to_string()
+            } else {
+                String::new()
+            }
+        })
+        .to_string()
 }
 
 /// Remove empty paragraphs (paragraphs with no text and no media elements)
 fn remove_empty_paragraphs(html: &str) -> String {
     // Match empty paragraphs - with no content or only whitespace/br tags
-    static EMPTY_P_REGEX: Lazy<Regex> =
-        Lazy::new(|| Regex::new(r"(?i)<p[^>]*>(\s*(<br\s*/?>)?\s*)*</p>").unwrap());
+    // (`&nbsp;` counts as whitespace here, as it is never content on its own)
+    static EMPTY_P_REGEX: Lazy<Regex> = Lazy::new(|| {
+        Regex::new(r"(?i)<p[^>]*>((?:\s|&nbsp;|&#160;|&#xa0;)*(<br\s*/?>)?(?:\s|&nbsp;|&#160;|&#xa0;)*)*</p>")
+            .unwrap()
+    });
 
     // Match paragraphs that contain only <span></span> or similar empty inline elements
     static EMPTY_SPAN_P_REGEX: Lazy<Regex> =
@@ -367,14 +1792,15 @@ fn remove_empty_paragraphs(html: &str) -> String {
     static ORPHAN_BR_REGEX: Lazy<Regex> =
         Lazy::new(|| Regex::new(r"(?i)(</(?:p|div|h[1-6])>)\s*(?:<br\s*/?>[\s\n]*)+\s*(<(?:p|div|h[1-6]))").unwrap());
 
+    let linked_ids = linked_fragment_ids(html);
     let mut html = html.to_string();
 
     // Remove empty paragraphs (iterate to handle nested cases)
     for _ in 0..5 {
         let prev = html.clone();
-        html = EMPTY_P_REGEX.replace_all(&html, "").to_string();
-        html = EMPTY_SPAN_P_REGEX.replace_all(&html, "").to_string();
-        html = BR_SPAN_P_REGEX.replace_all(&html, "").to_string();
+        html = remove_unlinked(&EMPTY_P_REGEX, &html, &linked_ids);
+        html = remove_unlinked(&EMPTY_SPAN_P_REGEX, &html, &linked_ids);
+        html = remove_unlinked(&BR_SPAN_P_REGEX, &html, &linked_ids);
         if html == prev {
             break;
         }
@@ -389,6 +1815,7 @@ fn remove_empty_paragraphs(html: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::constants::REGEXPS;
 
     #[test]
     fn test_remove_unwanted_elements() {
@@ -401,7 +1828,7 @@ mod tests {
             </article>
         "#;
 
-        let cleaned = remove_unwanted_elements(html);
+        let cleaned = remove_unwanted_elements(html, &REGEXPS.videos);
 
         assert!(cleaned.contains("<h1>Title</h1>"));
         assert!(cleaned.contains("<p>Content</p>"));
@@ -409,6 +1836,293 @@ mod tests {
         assert!(!cleaned.contains("<form"));
     }
 
+    #[test]
+    fn test_remove_unwanted_elements_keeps_video_embeds() {
+        let html = r#"
+            <article>
+                <p>Content</p>
+                <iframe src="https://fast.wistia.net/embed/iframe/abc123"></iframe>
+                <iframe src="https://w.soundcloud.com/player/?url=https%3A//api.soundcloud.com/tracks/1"></iframe>
+                <iframe src="https://ads.example.com/banner"></iframe>
+            </article>
+        "#;
+
+        let cleaned = remove_unwanted_elements(html, &REGEXPS.videos);
+
+        assert!(cleaned.contains("fast.wistia.net/embed/iframe/abc123"));
+        assert!(cleaned.contains("w.soundcloud.com/player"));
+        assert!(!cleaned.contains("ads.example.com"));
+    }
+
+    #[test]
+    fn test_remove_empty_wrappers() {
+        let html = "<div><p>Text</p><div class=\"ad-slot\"><div> <!-- ad --> </div>\n</div>\
+                    <section><div>&nbsp;</div></section><figure><img src=\"a.jpg\"></figure>\
+                    <div><br></div></div>";
+        assert_eq!(
+            remove_empty_wrappers(html),
+            "<div><p>Text</p><figure><img src=\"a.jpg\"></figure><div><br></div></div>"
+        );
+    }
+
+    #[test]
+    fn test_remove_empty_wrappers_stops_at_comment_end() {
+        let html = "<div><!-- a --><p>Text</p><!-- b --></div>";
+        assert_eq!(remove_empty_wrappers(html), html);
+    }
+
+    #[test]
+    fn test_remove_footnote_backrefs() {
+        let html = r##"<p>Basil bolts in heat.<sup id="ref1"><a href="#fn1">1</a></sup> See <a href="#care">back</a> for care.</p>
+<ol class="references">
+<li id="cite_note-1"><span class="mw-cite-backlink"><b><a href="#cite_ref-1">^</a></b></span> <span class="reference-text">Smith, Herbs, p. 12.</span></li>
+<li id="fn1"><p>Above 30 degrees. <a href="#ref1" class="footnote-backref">↩︎</a></p></li>
+<li id="fn2"><p>See <a href="https://example.com/top">↑</a> the source.</p></li>
+</ol>"##;
+        let result = remove_footnote_backrefs(html);
+
+        assert!(!result.contains("mw-cite-backlink"));
+        assert!(!result.contains("cite_ref-1"));
+        assert!(!result.contains("footnote-backref"));
+        assert!(result.contains(r#"<span class="reference-text">Smith, Herbs, p. 12.</span>"#));
+        assert!(result.contains("<p>Above 30 degrees. </p>"));
+        // Footnote references and other links stay.
+        assert!(result.contains(r##"<a href="#fn1">1</a>"##));
+        assert!(result.contains(r##"<a href="#care">back</a>"##));
+        assert!(result.contains(r#"<a href="https://example.com/top">↑</a>"#));
+    }
+
+    #[test]
+    fn test_strip_image_credits() {
+        let figure = |caption: &str| {
+            format!(r#"<figure><img src="a.jpg"><figcaption>{caption}</figcaption></figure>"#)
+        };
+        let heron = "A heron at dawn on the estuary.";
+        for credit in [
+            " Photo: Getty Images / Jane Doe",
+            " | Credit: Jane Doe",
+            " (Photo by Jane Doe)",
+            " © 2024 Jane Doe",
+            r#" <em>Image:</em> <a href="/jane">Jane Doe</a>"#,
+            r#" <span class="image-credit">Jane Doe</span>"#,
+        ] {
+            let html = figure(&format!("{heron}{credit}"));
+            assert_eq!(strip_image_credits(&html), figure(heron));
+        }
+
+        // Captions that are only a credit are removed; plain captions stay.
+        assert_eq!(
+            strip_image_credits(&figure("Photo: Jane Doe")),
+            r#"<figure><img src="a.jpg"></figure>"#
+        );
+        let plain = figure("Herons photographed by drone");
+        assert_eq!(strip_image_credits(&plain), plain);
+    }
+
+    #[test]
+    fn test_remove_standalone_captions() {
+        let html = "<figure>\n<figcaption>Map of the heronry</figcaption>\n</figure>\
+                    <figure><blockquote><p>Quote</p></blockquote><figcaption>Author</figcaption></figure>\
+                    <figure><img src=\"a.jpg\"><figcaption>Heron</figcaption></figure>";
+        assert_eq!(
+            remove_standalone_captions(html),
+            "<figure><blockquote><p>Quote</p></blockquote><figcaption>Author</figcaption></figure>\
+             <figure><img src=\"a.jpg\"><figcaption>Heron</figcaption></figure>"
+        );
+    }
+
+    #[test]
+    fn test_convert_scene_breaks() {
+        let html = "<p>One.</p><p>* * *</p><p>Two.</p><p class=\"break\"><span>⁂</span></p>\
+                    <p> &#183; &#183; &#183; </p><p>Three *</p><p>*<img src=\"star.png\"></p>";
+        assert_eq!(
+            convert_scene_breaks(html),
+            "<p>One.</p><hr><p>Two.</p><hr><hr><p>Three *</p><p>*<img src=\"star.png\"></p>"
+        );
+    }
+
+    #[test]
+    fn test_remove_emoji_paragraphs() {
+        let html = "<p>👏👏👏</p><p>Great 👏</p><p> <strong>❤️</strong> 🔥 </p>\
+                    <p>👍🏽</p><p>👨‍👩‍👧</p><p>:)</p><p>\u{fe0f}</p>";
+        assert_eq!(
+            remove_emoji_paragraphs(html),
+            "<p>Great 👏</p><p>:)</p><p>\u{fe0f}</p>"
+        );
+    }
+
+    #[test]
+    fn test_flatten_wrappers() {
+        let html = r#"<div id="app"><div class="layout"><div><div class="row"><div class="col body"><p>One</p><p>Two</p></div></div></div></div></div><div><p>Three</p></div>"#;
+        assert_eq!(
+            flatten_wrappers(html),
+            r#"<div class="layout row col body" id="app"><p>One</p><p>Two</p></div><div><p>Three</p></div>"#
+        );
+
+        let inner_id = r#"<div id="outer"><div id="inner"><p>Text</p></div></div>"#;
+        assert_eq!(
+            flatten_wrappers(inner_id),
+            r#"<div id="inner"><p>Text</p></div>"#
+        );
+
+        let with_text = "<div>Note <div><p>Text</p></div></div>";
+        assert_eq!(flatten_wrappers(with_text), with_text);
+    }
+
+    #[test]
+    fn test_make_leading_images_eager() {
+        let html = r#"<img alt="Bed" decoding="async" loading="lazy" sizes="50vw" src="a.jpg"><p>Text</p><IMG src="b.jpg" decoding=async><img loading="lazy" src="c.jpg">"#;
+        assert_eq!(
+            make_leading_images_eager(html, 2),
+            r#"<img loading="eager" alt="Bed" sizes="50vw" src="a.jpg"><p>Text</p><img loading="eager" src="b.jpg"><img loading="lazy" src="c.jpg">"#
+        );
+        assert_eq!(make_leading_images_eager(html, 0), html);
+    }
+
+    #[test]
+    fn test_remove_documentation_chrome() {
+        let html = r##"<div class="theme-doc-toc-mobile"><a href="#a">A</a></div><ul id="table-of-contents"><li>A</li></ul><p class="tocless">Text</p><div class="pagination-nav"><a href="/next">Next</a></div><div class="docs-content"><p>Body</p></div>"##;
+        assert_eq!(
+            remove_documentation_chrome(html),
+            r#"<p class="tocless">Text</p><div class="docs-content"><p>Body</p></div>"#
+        );
+    }
+
+    #[test]
+    fn test_open_details() {
+        let html = r#"<details class="faq"><summary>Is it free?</summary><p>Yes.</p></details><details open="" class="plot-spoiler"><summary>Ending</summary><p>He returns.</p></details><details><summary> <b>Spoilers</b> ahead</summary><p>She leaves.</p></details><details open><p>No summary.</p></details>"#;
+        assert_eq!(
+            open_details(html),
+            r#"<details class="faq" open><summary>Is it free?</summary><p>Yes.</p></details><details class="plot-spoiler" data-spoiler><summary>Ending</summary><p>He returns.</p></details><details data-spoiler><summary> <b>Spoilers</b> ahead</summary><p>She leaves.</p></details><details open><p>No summary.</p></details>"#
+        );
+        assert_eq!(open_details("<p>Text</p>"), "<p>Text</p>");
+    }
+
+    #[test]
+    fn test_remove_svgs() {
+        let html = r#"<p>Text <svg viewBox="0 0 16 16"><path d="M0 0"></path></svg></p><p><a href="/share"><svg viewBox="0 0 600 600"></svg>Share</a></p><svg aria-hidden="true" width="100%" viewBox="0 0 800 400"></svg><svg viewBox="0 0 800 400"><rect height="400" width="800"></rect></svg><svg height="20" width="20"><title>Heron</title></svg>"#;
+        assert_eq!(
+            remove_svgs(html, SvgPolicy::StripDecorative),
+            r#"<p>Text </p><p><a href="/share">Share</a></p><svg viewBox="0 0 800 400"><rect height="400" width="800"></rect></svg><svg height="20" width="20"><title>Heron</title></svg>"#
+        );
+        assert_eq!(remove_svgs(html, SvgPolicy::KeepAll), html);
+        assert!(!remove_svgs(html, SvgPolicy::StripAll).contains("<svg"));
+    }
+
+    #[test]
+    fn test_remove_ad_labels() {
+        let html = r#"<p>Herons hunt at dawn.</p><p>Advertisement</p><div><span>ADVERTISEMENT</span></div><p>They stand still.</p><span>Loading...</span><p>Read about the <em>ad</em> campaign.</p><h2>Advertising</h2><p>Ad <img src="ad.png"></p>"#;
+        assert_eq!(
+            remove_ad_labels(html),
+            r#"<p>Herons hunt at dawn.</p><p>They stand still.</p><p>Read about the <em>ad</em> campaign.</p><h2>Advertising</h2><p>Ad <img src="ad.png"></p>"#
+        );
+    }
+
+    #[test]
+    fn test_merge_split_lists() {
+        let html = r#"<ul><li>Herons</li></ul>
+<!-- ad --><ul><li>Egrets</li></ul><ol><li>Wait</li><li>Strike</li></ol><ol start="3"><li>Swallow</li></ol><ol><li>Again</li></ol><p>Text</p><ul><li>Bitterns</li></ul>"#;
+        assert_eq!(
+            merge_split_lists(html),
+            r#"<ul><li>Herons</li><li>Egrets</li></ul>
+<!-- ad --><ol><li>Wait</li><li>Strike</li><li>Swallow</li></ol><ol><li>Again</li></ol><p>Text</p><ul><li>Bitterns</li></ul>"#
+        );
+    }
+
+    #[test]
+    fn test_remove_byline() {
+        let html = r#"<h1>Herons</h1><div><p class="byline">By <a href="/jane">Jane Marsh</a></p></div><p>Grey herons hunt at dawn.</p>"#;
+        assert_eq!(
+            remove_byline(html, "Jane Marsh"),
+            "<h1>Herons</h1><p>Grey herons hunt at dawn.</p>"
+        );
+
+        // Bylines set in a line of other text, and other authors, are kept.
+        let inline = r#"<p>Story <span class="byline">by Jane Marsh</span> for the trust.</p>"#;
+        assert_eq!(remove_byline(inline, "Jane Marsh"), inline);
+        let other = r#"<p class="author-note">Kim Lee took the photos.</p>"#;
+        assert_eq!(remove_byline(other, "Jane Marsh"), other);
+    }
+
+    #[test]
+    fn test_collect_media() {
+        let html = r#"<figure><img src="/a.jpg" alt=" Raised bed " width="640" height="480px"><figcaption>Our  raised bed</figcaption></figure>
+<p><img src="b.jpg" alt=""><img data-src="lazy.jpg"><img src="c.jpg"></p>
+<video poster="p.jpg"><source src="clip.mp4" type="video/mp4"></video>
+<iframe src="https://www.youtube.com/embed/abc" width="560"></iframe>"#;
+        let media = collect_media(html, Some("https://example.com/notes/"));
+
+        assert_eq!(media.len(), 5);
+        assert_eq!(
+            media[0],
+            MediaItem {
+                kind: MediaKind::Image,
+                src: "https://example.com/a.jpg".to_string(),
+                alt: Some("Raised bed".to_string()),
+                caption: Some("Our raised bed".to_string()),
+                width: Some(640),
+                height: Some(480),
+            }
+        );
+        assert_eq!(media[1].src, "https://example.com/notes/b.jpg");
+        assert_eq!(media[1].alt.as_deref(), Some(""));
+        assert_eq!(media[2].alt, None);
+        assert_eq!(media[2].caption, None);
+        assert_eq!(media[3].kind, MediaKind::Video);
+        assert_eq!(media[3].src, "https://example.com/notes/clip.mp4");
+        assert_eq!(media[4].kind, MediaKind::Video);
+        assert_eq!(media[4].width, Some(560));
+
+        assert_eq!(collect_media(html, None)[1].src, "b.jpg");
+    }
+
+    #[test]
+    fn test_remove_empty_elements_keeps_link_targets() {
+        let html = r##"<p>Text<a href="#fn1">1</a></p><p id="fn1"><span></span></p><p id="x"> </p><div id="fn1-wrap"></div>"##;
+        assert_eq!(
+            remove_empty_paragraphs(html),
+            r##"<p>Text<a href="#fn1">1</a></p><p id="fn1"><span></span></p><div id="fn1-wrap"></div>"##
+        );
+        assert_eq!(
+            remove_empty_wrappers(
+                r##"<a href="#top">Top</a><div id="top"></div><div id="x"></div>"##
+            ),
+            r##"<a href="#top">Top</a><div id="top"></div>"##
+        );
+    }
+
+    #[test]
+    fn test_remove_unwanted_elements_keeps_escaped_video_embeds() {
+        let html = r#"<p>Content</p><iframe src="https:&#x2f;&#x2f;www.youtube.com&#x2f;embed&#x2f;abc" role="region" aria-label="Planting video"></iframe>"#;
+
+        let cleaned = remove_unwanted_elements(html, &REGEXPS.videos);
+        assert!(cleaned.contains(r#"role="region" aria-label="Planting video""#));
+    }
+
+    #[test]
+    fn test_replace_iframes_with_links() {
+        let html = r#"<iframe src='map.html?a=1&amp;b="2"'></iframe><iframe title="Empty"></iframe><iframe src="https://player.vimeo.com/video/1"></iframe>"#;
+        assert_eq!(
+            replace_iframes_with_links(html, None, &REGEXPS.videos),
+            r#"<p><a href="map.html?a=1&amp;b=&quot;2&quot;">Embedded content</a></p><iframe title="Empty"></iframe><iframe src="https://player.vimeo.com/video/1"></iframe>"#
+        );
+
+        let html = r#"<p>Map</p><iframe src="javascript:alert(1)"></iframe><iframe src="data:text/html,<p>x</p>"></iframe><iframe src="/maps/1"></iframe>"#;
+        assert_eq!(
+            replace_iframes_with_links(html, Some("https://example.com/a/"), &REGEXPS.videos),
+            r#"<p>Map</p><p><a href="https://example.com/maps/1">Embedded content</a></p>"#
+        );
+    }
+
+    #[test]
+    fn test_remove_unwanted_elements_custom_video_regex() {
+        let html = r#"<p>Content</p><iframe src="https://video.example.org/e/1"></iframe>"#;
+        let custom = Regex::new(r"(?i)//video\.example\.org").unwrap();
+
+        assert!(remove_unwanted_elements(html, &custom).contains("video.example.org"));
+        assert!(!remove_unwanted_elements(html, &REGEXPS.videos).contains("video.example.org"));
+    }
+
     #[test]
     fn test_remove_empty_paragraphs() {
         let html = r#"
@@ -428,6 +2142,12 @@ mod tests {
         assert!(!cleaned.contains("<p>   </p>"));
     }
 
+    #[test]
+    fn test_remove_nbsp_only_paragraphs() {
+        let html = "<p>Keep&nbsp;me</p><p>&nbsp;</p><p> &#160; <br> &nbsp;</p><p>\u{a0}</p>";
+        assert_eq!(remove_empty_paragraphs(html), "<p>Keep&nbsp;me</p>");
+    }
+
     #[test]
     fn test_remove_share_elements() {
         let html = r##"
@@ -471,6 +2191,34 @@ mod tests {
         assert!(!cleaned.contains("navbar"));
     }
 
+    #[test]
+    fn test_removal_passes_match_any_attribute_quoting() {
+        let html = r#"<div><p>Article content</p>
+            <div class='share-bar'><div><a href='#'>Share</a></div><span>Tweet</span></div>
+            <ul class=social-links><li>Follow</li></ul>
+            <div class="site&#x2d;menu"><a href="/">Home</a></div>
+            <ul class='nav'><li><a href='/'>Home</a></li></ul>
+            <p>More content</p></div>"#;
+
+        let cleaned = remove_navigation_elements(&remove_share_elements(html));
+
+        assert!(cleaned.contains("<p>Article content</p>"));
+        assert!(cleaned.contains("<p>More content</p>"));
+        assert!(!cleaned.contains("Share"));
+        assert!(!cleaned.contains("Tweet"));
+        assert!(!cleaned.contains("Home"));
+    }
+
+    #[test]
+    fn test_removal_passes_keep_article_sections() {
+        let paragraph = "<p>This section explains how shared history is rewritten safely.</p>";
+        let html = format!(
+            r#"<div><div class="section" id="sharing-history">{}</div></div>"#,
+            paragraph.repeat(10)
+        );
+        assert_eq!(remove_share_elements(&html), html);
+    }
+
     #[test]
     fn test_prep_article_full() {
         let html = r#"
@@ -485,7 +2233,7 @@ mod tests {
             </article>
         "#;
 
-        let cleaned = prep_article(html, true, true);
+        let cleaned = prep_article(html, true, true, &REGEXPS.videos, &[]);
 
         assert!(cleaned.contains("<h1>Article Title</h1>"));
         assert!(cleaned.contains("<p>First paragraph</p>"));
@@ -495,6 +2243,131 @@ mod tests {
         assert!(!cleaned.contains("<p></p>"));
     }
 
+    fn boilerplate_phrases() -> Vec<String> {
+        crate::constants::DEFAULT_BOILERPLATE_PHRASES
+            .iter()
+            .map(|phrase| phrase.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_strip_boilerplate_paragraphs() {
+        let html = r#"<div><p>Sign up for our newsletter to get the latest stories.</p><h1>Title</h1><p>The first real paragraph.</p><p>Readers who follow us on social media asked about this, so here is a longer paragraph that still counts as content.</p><p>The last real paragraph.</p><p>Follow us on Twitter!</p><p>© 2024 Example Media. All Rights Reserved.</p></div>"#;
+        let result = strip_boilerplate_paragraphs(html, &boilerplate_phrases());
+
+        assert!(!result.contains("Sign up for our newsletter"));
+        assert!(!result.contains("Follow us on Twitter"));
+        assert!(!result.contains("All Rights Reserved"));
+        assert!(result.contains("<h1>Title</h1>"));
+        assert!(result.contains("The first real paragraph."));
+        assert!(result.contains("Readers who follow us on social media"));
+        assert!(result.contains("The last real paragraph."));
+    }
+
+    #[test]
+    fn test_strip_boilerplate_paragraphs_keeps_middle_matches() {
+        let html = "<div><p>Intro.</p><p>Follow us on Mastodon.</p><p>Outro.</p></div>";
+        assert_eq!(strip_boilerplate_paragraphs(html, &boilerplate_phrases()), html);
+    }
+
+    #[test]
+    fn test_strip_boilerplate_paragraphs_custom_phrases() {
+        let html = "<div><p>Support our journalism today.</p><p>Body text.</p><p>Follow us on Twitter.</p></div>";
+        let result = strip_boilerplate_paragraphs(html, &["Support our journalism".to_string()]);
+        assert!(!result.contains("Support our journalism"));
+        assert!(result.contains("Body text."));
+        assert!(result.contains("Follow us on Twitter."));
+    }
+
+    #[test]
+    fn test_strip_leading_breadcrumbs() {
+        let body = "<h1>Raised beds</h1><p>The first real paragraph.</p>";
+        for crumbs in [
+            r#"<p><a href="/">Home</a> › <a href="/garden">Garden</a> › Raised beds</p>"#,
+            "<div>Home » Garden » Raised beds</div>",
+            "<p>Home / Garden / Vegetables / Raised beds</p>",
+            r#"<p><a href="/">Home</a> › Raised beds</p>"#,
+        ] {
+            let html = format!("<div>{crumbs}{body}</div>");
+            assert_eq!(
+                strip_leading_breadcrumbs(&html),
+                format!("<div>{body}</div>")
+            );
+        }
+
+        for kept in [
+            // Not at the very top.
+            "<div><h1>Raised beds</h1><p>Home › Garden › Raised beds</p></div>",
+            // Bylines and prose that happen to contain separators.
+            r#"<div><p>By <a href="/jane">Jane Doe</a> / May 1, 2024</p><p>Text.</p></div>"#,
+            "<div><p>Prices rose 3% year over year / the index fell / analysts were surprised by both moves this spring.</p></div>",
+            "<div><p>Home › Garden</p><p>Text.</p></div>",
+        ] {
+            assert_eq!(strip_leading_breadcrumbs(kept), kept);
+        }
+    }
+
+    #[test]
+    fn test_wrap_heading_sections() {
+        let html = "<h2>Setup</h2><p>Install it.</p><h2>Usage</h2><p>Run it.</p>";
+        assert_eq!(
+            wrap_heading_sections(html),
+            "<section><h2>Setup</h2><p>Install it.</p></section>\
+             <section><h2>Usage</h2><p>Run it.</p></section>"
+        );
+    }
+
+    #[test]
+    fn test_wrap_heading_sections_nests_lower_levels() {
+        let html = r#"<div class="page"><p>Intro.</p><h2>One</h2><p>A</p><h3>Detail</h3><p>B</p><h2>Two</h2><p>C</p></div>"#;
+        assert_eq!(
+            wrap_heading_sections(html),
+            "<div class=\"page\"><p>Intro.</p>\
+             <section><h2>One</h2><p>A</p><section><h3>Detail</h3><p>B</p></section></section>\
+             <section><h2>Two</h2><p>C</p></section></div>"
+        );
+    }
+
+    #[test]
+    fn test_wrap_heading_sections_is_idempotent() {
+        let html = "<h2>Setup</h2><p>Install &amp; run.</p><h3>Linux</h3><p>Use a &lt;shell&gt;.</p>\
+                    <h2>Usage</h2><p>Run it.</p>";
+        let once = wrap_heading_sections(html);
+        assert_eq!(once.matches("<section>").count(), 3);
+        assert_eq!(wrap_heading_sections(&once), once);
+
+        let sectioned = "<section><h2>Existing</h2><p>Already wrapped.</p></section>";
+        assert_eq!(wrap_heading_sections(sectioned), sectioned);
+    }
+
+    #[test]
+    fn test_remove_empty_sections() {
+        let html = "<h2>Watch</h2>\n<div> <br></div><!-- player --><h2>Aftermath</h2><p>Text.</p>\
+                    <h3>Detail</h3><p>More.</p><h2>Gallery</h2><h3>Photos</h3><p>Caption.</p>\
+                    <h3>Video</h3><h2>Reaction</h2><p>Quotes.</p><h4>Last</h4>";
+        assert_eq!(
+            remove_empty_sections(html),
+            "\n<div> <br></div><!-- player --><h2>Aftermath</h2><p>Text.</p>\
+             <h3>Detail</h3><p>More.</p><h2>Gallery</h2><h3>Photos</h3><p>Caption.</p>\
+             <h2>Reaction</h2><p>Quotes.</p><h4>Last</h4>"
+        );
+
+        let with_image = r#"<h2>Map</h2><div><img src="map.png"></div><h2>Route</h2>"#;
+        assert_eq!(remove_empty_sections(with_image), with_image);
+    }
+
+    #[test]
+    fn test_demote_extra_h1s() {
+        let html = r#"<h1>Article Title</h1><p>Intro</p><H1 class="section">Part two</H1><p>More</p><h1>Part three</h1>"#;
+        assert_eq!(
+            demote_extra_h1s(html),
+            r#"<h1>Article Title</h1><p>Intro</p><h2 class="section">Part two</h2><p>More</p><h2>Part three</h2>"#
+        );
+
+        let single = "<h1>Only</h1><h2>Sub</h2>";
+        assert_eq!(demote_extra_h1s(single), single);
+    }
+
     #[test]
     fn test_remove_title_from_content_h1() {
         let html = r#"
@@ -624,6 +2497,45 @@ mod tests {
         assert!(cleaned.contains("<p>Content</p>"));
     }
 
+    #[test]
+    fn test_promote_title_header_keeps_byline_and_date() {
+        let html = r#"<article>
+  <header class="entry-header">
+    <h1>Article Title</h1>
+    <span class="byline">By <a href="/author/jane">Jane Doe</a></span>
+    <time datetime="2024-05-01">May 1, 2024</time>
+  </header>
+  <p>Content</p>
+</article>"#;
+
+        let promoted = promote_title_header(html, "Article Title");
+        let cleaned = remove_title_from_content(&promoted, "Article Title");
+
+        assert!(!cleaned.contains("<header"));
+        assert!(!cleaned.contains("<h1>"));
+        let byline = cleaned.find(r#"<p><span class="byline">By <a href="/author/jane">Jane Doe</a></span>"#);
+        let date = cleaned.find(r#"<time datetime="2024-05-01">May 1, 2024</time></p>"#);
+        let content = cleaned.find("<p>Content</p>");
+        assert!(byline.is_some() && date.is_some() && content.is_some());
+        assert!(byline < date && date < content);
+    }
+
+    #[test]
+    fn test_promote_title_header_keeps_block_content() {
+        let html = r#"<article><header><h1>Article Title</h1><p class="meta">By Author</p></header><p>Content</p></article>"#;
+        let promoted = promote_title_header(html, "Article Title");
+        assert_eq!(
+            promoted,
+            r#"<article><h1>Article Title</h1><p class="meta">By Author</p><p>Content</p></article>"#
+        );
+    }
+
+    #[test]
+    fn test_promote_title_header_without_header() {
+        let html = "<article><h1>Article Title</h1><p>Content</p></article>";
+        assert_eq!(promote_title_header(html, "Article Title"), html);
+    }
+
     #[test]
     fn test_remove_title_preserves_header_with_other_content() {
         let html = r#"<article>
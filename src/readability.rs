@@ -27,16 +27,25 @@
 //! ```
 
 use crate::{
-    article::Article,
+    article::{Article, Recipe},
     cleaner,
-    content_extractor::grab_article,
+    constants::REGEXPS,
+    content_extractor::{
+        grab_article, grab_clean_fragment, root_extraction, Extraction, ExtractionSource,
+    },
     dom_utils,
     error::{ReadabilityError, Result},
-    metadata::{get_article_metadata, get_json_ld, Metadata},
-    options::ReadabilityOptions,
+    metadata::{self, Metadata},
+    options::{ContentProfile, ReadabilityOptions},
     utils,
 };
+use ego_tree::iter::Edge;
+use once_cell::sync::Lazy;
+use regex::Regex;
 use scraper::{ElementRef, Html, Selector};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::Arc;
 
 /// The main Readability parser.
 ///
@@ -131,7 +140,18 @@ impl Readability {
     pub fn new(html: &str, url: Option<&str>, options: Option<ReadabilityOptions>) -> Result<Self> {
         // Parse raw HTML for metadata extraction
         // Preprocessing happens later in parse() before content extraction
-        let document = Html::parse_document(html);
+        let mut html = Cow::Borrowed(html);
+        let mut document = Html::parse_document(&html);
+        if !cleaner::has_body_content(&document) && cleaner::has_text_outside_tags(&html) {
+            // Severely broken markup can leave the body empty; repair and retry,
+            // keeping the original parse if the repair does not help.
+            let sanitized = cleaner::sanitize_malformed_html(&html);
+            let repaired = Html::parse_document(&sanitized);
+            if cleaner::has_body_content(&repaired) {
+                document = repaired;
+                html = Cow::Owned(sanitized);
+            }
+        }
 
         // Validate base URL if provided
         let base_url = url
@@ -146,7 +166,7 @@ impl Readability {
 
         Ok(Self {
             document,
-            html: html.to_string(),
+            html: html.into_owned(),
             base_url,
             options,
             metadata: Metadata::default(),
@@ -157,95 +177,278 @@ impl Readability {
     ///
     /// # Returns
     /// `Option<Article>` - Some(article) if successful, None if no article found
-    pub fn parse(mut self) -> Option<Article> {
-        let json_ld = if !self.options.disable_json_ld {
-            get_json_ld(&self.document)
-        } else {
-            Metadata::default()
-        };
+    pub fn parse(self) -> Option<Article> {
+        self.parse_excluding(&[])
+    }
 
-        self.metadata = get_article_metadata(&self.document, json_ld);
+    /// Parse the document while skipping previously returned content
+    ///
+    /// `paths` are [`Article::candidate_path`] values from earlier parses of
+    /// the same HTML with the same options. The subtrees they point to are
+    /// masked before scoring, so the next-best candidate is selected instead.
+    /// Pass every path rejected so far to keep stepping down the ranking, as
+    /// in a "not that block, try again" loop. Paths that match nothing are
+    /// ignored.
+    ///
+    /// Each call runs the whole pipeline again, preprocessing and scoring
+    /// included; scores from earlier parses are not reused.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use readabilityrs::Readability;
+    ///
+    /// # let html = "<html></html>";
+    /// let first = Readability::new(html, None, None).unwrap().parse().unwrap();
+    /// let rejected = first.candidate_path.unwrap();
+    ///
+    /// let readability = Readability::new(html, None, None).unwrap();
+    /// let next = readability.parse_excluding(&[rejected.as_str()]);
+    /// ```
+    pub fn parse_excluding(mut self, paths: &[&str]) -> Option<Article> {
+        self.extract_metadata();
 
-        let preprocessed_html = cleaner::prep_document(&self.html);
-        let preprocessed_doc = Html::parse_document(&preprocessed_html);
+        dom_utils::with_text_normalizer(self.options.text_normalizer.as_ref(), || {
+            let mut preprocessed_doc = self.preprocess_document();
+            mask_paths(&mut preprocessed_doc, paths);
+            let extraction = self.grab_content(&preprocessed_doc)?;
+            Some(self.build_article(extraction))
+        })
+    }
 
-        match grab_article(&preprocessed_doc, &self.options) {
-            Ok(Some(content_html)) => {
-                let cleaned_wrapper_html =
-                    cleaner::clean_article_content_light(&content_html, self.base_url.as_deref())
-                        .unwrap_or_else(|_| content_html.clone());
+    /// Parse the document, taking the content from the element at `selector`
+    ///
+    /// For sites whose layout is known, such as one keeping its posts in
+    /// `article.post`. The first element matching the CSS `selector` in the
+    /// preprocessed document is used as the content without scoring
+    /// candidates, then goes through the same cleaning, URL fixing and image
+    /// normalization as scored content. When nothing matches, normal
+    /// extraction runs instead, unless `selector_fallback` is disabled.
+    ///
+    /// # Errors
+    /// - [`ReadabilityError::InvalidSelector`] if `selector` is not valid CSS.
+    /// - [`ReadabilityError::SelectorNotFound`] if nothing matches and
+    ///   `selector_fallback` is disabled.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use readabilityrs::Readability;
+    ///
+    /// let html = r#"<html><body><div class="teaser"><p>Subscribe today.</p></div>
+    /// <article class="post"><p>Herons nest in colonies high in the trees.</p></article></body></html>"#;
+    /// let readability = Readability::new(html, None, None).unwrap();
+    /// let article = readability.parse_within("article.post").unwrap().unwrap();
+    /// assert!(article.text_content.unwrap().contains("Herons nest"));
+    /// ```
+    pub fn parse_within(mut self, selector: &str) -> Result<Option<Article>> {
+        let root_selector = Selector::parse(selector)
+            .map_err(|_| ReadabilityError::InvalidSelector(selector.to_string()))?;
+        self.extract_metadata();
 
-                let mut prepped_html = crate::post_processor::prep_article(
-                    &cleaned_wrapper_html,
-                    self.options.clean_styles,
-                    self.options.clean_whitespace,
-                );
+        dom_utils::with_text_normalizer(self.options.text_normalizer.as_ref(), || {
+            let preprocessed_doc = self.preprocess_document();
+            let extraction = match preprocessed_doc.select(&root_selector).next() {
+                Some(root) => Some(root_extraction(root, &self.options)),
+                None if self.options.selector_fallback => self.grab_content(&preprocessed_doc),
+                None => return Err(ReadabilityError::SelectorNotFound(selector.to_string())),
+            };
+            Ok(extraction.map(|extraction| self.build_article(extraction)))
+        })
+    }
 
-                // Remove title from content if the option is enabled
-                if self.options.remove_title_from_content {
-                    if let Some(ref title) = self.metadata.title {
-                        prepped_html =
-                            crate::post_processor::remove_title_from_content(&prepped_html, title);
-                    }
-                }
-                let cleaned_html =
-                    match cleaner::clean_article_content(&prepped_html, self.base_url.as_deref()) {
-                        Ok(html) => html,
-                        Err(e) => {
-                            if self.options.debug {
-                                eprintln!("Error cleaning content: {e}");
-                            }
-                            prepped_html
-                        }
-                    };
+    /// Extract the metadata for a single parse
+    fn extract_metadata(&mut self) {
+        self.metadata = Metadata::from_document(
+            &self.document,
+            self.base_url.as_deref(),
+            !self.options.disable_json_ld,
+        );
+        self.infer_site_name();
+    }
 
-                let text_content = self.get_text_content(&cleaned_html);
-                let length = text_content.len();
-
-                // Generate excerpt from content if not in metadata
-                // Try first paragraph of extracted content, then fall back to text
-                let excerpt = self.metadata.excerpt.clone().or_else(|| {
-                    self.generate_excerpt_from_html(&cleaned_html)
-                        .or_else(|| self.generate_excerpt_from_text(&text_content))
-                });
-
-                // Extract text direction from document
-                let dir = crate::dom_utils::get_article_direction(&self.document);
-
-                // Optionally produce markdown output
-                let markdown_content = if self.options.output_markdown {
-                    let md_opts = self
-                        .options
-                        .markdown_options
-                        .as_ref()
-                        .cloned()
-                        .unwrap_or_default();
-                    let standardized = crate::elements::standardize_all(
-                        &cleaned_html,
-                        self.metadata.title.as_deref(),
-                    );
-                    Some(crate::markdown::html_to_markdown(&standardized, &md_opts))
-                } else {
-                    None
-                };
-
-                Some(Article {
-                    title: self.metadata.title,
-                    content: Some(cleaned_html),
-                    raw_content: Some(content_html),
-                    text_content: Some(text_content),
-                    length,
-                    excerpt,
-                    image: self.metadata.image,
-                    byline: self.metadata.byline,
-                    dir,
-                    site_name: self.metadata.site_name,
-                    lang: self.metadata.lang,
-                    published_time: self.metadata.published_time,
-                    markdown_content,
+    /// Parse the document once for each set of options
+    ///
+    /// Runs extraction for every entry of `variants` and returns each options
+    /// value paired with its result, in the same order. This is meant for
+    /// tuning heuristics: it is much cheaper than building one `Readability`
+    /// per variant because work that only depends on a subset of the options
+    /// is done once and reused.
+    ///
+    /// Shared across variants:
+    /// - Parsing the original HTML.
+    /// - Metadata extraction (once per `disable_json_ld` value).
+    /// - Document preprocessing (once per combination of `annotate_source`,
+    ///   `content_profile` and `remove_consent_banners`).
+    /// - Candidate scoring and content selection (once per combination of
+    ///   `char_threshold`, `nb_top_candidates`, `ancestor_score_depth`,
+    ///   `link_density_modifier`, `locale`, `retry_strategy`,
+    ///   `embedded_html_min_length`, `iframe_srcdoc_min_length`,
+    ///   `clean_fragment_max_elements`, `convert_brs`,
+    ///   `preserve_whitespace_tags`, `text_normalizer`, `content_profile`,
+    ///   `remove_consent_banners` and the article title, which depends on
+    ///   `disable_json_ld` and `infer_site_name`, on top of the preprocessing).
+    ///
+    /// Done per variant: post-processing and cleaning of the selected content
+    /// (styles, whitespace, boilerplate, title removal, video embeds), text and
+    /// excerpt generation, and markdown conversion.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use readabilityrs::{Readability, ReadabilityOptions};
+    ///
+    /// let html = "<html><body><article><p>Content...</p></article></body></html>";
+    /// let variants = vec![
+    ///     ReadabilityOptions::default(),
+    ///     ReadabilityOptions::builder().nb_top_candidates(10).build(),
+    /// ];
+    ///
+    /// let readability = Readability::new(html, None, None).unwrap();
+    /// for (options, article) in readability.parse_variants(variants) {
+    ///     println!("{}: {:?}", options.nb_top_candidates, article.map(|a| a.length));
+    /// }
+    /// ```
+    pub fn parse_variants(
+        mut self,
+        variants: Vec<ReadabilityOptions>,
+    ) -> Vec<(ReadabilityOptions, Option<Article>)> {
+        let mut metadata_cache: HashMap<bool, Metadata> = HashMap::new();
+        let mut document_cache: HashMap<(bool, ContentProfile, bool), Html> = HashMap::new();
+        let mut content_cache: HashMap<ExtractionKey, Option<Extraction>> = HashMap::new();
+        let mut results = Vec::with_capacity(variants.len());
+
+        for options in variants {
+            self.options = options;
+
+            self.metadata = metadata_cache
+                .entry(self.options.disable_json_ld)
+                .or_insert_with(|| {
+                    Metadata::from_document(
+                        &self.document,
+                        self.base_url.as_deref(),
+                        !self.options.disable_json_ld,
+                    )
                 })
+                .clone();
+            self.infer_site_name();
+
+            let preprocessed_doc = document_cache
+                .entry((
+                    self.options.annotate_source,
+                    self.options.content_profile,
+                    self.options.remove_consent_banners,
+                ))
+                .or_insert_with(|| self.preprocess_document());
+
+            let normalizer = self.options.text_normalizer.clone();
+            let article = dom_utils::with_text_normalizer(normalizer.as_ref(), || {
+                content_cache
+                    .entry(ExtractionKey::new(
+                        &self.options,
+                        self.metadata.title.as_deref(),
+                    ))
+                    .or_insert_with(|| self.grab_content(preprocessed_doc))
+                    .clone()
+                    .map(|extraction| self.build_article(extraction))
+            });
+            results.push((std::mem::take(&mut self.options), article));
+        }
+
+        results
+    }
+
+    /// Fill in a missing site name and strip it from the title, if enabled
+    fn infer_site_name(&mut self) {
+        if !self.options.infer_site_name {
+            return;
+        }
+
+        if self.metadata.site_name.is_none() {
+            self.metadata.site_name = metadata::infer_site_name(
+                &self.document,
+                self.base_url.as_deref(),
+                !self.options.disable_json_ld,
+            );
+        }
+        if let (Some(title), Some(site_name)) = (&self.metadata.title, &self.metadata.site_name) {
+            self.metadata.title = Some(metadata::strip_site_name_from_title(title, site_name));
+        }
+    }
+
+    /// Run the preprocessing passes and parse the result for content extraction
+    fn preprocess_document(&self) -> Html {
+        let preprocessed_html = if self.options.annotate_source {
+            cleaner::prep_document(&cleaner::annotate_source_lines(&self.html))
+        } else {
+            cleaner::prep_document(&self.html)
+        };
+        let mut preprocessed_html = cleaner::promote_layout_table_content(&preprocessed_html);
+        if self.options.content_profile == ContentProfile::News {
+            preprocessed_html = cleaner::remove_news_clutter(&preprocessed_html);
+        }
+        if self.options.remove_consent_banners {
+            preprocessed_html = cleaner::remove_consent_banners(&preprocessed_html);
+        }
+        Html::parse_document(&preprocessed_html)
+    }
+
+    /// Select the article content from a preprocessed document
+    ///
+    /// A small, clean fragment is taken whole when
+    /// `clean_fragment_max_elements` allows it. Falls back to HTML embedded in
+    /// script data or an iframe `srcdoc` when that is enabled and the document
+    /// yields less than `char_threshold` characters.
+    fn grab_content(&self, preprocessed_doc: &Html) -> Option<Extraction> {
+        static DOCUMENT_TAG_REGEX: Lazy<Regex> =
+            Lazy::new(|| Regex::new(r"(?i)<(?:html|head|body)\b").unwrap());
+        if self.options.clean_fragment_max_elements > 0 && !DOCUMENT_TAG_REGEX.is_match(&self.html)
+        {
+            let max_elements = self.options.clean_fragment_max_elements;
+            if let Some(extraction) = grab_clean_fragment(preprocessed_doc, max_elements) {
+                return Some(extraction);
             }
-            Ok(None) => None,
+        }
+
+        let extraction = self.grab_document_content(preprocessed_doc);
+        let embedded_min_length = self.options.embedded_html_min_length;
+        let srcdoc_min_length = self.options.iframe_srcdoc_min_length;
+        if embedded_min_length == 0 && srcdoc_min_length == 0 {
+            return extraction;
+        }
+
+        let length = extraction.as_ref().map_or(0, extraction_text_length);
+        if length >= self.options.char_threshold {
+            return extraction;
+        }
+        let embedded = (embedded_min_length > 0)
+            .then(|| crate::embedded::find_embedded_html(&self.document, embedded_min_length))
+            .flatten()
+            .map(|html| {
+                let document = format!("<html><body>{html}</body></html>");
+                (document, ExtractionSource::EmbeddedHtml)
+            });
+        let srcdoc = (srcdoc_min_length > 0)
+            .then(|| crate::embedded::find_iframe_srcdoc(&self.document, srcdoc_min_length))
+            .flatten()
+            .map(|document| (document, ExtractionSource::IframeSrcdoc));
+        [embedded, srcdoc]
+            .into_iter()
+            .flatten()
+            .filter_map(|(document, source)| self.grab_embedded_content(&document, source))
+            .filter(|embedded| extraction_text_length(embedded) > length)
+            .max_by_key(extraction_text_length)
+            .or(extraction)
+    }
+
+    /// Run content selection on a preprocessed document
+    fn grab_document_content(&self, preprocessed_doc: &Html) -> Option<Extraction> {
+        match grab_article(
+            preprocessed_doc,
+            &self.options,
+            self.metadata.title.as_deref(),
+        ) {
+            Ok(extraction) => extraction,
             Err(e) => {
                 if self.options.debug {
                     eprintln!("Error grabbing article: {e}");
@@ -255,10 +458,257 @@ impl Readability {
         }
     }
 
+    /// Select the article content from a document embedded in the page
+    fn grab_embedded_content(
+        &self,
+        document: &str,
+        source: ExtractionSource,
+    ) -> Option<Extraction> {
+        let preprocessed_doc = Html::parse_document(&cleaner::prep_document(document));
+        let extraction = self.grab_document_content(&preprocessed_doc)?;
+        Some(Extraction {
+            source,
+            ..extraction
+        })
+    }
+
+    /// Clean the selected content and assemble the final article
+    fn build_article(&self, extraction: Extraction) -> Article {
+        let content_html = extraction.content;
+        let cleaned_html = if extraction.source == ExtractionSource::CleanFragment {
+            clean_fragment_content(&content_html, self.base_url.as_deref(), &self.options)
+        } else {
+            clean_content(
+                &content_html,
+                self.base_url.as_deref(),
+                &self.options,
+                self.metadata.title.as_deref(),
+                self.metadata.byline.as_deref(),
+                self.metadata.recipe.as_ref(),
+            )
+        };
+        let light_content = self.options.include_light_content.then(|| {
+            cleaner::clean_article_content_light(&content_html, self.base_url.as_deref())
+                .unwrap_or_else(|_| content_html.clone())
+        });
+
+        let media = if self.options.extract_media {
+            crate::post_processor::collect_media(&cleaned_html, self.base_url.as_deref())
+        } else {
+            Vec::new()
+        };
+
+        let text_content = self.get_text_content(&cleaned_html);
+        let length = text_content.len();
+        let word_count = utils::count_words(&text_content);
+        let score = crate::scoring::extraction_confidence(
+            extraction.candidate_score,
+            length,
+            dom_utils::get_link_density(Html::parse_fragment(&cleaned_html).root_element()),
+        );
+
+        // Generate excerpt from content if not in metadata
+        // Try first paragraph of extracted content, then fall back to text
+        let excerpt = self.metadata.excerpt.clone().or_else(|| {
+            self.generate_excerpt_from_html(&cleaned_html)
+                .or_else(|| self.generate_excerpt_from_text(&text_content))
+        });
+
+        // Extract text direction from document
+        let dir = crate::dom_utils::get_article_direction(&self.document);
+
+        // Markdown and block output start from the standardized content
+        let needs_standardized = self.options.output_markdown || self.options.output_blocks;
+        let standardized = needs_standardized.then(|| {
+            crate::elements::standardize_all_for_width(
+                &cleaned_html,
+                self.metadata.title.as_deref(),
+                self.options.target_width,
+            )
+        });
+
+        // Optionally produce markdown output
+        let markdown_content = match &standardized {
+            Some(standardized) if self.options.output_markdown => {
+                let mut md_opts = self
+                    .options
+                    .markdown_options
+                    .as_ref()
+                    .cloned()
+                    .unwrap_or_default();
+                if md_opts.lang.is_none() {
+                    md_opts.lang = self.content_lang().map(str::to_string);
+                }
+                Some(crate::markdown::html_to_markdown(standardized, &md_opts))
+            }
+            _ => None,
+        };
+
+        let blocks = standardized
+            .as_deref()
+            .filter(|_| self.options.output_blocks)
+            .map(crate::blocks::html_to_blocks);
+
+        let plain_text = self
+            .options
+            .output_plain_text
+            .then(|| crate::plain_text::html_to_plain_text(&cleaned_html));
+
+        let amp_content = self
+            .options
+            .output_amp
+            .then(|| crate::amp::html_to_amp(&cleaned_html));
+
+        Article {
+            title: self.metadata.title.clone(),
+            content: Some(cleaned_html),
+            raw_content: Some(content_html),
+            light_content,
+            text_content: Some(text_content),
+            word_count,
+            length,
+            score,
+            candidate_path: (extraction.source == ExtractionSource::Page)
+                .then_some(extraction.path),
+            excerpt,
+            image: self.metadata.image.clone(),
+            byline: self.metadata.byline.clone(),
+            dir,
+            site_name: self.metadata.site_name.clone(),
+            lang: self.metadata.lang.clone(),
+            alternates: self.metadata.alternates.clone(),
+            recipe: self.metadata.recipe.clone(),
+            icon: self.metadata.icon.clone(),
+            breadcrumbs: if self.options.extract_breadcrumbs {
+                metadata::get_breadcrumbs(&self.document, !self.options.disable_json_ld)
+            } else {
+                Vec::new()
+            },
+            section: self.metadata.section.clone(),
+            media,
+            published_time: self.metadata.published_time.clone(),
+            modified_time: self.metadata.modified_time.clone(),
+            #[cfg(feature = "dates")]
+            published: self.metadata.published,
+            #[cfg(feature = "dates")]
+            modified: self.metadata.modified,
+            json_ld: self.metadata.json_ld.clone(),
+            markdown_content,
+            amp_content,
+            blocks,
+            plain_text,
+        }
+    }
+
     /// Extract plain text from HTML content
     fn get_text_content(&self, html: &str) -> String {
         let doc = Html::parse_fragment(html);
-        doc.root_element().text().collect::<String>()
+        let preserve_tags = &self.options.preserve_whitespace_tags;
+        let clean = |text: &str| {
+            utils::replace_nbsp(&utils::remove_soft_breaks(
+                text,
+                self.options.strip_soft_hyphens,
+            ))
+        };
+        let normalize = |text: &str| match &self.options.text_normalizer {
+            Some(normalizer) => normalizer.normalize(&clean(text)),
+            None => clean(text),
+        };
+
+        // Text outside preserved elements is normalized a run at a time.
+        let mut output = String::new();
+        let mut text = String::new();
+        let mut preserved_depth = 0;
+        let mut skip_until = None;
+        let mut after_br = false;
+        for edge in doc.root_element().traverse() {
+            match edge {
+                Edge::Open(node) => {
+                    if skip_until.is_some() {
+                        continue;
+                    }
+                    if ElementRef::wrap(node)
+                        .is_some_and(|el| dom_utils::preserves_whitespace(el, preserve_tags))
+                    {
+                        if preserved_depth == 0 {
+                            output.push_str(&normalize(&text));
+                            text.clear();
+                        }
+                        preserved_depth += 1;
+                    }
+                    if let Some(t) = node.value().as_text() {
+                        // Source line breaks after a kept `<br>` would double it.
+                        let keep_whitespace = preserved_depth > 0 || !after_br;
+                        text.push_str(if keep_whitespace { t } else { t.trim_start() });
+                        after_br = after_br && t.trim().is_empty();
+                    } else if (!self.options.convert_brs || preserved_depth > 0)
+                        && node
+                            .value()
+                            .as_element()
+                            .is_some_and(|el| el.name() == "br")
+                    {
+                        text.push('\n');
+                        after_br = true;
+                    } else if let Some(script) =
+                        ElementRef::wrap(node).and_then(|el| unicode_script_text(el))
+                    {
+                        // Keep formulas like H₂O and x² meaningful in plain text.
+                        text.push_str(&script);
+                        skip_until = Some(node.id());
+                    } else if let Some(quote) =
+                        ElementRef::wrap(node).filter(|el| el.value().name() == "q")
+                    {
+                        text.push_str(self.quote_marks(quote).0);
+                    }
+                }
+                Edge::Close(node) => {
+                    if skip_until == Some(node.id()) {
+                        skip_until = None;
+                        continue;
+                    }
+                    if skip_until.is_some() {
+                        continue;
+                    }
+                    let Some(element) = ElementRef::wrap(node) else {
+                        continue;
+                    };
+                    if dom_utils::preserves_whitespace(element, preserve_tags) {
+                        preserved_depth -= 1;
+                        if preserved_depth == 0 {
+                            output.push_str(&clean(&text));
+                            text.clear();
+                        }
+                    }
+                    match element.value().name() {
+                        "q" => text.push_str(self.quote_marks(element).1),
+                        "abbr" if self.options.expand_abbreviations => {
+                            let title = element.value().attr("title").unwrap_or("").trim();
+                            let short = element.text().collect::<String>();
+                            if !title.is_empty() && title != short.trim() {
+                                text.push_str(&format!(" ({title})"));
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+        output.push_str(&normalize(&text));
+        output
+    }
+
+    /// Language of the article, from the `locale` option or the page
+    fn content_lang(&self) -> Option<&str> {
+        self.options
+            .locale
+            .as_deref()
+            .or(self.metadata.lang.as_deref())
+    }
+
+    /// Quotation marks to wrap the text of a `<q>` element in
+    fn quote_marks(&self, quote: ElementRef) -> (&'static str, &'static str) {
+        let lang = dom_utils::inherited_lang(quote).or(self.content_lang());
+        utils::quote_marks(lang, dom_utils::quote_depth(quote))
     }
 
     /// Generate an excerpt from the first paragraph of article HTML
@@ -422,7 +872,9 @@ impl Readability {
         }
 
         let truncated: String = text.chars().take(max_len).collect();
-        if let Some(last_space_pos) = truncated.rfind(char::is_whitespace) {
+        if let Some(sentence_end) = utils::last_sentence_end(&truncated, self.content_lang()) {
+            truncated[..sentence_end].trim().to_string()
+        } else if let Some(last_space_pos) = truncated.rfind(char::is_whitespace) {
             truncated[..last_space_pos].trim().to_string()
         } else {
             truncated.trim().to_string()
@@ -438,9 +890,313 @@ impl Readability {
     }
 }
 
+/// Run the content cleaning passes on HTML that has already been isolated.
+///
+/// This is the cleaning half of [`Readability::parse`] without candidate
+/// scoring or metadata extraction, for callers that select the article
+/// content themselves. The passes, in order:
+///
+/// - remove navigation sections
+/// - strip inline styles and presentational attributes (`clean_styles`)
+/// - remove forms, footers, asides, non-video embeds and share widgets
+/// - remove empty paragraphs and collapse whitespace (`clean_whitespace`)
+/// - conditionally remove tables, lists and `div`s that look like ads,
+///   link farms or widgets, by text, link density and image counts
+/// - remove empty wrappers (`remove_empty_wrappers`)
+///
+/// Optional passes such as `demote_extra_h1`, `strip_boilerplate` or
+/// `wrap_sections` run when enabled in `options`. Title removal is skipped,
+/// as there is no document to read the title from. Pass `None` to use the
+/// default options.
+///
+/// ```rust
+/// use readabilityrs::clean;
+///
+/// let html = r#"<div><p style="color: red">Article text.</p><p></p></div>"#;
+/// let cleaned = clean(html, Some("https://example.com"), None);
+/// assert_eq!(cleaned.trim(), "<div><p>Article text.</p></div>");
+/// ```
+pub fn clean(html: &str, base_url: Option<&str>, options: Option<ReadabilityOptions>) -> String {
+    let options = options.unwrap_or_default();
+    dom_utils::with_text_normalizer(options.text_normalizer.as_ref(), || {
+        clean_content(html, base_url, &options, None, None, None)
+    })
+}
+
+/// Clean extracted content, removing `title` when the options ask for it and
+/// a standalone `byline` element.
+fn clean_content(
+    html: &str,
+    base_url: Option<&str>,
+    options: &ReadabilityOptions,
+    title: Option<&str>,
+    byline: Option<&str>,
+    recipe: Option<&Recipe>,
+) -> String {
+    let cleaned_wrapper_html =
+        cleaner::clean_article_content_light(html, base_url).unwrap_or_else(|_| html.to_string());
+
+    let video_regex = options
+        .allowed_video_regex
+        .as_ref()
+        .unwrap_or(&REGEXPS.videos);
+
+    let cleaned_wrapper_html = if options.iframe_placeholders {
+        crate::post_processor::replace_iframes_with_links(
+            &cleaned_wrapper_html,
+            base_url,
+            video_regex,
+        )
+    } else {
+        cleaned_wrapper_html
+    };
+
+    let is_documentation = options.content_profile == ContentProfile::Documentation;
+    let cleaned_wrapper_html = if is_documentation {
+        crate::post_processor::remove_documentation_chrome(&cleaned_wrapper_html)
+    } else {
+        cleaned_wrapper_html
+    };
+
+    let mut prepped_html = crate::post_processor::prep_article(
+        &cleaned_wrapper_html,
+        options.clean_styles,
+        options.clean_whitespace,
+        video_regex,
+        &options.preserve_whitespace_tags,
+    );
+
+    // Remove title from content if the option is enabled
+    if options.remove_title_from_content {
+        if let Some(title) = title {
+            if options.promote_title_header {
+                prepped_html = crate::post_processor::promote_title_header(&prepped_html, title);
+            }
+            prepped_html = crate::post_processor::remove_title_from_content(&prepped_html, title);
+        }
+    }
+    if options.demote_extra_h1 {
+        prepped_html = crate::post_processor::demote_extra_h1s(&prepped_html);
+    }
+    if options.strip_text_breadcrumbs {
+        prepped_html = crate::post_processor::strip_leading_breadcrumbs(&prepped_html);
+    }
+    if options.strip_boilerplate {
+        prepped_html = crate::post_processor::strip_boilerplate_paragraphs(
+            &prepped_html,
+            &options.boilerplate_phrases,
+        );
+    }
+    if options.remove_footnote_backrefs {
+        prepped_html = crate::post_processor::remove_footnote_backrefs(&prepped_html);
+    }
+    if options.strip_image_credits {
+        prepped_html = crate::post_processor::strip_image_credits(&prepped_html);
+    }
+    let mut cleaned_html = match cleaner::clean_article_content(
+        &prepped_html,
+        base_url,
+        video_regex,
+        recipe,
+        &options.content_containers,
+        !is_documentation,
+    ) {
+        Ok(html) => html,
+        Err(e) => {
+            if options.debug {
+                eprintln!("Error cleaning content: {e}");
+            }
+            prepped_html
+        }
+    };
+
+    if let Some(byline) = byline {
+        cleaned_html = crate::post_processor::remove_byline(&cleaned_html, byline);
+    }
+    if !options.keep_standalone_captions {
+        cleaned_html = crate::post_processor::remove_standalone_captions(&cleaned_html);
+    }
+    if options.convert_scene_breaks {
+        cleaned_html = crate::post_processor::convert_scene_breaks(&cleaned_html);
+    }
+    if options.remove_emoji_paragraphs {
+        cleaned_html = crate::post_processor::remove_emoji_paragraphs(&cleaned_html);
+    }
+    if options.open_details {
+        cleaned_html = crate::post_processor::open_details(&cleaned_html);
+    }
+    cleaned_html = crate::post_processor::remove_svgs(&cleaned_html, options.svg_policy);
+    cleaned_html = crate::post_processor::remove_ad_labels(&cleaned_html);
+    if options.remove_empty_wrappers {
+        cleaned_html = crate::post_processor::remove_empty_wrappers(&cleaned_html);
+    }
+    if options.remove_empty_sections {
+        cleaned_html = crate::post_processor::remove_empty_sections(&cleaned_html);
+    }
+    if options.merge_split_lists {
+        cleaned_html = crate::post_processor::merge_split_lists(&cleaned_html);
+    }
+    if options.flatten_wrappers {
+        cleaned_html = crate::post_processor::flatten_wrappers(&cleaned_html);
+    }
+    if let Some(width) = options.target_width {
+        cleaned_html = crate::elements::images::resolve_responsive_images(&cleaned_html, width);
+    }
+    if options.eager_images > 0 {
+        cleaned_html =
+            crate::post_processor::make_leading_images_eager(&cleaned_html, options.eager_images);
+    }
+    if options.annotate_source {
+        cleaned_html = cleaner::retain_block_source_lines(&cleaned_html);
+    }
+    if options.wrap_sections {
+        cleaned_html = crate::post_processor::wrap_heading_sections(&cleaned_html);
+    }
+    if options.strip_soft_hyphens_html {
+        cleaned_html = cleaned_html.replace('\u{ad}', "");
+    }
+
+    cleaned_html
+}
+
+/// Clean a fragment taken whole by the clean-fragment fast path
+///
+/// Only URLs are fixed, navigation-like sections removed and, with a
+/// `target_width`, responsive images resolved; the scoring-oriented passes of
+/// [`clean_content`] are skipped.
+fn clean_fragment_content(
+    html: &str,
+    base_url: Option<&str>,
+    options: &ReadabilityOptions,
+) -> String {
+    let mut cleaned =
+        cleaner::clean_article_content_light(html, base_url).unwrap_or_else(|_| html.to_string());
+    if let Some(width) = options.target_width {
+        cleaned = crate::elements::images::resolve_responsive_images(&cleaned, width);
+    }
+    cleaned
+}
+
+/// Length of the text in an extraction's content.
+fn extraction_text_length(extraction: &Extraction) -> usize {
+    let fragment = Html::parse_fragment(&extraction.content);
+    dom_utils::get_inner_text(fragment.root_element(), true).len()
+}
+
+/// Empty the elements at `paths` so extraction cannot select their content.
+///
+/// The elements themselves stay in place, keeping the positions in paths to
+/// their later siblings valid across runs.
+fn mask_paths(document: &mut Html, paths: &[&str]) {
+    let children: Vec<_> = paths
+        .iter()
+        .filter_map(|path| dom_utils::find_element_by_path(document, path))
+        .flat_map(|element| element.children().map(|child| child.id()))
+        .collect();
+    for id in children {
+        if let Some(mut node) = document.tree.get_mut(id) {
+            node.detach();
+        }
+    }
+}
+
+/// The options and article title that influence content selection, used to
+/// share `grab_article` results between variants in
+/// [`Readability::parse_variants`].
+#[derive(PartialEq, Eq, Hash)]
+struct ExtractionKey {
+    annotate_source: bool,
+    char_threshold: usize,
+    strict: bool,
+    nb_top_candidates: usize,
+    ancestor_score_depth: usize,
+    link_density_modifier: u64,
+    max_link_density: u64,
+    base_score_overrides: Vec<(String, u64)>,
+    locale: Option<String>,
+    retry_strategy: Option<*const ()>,
+    embedded_html_min_length: usize,
+    iframe_srcdoc_min_length: usize,
+    clean_fragment_max_elements: usize,
+    convert_brs: bool,
+    preserve_whitespace_tags: Vec<String>,
+    text_normalizer: Option<*const ()>,
+    content_profile: ContentProfile,
+    remove_consent_banners: bool,
+    title: Option<String>,
+}
+
+impl ExtractionKey {
+    fn new(options: &ReadabilityOptions, title: Option<&str>) -> Self {
+        Self {
+            annotate_source: options.annotate_source,
+            char_threshold: options.char_threshold,
+            strict: options.strict,
+            nb_top_candidates: options.nb_top_candidates,
+            ancestor_score_depth: options.ancestor_score_depth,
+            link_density_modifier: options.link_density_modifier.to_bits(),
+            max_link_density: options.max_link_density.to_bits(),
+            base_score_overrides: {
+                let mut overrides: Vec<(String, u64)> = options
+                    .base_score_overrides
+                    .iter()
+                    .map(|(tag, score)| (tag.clone(), score.to_bits()))
+                    .collect();
+                overrides.sort();
+                overrides
+            },
+            locale: options.locale.clone(),
+            retry_strategy: options
+                .retry_strategy
+                .as_ref()
+                .map(|strategy| Arc::as_ptr(strategy) as *const ()),
+            embedded_html_min_length: options.embedded_html_min_length,
+            iframe_srcdoc_min_length: options.iframe_srcdoc_min_length,
+            clean_fragment_max_elements: options.clean_fragment_max_elements,
+            convert_brs: options.convert_brs,
+            preserve_whitespace_tags: {
+                let mut tags = options.preserve_whitespace_tags.clone();
+                tags.sort();
+                tags
+            },
+            text_normalizer: options
+                .text_normalizer
+                .as_ref()
+                .map(|normalizer| normalizer.as_ptr()),
+            content_profile: options.content_profile,
+            remove_consent_banners: options.remove_consent_banners,
+            title: title.map(str::to_string),
+        }
+    }
+}
+
+/// Unicode rendering of a `<sub>`/`<sup>` element's text, if every character has one.
+fn unicode_script_text(element: ElementRef) -> Option<String> {
+    let text = element.text().collect::<String>();
+    match element.value().name() {
+        "sup" => utils::to_unicode_superscript(&text),
+        "sub" => utils::to_unicode_subscript(&text),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::markdown::options::{HighlightStyle, MarkdownOptions};
+    use crate::options::SvgPolicy;
+
+    /// Two paragraphs of article text, enough for a `char_threshold` of 100.
+    const GARDEN_PARAGRAPHS: &str = "<p>Spring has finally arrived, and with it the first wave of seedlings. We spent the week transplanting tomatoes, peppers, and a few experimental squash varieties.</p>
+<p>The soil temperature matters more than the air temperature, so we waited until our thermometer read a steady fifteen degrees before moving anything outside.</p>";
+
+    /// Parse `html` with `options`, expecting an article to be found.
+    fn parse_with(html: &str, options: ReadabilityOptions) -> Article {
+        Readability::new(html, None, Some(options))
+            .unwrap()
+            .parse()
+            .unwrap()
+    }
 
     #[test]
     fn test_new_readability() {
@@ -476,19 +1232,2040 @@ mod tests {
     }
 
     #[test]
-    fn excerpt_skips_hatnote_paragraphs() {
-        let html = r#"
-        <p class="hatnote" role="note">See also: Something else entirely.</p>
-        <p>This is the first real paragraph with sufficient length to act as an excerpt. It should be returned.</p>
-        "#;
-        let reader = Readability::new(html, None, None).unwrap();
-        let excerpt = reader.generate_excerpt_from_html(html);
-        assert_eq!(
-            excerpt,
-            Some(
-                "This is the first real paragraph with sufficient length to act as an excerpt. It should be returned."
-                    .to_string()
+    fn test_annotate_source_lines_are_monotonic() {
+        let html = r#"<html>
+<head><title>Annotated</title></head>
+<body>
+<article>
+<p>The first paragraph has enough text to be picked up as article content, with commas, clauses, and more.</p>
+<p>The second paragraph continues the story with <a href="https://example.com">a link</a> and more detail.</p>
+
+<p>The third paragraph closes the article with another sentence that carries a reasonable amount of text.</p>
+</article>
+</body>
+</html>"#;
+
+        let options = ReadabilityOptions::builder()
+            .char_threshold(100)
+            .annotate_source(true)
+            .build();
+        let article = parse_with(html, options);
+        let content = article.content.unwrap();
+
+        let doc = Html::parse_fragment(&content);
+        let selector = Selector::parse("p").unwrap();
+        let lines: Vec<usize> = doc
+            .select(&selector)
+            .map(|p| p.value().attr("data-src-line").unwrap().parse().unwrap())
+            .collect();
+        assert_eq!(lines, vec![5, 6, 8]);
+
+        let link_selector = Selector::parse("a").unwrap();
+        let link = doc.select(&link_selector).next().unwrap();
+        assert!(link.value().attr("data-src-line").is_none());
+    }
+
+    #[test]
+    fn test_source_lines_absent_by_default() {
+        let html = r#"<html><body><article>
+<p>The first paragraph has enough text to be picked up as article content, with commas, clauses, and more.</p>
+<p>The second paragraph continues the story with some more detail to pass the character threshold.</p>
+</article></body></html>"#;
+
+        let options = ReadabilityOptions::builder().char_threshold(100).build();
+        let article = parse_with(html, options);
+        assert!(!article.content.unwrap().contains("data-src-line"));
+    }
+
+    #[test]
+    fn test_abbr_title_is_preserved() {
+        let html = r#"<html><body><article>
+<p>The <abbr title="World Health Organization">WHO</abbr> published a report with enough text to be picked up as article content, with commas, clauses, and more.</p>
+<p>The second paragraph continues the story with some more detail to pass the character threshold.</p>
+</article></body></html>"#;
+
+        let options = ReadabilityOptions::builder().char_threshold(100).build();
+        let article = parse_with(html, options);
+        assert!(article
+            .content
+            .unwrap()
+            .contains(r#"<abbr title="World Health Organization">WHO</abbr>"#));
+        assert!(article.text_content.unwrap().contains("The WHO published"));
+
+        let options = ReadabilityOptions::builder()
+            .char_threshold(100)
+            .expand_abbreviations(true)
+            .build();
+        let article = parse_with(html, options);
+        assert!(article
+            .text_content
+            .unwrap()
+            .contains("The WHO (World Health Organization) published"));
+    }
+
+    #[test]
+    fn test_glossary_term_markup_is_kept() {
+        let html = r##"<html><head><title>Glossary of build terms</title></head><body><article>
+<p>This glossary defines the terms used across the build documentation, with links from every page that uses them.</p>
+<h3><dfn id="term-artifact">Artifact</dfn></h3>
+<p>Any file produced by the pipeline, such as an archive or a coverage report that later <a href="#term-artifact">artifacts</a> build on.</p>
+<div><dfn id="term-cache">Cache</dfn></div>
+<p>A store kept between runs and served through the <abbr aria-hidden="true" title="Content Delivery Network">CDN</abbr><span class="sr-only">Content Delivery Network</span> to other regions.</p>
+</article></body></html>"##;
+
+        let options = ReadabilityOptions::builder().char_threshold(100).build();
+        let content = parse_with(html, options).content.unwrap();
+
+        assert!(content.contains(r#"<h3><dfn id="term-artifact">Artifact</dfn></h3>"#));
+        assert!(content.contains(r#"<dfn id="term-cache">Cache</dfn>"#));
+        assert!(content.contains(
+            r#"through the <abbr aria-hidden="true" title="Content Delivery Network">CDN</abbr>"#
+        ));
+    }
+
+    #[test]
+    fn test_inline_quotes_follow_language() {
+        let page = |lang: &str| {
+            format!(
+                r#"<html lang="{lang}"><head><title>Herons</title></head><body><article>
+<p>The warden told us <q cite="https://example.com/interview">the birds came back when <q>the river ran clean</q> again</q> and stayed for the whole of the spring.</p>
+<p>The trust will publish its full survey of the estuary later in the year, with counts for every colony.</p>
+</article></body></html>"#
             )
+        };
+        let parse = |html: &str| {
+            let options = ReadabilityOptions::builder()
+                .char_threshold(100)
+                .output_markdown(true)
+                .build();
+            parse_with(html, options)
+        };
+
+        let english = parse(&page("en"));
+        assert!(english
+            .content
+            .unwrap()
+            .contains(r#"<q cite="https://example.com/interview">the birds came back"#));
+        let quote = "told us “the birds came back when ‘the river ran clean’ again” and stayed";
+        assert!(english.text_content.unwrap().contains(quote));
+        assert!(english.markdown_content.unwrap().contains(quote));
+
+        let french = parse(&page("fr"));
+        let quote = "told us « the birds came back when “the river ran clean” again » and stayed";
+        assert!(french.text_content.unwrap().contains(quote));
+        assert!(french
+            .markdown_content
+            .unwrap()
+            .contains("told us «\u{a0}the birds came back"));
+    }
+
+    #[test]
+    fn test_recipe_page_keeps_ingredients() {
+        let html = r##"<html><head>
+<script type="application/ld+json">
+{"@context": "https://schema.org", "@type": "Recipe", "name": "Flatbread",
+ "recipeIngredient": ["2 cups flour", "1 tsp salt", "3/4 cup warm water"],
+ "recipeInstructions": [{"@type": "HowToStep", "text": "Mix everything into a dough."},
+                        {"@type": "HowToStep", "text": "Cook in a hot pan for two minutes per side."}]}
+</script></head><body><article>
+<p>This flatbread is the easiest bread you will ever make, with only three ingredients, no yeast, and no oven required.</p>
+<div class="widget"><ul>
+<li><a href="#flour">2 cups flour</a></li>
+<li><a href="#salt">1 tsp salt</a></li>
+<li><a href="#water">3/4 cup warm water</a></li>
+</ul></div>
+<p>Once you have made it a couple of times, you can flavour the dough with herbs, garlic, or spices to suit the meal.</p>
+</article></body></html>"##;
+
+        let options = ReadabilityOptions::builder().char_threshold(100).build();
+        let article = parse_with(html, options);
+        let recipe = article.recipe.expect("recipe should be extracted");
+        assert_eq!(recipe.ingredients.len(), 3);
+        assert_eq!(recipe.instructions[0], "Mix everything into a dough.");
+        let content = article.content.unwrap();
+        assert!(content.contains("2 cups flour"));
+        assert!(content.contains("3/4 cup warm water"));
+    }
+
+    #[test]
+    fn test_sub_and_sup_survive_extraction() {
+        let html = r#"<html><body><article>
+<p>Water, or H<sub>2</sub>O, covers most of the planet, and chemists have studied it for centuries with great care.</p>
+<p>The area of a square with side x is x<sup>2</sup>, a fact every student meets early in school, along with many others.</p>
+</article></body></html>"#;
+
+        let options = ReadabilityOptions::builder().char_threshold(100).build();
+        let article = parse_with(html, options);
+        let content = article.content.unwrap();
+        assert!(content.contains("H<sub>2</sub>O"));
+        assert!(content.contains("x<sup>2</sup>"));
+
+        let text = article.text_content.unwrap();
+        assert!(text.contains("or H₂O, covers"));
+        assert!(text.contains("side x is x², a fact"));
+    }
+
+    #[test]
+    fn test_word_count_matches_text_content() {
+        let html = r#"<html><body><article>
+<p>长城是中国古代的军事防御工程，修建历史可以追溯到西周时期，绵延数千公里，横跨多个省份。</p>
+<p>明代对长城进行了大规模的修缮和扩建，今天我们看到的大部分城墙都是明朝留下来的。</p>
+</article></body></html>"#;
+
+        let options = ReadabilityOptions::builder().char_threshold(50).build();
+        let article = parse_with(html, options);
+        let text = article.text_content.unwrap();
+        assert!(article.word_count > 60);
+        assert_eq!(article.word_count, utils::count_words(&text));
+    }
+
+    #[test]
+    fn test_kbd_and_samp_are_preserved() {
+        let html = r#"<html><head><title>Copying text</title></head><body><article>
+<h1>Copying text</h1>
+<p>To copy the selection, press <kbd>Ctrl+C</kbd> in any window. This works across most desktop applications and in the terminal emulators we tested.</p>
+<p>The terminal will then print <samp>Copied 12 bytes</samp> to confirm the operation, which is useful when you are working over a remote connection.</p>
+</article></body></html>"#;
+
+        let options = ReadabilityOptions::builder()
+            .char_threshold(100)
+            .output_markdown(true)
+            .build();
+        let article = parse_with(html, options);
+
+        let content = article.content.unwrap();
+        assert!(content.contains("<kbd>Ctrl+C</kbd>"));
+        assert!(content.contains("<samp>Copied 12 bytes</samp>"));
+
+        let markdown = article.markdown_content.unwrap();
+        assert!(markdown.contains("press `Ctrl+C` in any window"));
+        assert!(markdown.contains("print `Copied 12 bytes` to confirm"));
+    }
+
+    #[test]
+    fn test_truncate_text_breaks_at_japanese_sentence_end() {
+        let options = ReadabilityOptions::builder().locale("ja").build();
+        let reader = Readability::new("<p></p>", None, Some(options)).unwrap();
+        let text = "今日は晴れです。明日は雨が降るでしょう。週末はまた晴れる予報です。";
+        assert_eq!(
+            reader.truncate_text(text, 25),
+            "今日は晴れです。明日は雨が降るでしょう。"
+        );
+
+        let reader = Readability::new("<p></p>", None, None).unwrap();
+        assert_eq!(reader.truncate_text(text, 25).chars().count(), 25);
+    }
+
+    #[test]
+    fn test_parse_variants_matches_individual_parses() {
+        let html = format!(
+            r#"<html lang="en"><head><title>Garden notes</title>
+<meta name="description" content="Notes from the garden">
+<script type="application/ld+json">{{"@type": "NewsArticle", "headline": "Structured garden notes"}}</script>
+</head><body>
+<div class="sidebar"><a href="/a">Archive</a> <a href="/b">About</a></div>
+<article>
+<h1>Garden notes</h1>
+<p>Sign up for our newsletter to get notes like these every week.</p>
+{GARDEN_PARAGRAPHS}
+<div><samp>line one<br><br>line two</samp></div>
+</article></body></html>"#
+        );
+
+        let variants = vec![
+            ReadabilityOptions::builder().char_threshold(100).build(),
+            ReadabilityOptions::builder()
+                .char_threshold(100)
+                .strip_boilerplate(true)
+                .build(),
+            ReadabilityOptions::builder()
+                .char_threshold(100)
+                .disable_json_ld(true)
+                .annotate_source(true)
+                .build(),
+            ReadabilityOptions::builder()
+                .char_threshold(100)
+                .nb_top_candidates(1)
+                .output_markdown(true)
+                .build(),
+            ReadabilityOptions::builder()
+                .char_threshold(100)
+                .preserve_whitespace_tags(vec!["pre".to_string(), "samp".to_string()])
+                .build(),
+        ];
+
+        let results = Readability::new(&html, None, None)
+            .unwrap()
+            .parse_variants(variants.clone());
+        assert_eq!(results.len(), variants.len());
+
+        for ((options, article), expected_options) in results.into_iter().zip(variants) {
+            assert_eq!(
+                options.strip_boilerplate,
+                expected_options.strip_boilerplate
+            );
+            let expected = Readability::new(&html, None, Some(expected_options))
+                .unwrap()
+                .parse();
+            assert_eq!(article, expected);
+        }
+    }
+
+    #[test]
+    fn test_parse_variants_keys_content_on_title() {
+        let cards: String = (0..60)
+            .map(|i| format!(r#"<div class="card"><h3><a href="/p/{i}">Related story number {i}: everything we learned about the orchard and its trees this year</a></h3><p>Apples, pears, plums, and cherries, pruned and trained.</p></div>"#))
+            .collect();
+        let html = format!(
+            r#"<html><head><title>Notes from a small orchard in the valley</title>
+<script type="application/ld+json">{{"@context": "https://schema.org", "@type": "Article", "headline": "Pruning apple trees in winter"}}</script>
+</head><body><div id="page"><div class="wrap">
+<div class="story">
+<h1>Pruning apple trees in winter</h1>
+<p>Winter is the best time to prune, because without leaves you can see the whole <a href="/g/structure">structure of the tree</a>. Start with <a href="/g/dead-wood">dead, damaged or diseased wood</a>, then remove branches that cross or rub.</p>
+<p>Aim for an open, goblet shape that lets light reach the centre. Never remove more than a quarter of the canopy in one year, or the tree will respond with a thicket of <a href="/g/water-shoots">water shoots</a> next summer.</p>
+<p>Use <a href="/tools/secateurs">sharp secateurs</a> for thin growth and a <a href="/tools/saws">pruning saw</a> for anything thicker than a finger, and clean the blades between trees.</p>
+</div>
+<div class="cards">{cards}</div>
+</div></div></body></html>"#
         );
+
+        // The JSON-LD headline steers content selection; without it the
+        // title is the page title and the link-heavy wrapper wins.
+        let options = |disable_json_ld: bool| {
+            ReadabilityOptions::builder()
+                .char_threshold(100)
+                .max_link_density(1.0)
+                .disable_json_ld(disable_json_ld)
+                .build()
+        };
+        let variants = vec![options(false), options(true)];
+        let results = Readability::new(&html, None, None)
+            .unwrap()
+            .parse_variants(variants.clone());
+        let path = |index: usize| results[index].1.as_ref().unwrap().candidate_path.clone();
+        assert_ne!(path(0), path(1));
+        for ((_, article), options) in results.into_iter().zip(variants) {
+            let expected = Readability::new(&html, None, Some(options))
+                .unwrap()
+                .parse();
+            assert_eq!(article, expected);
+        }
+    }
+
+    #[test]
+    fn test_promote_title_header_keeps_byline_and_date() {
+        let html = format!(
+            r#"<html><head><title>Growing tomatoes in small spaces</title></head><body><article>
+<header class="entry-header">
+  <h1 class="entry-title">Growing tomatoes in small spaces</h1>
+  <span class="byline">By <a href="/author/jane" rel="author">Jane Doe</a></span> <time datetime="2024-05-01">May 1, 2024</time>
+</header>
+{GARDEN_PARAGRAPHS}
+<p>Another paragraph of filler text so that the article has enough content to pass the character threshold used by the extraction algorithm in the default configuration.</p>
+</article></body></html>"#
+        );
+
+        let options = ReadabilityOptions::builder()
+            .char_threshold(100)
+            .remove_title_from_content(true)
+            .promote_title_header(true)
+            .build();
+        let article = parse_with(&html, options);
+        let content = article.content.unwrap();
+
+        assert!(!content.contains("<h1"));
+        assert!(!content.contains("<header"));
+        let lede = content.find(r#"<p><span class="byline">By <a href="/author/jane" rel="author">Jane Doe</a></span> <time datetime="2024-05-01">May 1, 2024</time></p>"#);
+        let body = content.find("<p>Spring has finally arrived");
+        assert!(lede.is_some() && body.is_some());
+        assert!(lede < body);
+    }
+
+    #[test]
+    fn test_nbsp_heavy_content() {
+        let html = "<html><head><title>Spacing</title></head><body><article>\
+<p>&nbsp;&nbsp;Typesetters&nbsp;used&nbsp;non-breaking&nbsp;spaces&nbsp;between&nbsp;every&nbsp;word&nbsp;of&nbsp;this&nbsp;paragraph, which makes it look longer than it is.&nbsp;</p>\
+<p>&nbsp;</p>\
+<p>The second paragraph is ordinary text written with regular spaces, long enough to count as real content for the scorer.</p>\
+<p> &nbsp; <br> &nbsp; </p>\
+</article></body></html>";
+
+        let options = ReadabilityOptions::builder().char_threshold(100).build();
+        let article = parse_with(html, options);
+
+        let text = article.text_content.unwrap();
+        assert!(!text.contains('\u{a0}'));
+        assert!(text.contains("Typesetters used non-breaking spaces between every word"));
+        assert_eq!(article.length, text.len());
+
+        let content = article.content.unwrap();
+        assert!(!content.contains("<p>&nbsp;</p>"));
+        assert!(!content.contains("<br>"));
+        assert_eq!(content.matches("<p").count(), 2);
+    }
+
+    #[test]
+    fn test_breadcrumbs_are_opt_in() {
+        let html = format!(
+            r#"<html><head><title>Tomato season</title>
+<script type="application/ld+json">{{"@type": "BreadcrumbList", "itemListElement": [
+    {{"@type": "ListItem", "position": 1, "name": "Garden"}},
+    {{"@type": "ListItem", "position": 2, "name": "Vegetables"}}
+]}}</script></head><body>
+<nav aria-label="breadcrumb"><ol><li><a href="/garden">Garden</a></li><li><a href="/garden/veg">Vegetables</a></li></ol></nav>
+<article>
+{GARDEN_PARAGRAPHS}
+</article></body></html>"#
+        );
+
+        let parse = |extract: bool| {
+            let options = ReadabilityOptions::builder()
+                .char_threshold(100)
+                .extract_breadcrumbs(extract)
+                .build();
+            parse_with(&html, options)
+        };
+
+        assert!(parse(false).breadcrumbs.is_empty());
+        let article = parse(true);
+        assert_eq!(article.breadcrumbs, vec!["Garden", "Vegetables"]);
+        assert!(!article.content.unwrap().contains("breadcrumb"));
+    }
+
+    #[test]
+    fn test_demote_extra_h1() {
+        let html = format!(
+            r#"<html><head><title>Garden notes</title></head><body><article>
+<h1>Garden notes</h1>
+{GARDEN_PARAGRAPHS}
+<h1>Watering</h1>
+<p>The soil temperature matters more than the air temperature, so we waited until our thermometer read a steady fifteen degrees before moving anything outside.</p>
+</article></body></html>"#
+        );
+
+        let parse = |demote: bool| {
+            let options = ReadabilityOptions::builder()
+                .char_threshold(100)
+                .demote_extra_h1(demote)
+                .build();
+            parse_with(&html, options).content.unwrap()
+        };
+
+        assert_eq!(parse(false).matches("<h1").count(), 2);
+        let content = parse(true);
+        assert_eq!(content.matches("<h1").count(), 1);
+        assert!(content.contains("<h1>Garden notes</h1>"));
+        assert!(content.contains("<h2>Watering</h2>"));
+    }
+
+    #[test]
+    fn test_wrap_sections() {
+        let html = format!(
+            r#"<html><head><title>Garden notes</title></head><body><article>
+<h2>Seedlings</h2>
+{GARDEN_PARAGRAPHS}
+<h2>Watering</h2>
+<p>The soil temperature matters more than the air temperature, so we waited until our thermometer read a steady fifteen degrees before moving anything outside.</p>
+</article></body></html>"#
+        );
+
+        let options = ReadabilityOptions::builder()
+            .char_threshold(100)
+            .wrap_sections(true)
+            .output_markdown(true)
+            .build();
+        let article = parse_with(&html, options);
+        let content = article.content.unwrap();
+        assert_eq!(content.matches("<section>").count(), 2);
+        assert!(content.contains("<section><h2>Seedlings</h2>"));
+        assert!(content.contains("<section><h2>Watering</h2>"));
+        assert!(article.markdown_content.unwrap().contains("## Watering"));
+    }
+
+    #[test]
+    fn test_accessibility_attributes_are_kept() {
+        let html = format!(
+            r#"<html><head><title>Seedlings</title></head><body><article>
+{GARDEN_PARAGRAPHS}
+<figure role="group" aria-label="Seedling photos"><img src="https://example.com/a.jpg" alt="Tomato seedlings"><figcaption>Tomatoes after two weeks.</figcaption></figure>
+<blockquote role="note" aria-label="Gardener's tip"><p>Soil temperature matters more than air temperature, so wait for a steady fifteen degrees.</p></blockquote>
+<iframe src="https://www.youtube.com/embed/abc" role="region" aria-label="Planting video"></iframe>
+<p>The soil temperature matters more than the air temperature, so we waited until our thermometer read a steady fifteen degrees before moving anything outside.</p>
+<span aria-hidden="true">Decorative flourish that screen readers skip</span>
+</article></body></html>"#
+        );
+
+        let options = ReadabilityOptions::builder().char_threshold(100).build();
+        let content = parse_with(&html, options).content.unwrap();
+
+        assert!(content.contains(r#"<figure aria-label="Seedling photos" role="group">"#));
+        assert!(content.contains(r#"<blockquote aria-label="Gardener's tip" role="note">"#));
+        assert!(content.contains(r#"aria-label="Planting video" role="region""#));
+        assert!(!content.contains("Decorative flourish"));
+    }
+
+    #[test]
+    fn test_unclosed_wrappers_before_article() {
+        // A template that opens its layout wrappers without ever closing them.
+        let html = format!(
+            r#"<html><head><title>Garden notes</title></head><body>
+{}
+<article>
+{GARDEN_PARAGRAPHS}
+</article>
+</body></html>"#,
+            r#"<div class="container"><div class="row"><div class="col">"#.repeat(4)
+        );
+
+        let options = ReadabilityOptions::builder().char_threshold(100).build();
+        let content = parse_with(&html, options).content.unwrap();
+
+        assert!(content.contains("first wave of seedlings"));
+        assert!(content.contains("a steady fifteen degrees"));
+        assert!(content.matches(r#"class="row""#).count() <= 1);
+    }
+
+    #[test]
+    fn test_all_hidden_tab_panels() {
+        // Server-rendered tabs arrive with every panel hidden until a script runs.
+        let html = format!(
+            r#"<html><head><title>Garden notes</title></head><body>
+<ul role="tablist"><li role="tab">Summary</li><li role="tab">Article</li></ul>
+<div role="tabpanel" hidden><p>A short summary of this week in the garden.</p></div>
+<div role="tabpanel" hidden>
+{GARDEN_PARAGRAPHS}
+</div>
+</body></html>"#
+        );
+
+        let options = ReadabilityOptions::builder().char_threshold(100).build();
+        let content = parse_with(&html, options).content.unwrap();
+
+        assert!(content.contains("first wave of seedlings"));
+        assert!(content.contains("a steady fifteen degrees"));
+        assert!(!content.contains("short summary"));
+    }
+
+    #[test]
+    fn test_empty_wrappers_removed_after_cleaning() {
+        let links: String = (1..=6)
+            .map(|i| {
+                format!(r#"<li class="story"><a href="https://news.example.com/s/{i}">Headline number {i} about something else entirely</a></li>"#)
+            })
+            .collect();
+        let html = format!(
+            r#"<html><head><title>Garden notes</title></head><body><article>
+{GARDEN_PARAGRAPHS}
+<section class="mod">
+<div class="listings-module">
+<div class="hd"><h2>Top Stories</h2></div>
+<div class="bd"><div class="latest-content"><ul>{links}</ul></div></div>
+</div>
+</section>
+<p>Hardening off took about ten days. Each morning the trays went out for a little longer, and each evening they came back in before the temperature dropped.</p>
+</article></body></html>"#
+        );
+
+        let parse = |remove: bool| {
+            let options = ReadabilityOptions::builder()
+                .char_threshold(100)
+                .remove_empty_wrappers(remove)
+                .build();
+            parse_with(&html, options).content.unwrap()
+        };
+
+        // The link list is cleaned away, leaving its wrappers behind.
+        let kept = parse(false);
+        assert!(!kept.contains("Headline number"));
+        assert!(kept.contains(r#"<div class="bd"></div>"#));
+
+        let content = parse(true);
+        assert!(!content.contains(r#"class="bd""#));
+        assert!(content.contains("<h2>Top Stories</h2>"));
+        assert!(content.contains("first wave of seedlings"));
+    }
+
+    #[test]
+    fn test_remove_footnote_backrefs() {
+        let html = r##"<html><head><title>Basil</title></head><body><div id="content">
+<p>Basil is a culinary herb of the mint family, grown for its fragrant leaves in warm climates around the world.<sup id="cite_ref-1"><a href="#cite_note-1">[1]</a></sup></p>
+<p>It bolts quickly in hot weather, so gardeners pinch off the flower buds to keep the leaves sweet and tender.<sup id="cite_ref-2"><a href="#cite_note-2">[2]</a></sup></p>
+<ol class="references">
+<li id="cite_note-1"><span class="mw-cite-backlink"><b><a href="#cite_ref-1">^</a></b></span> <span class="reference-text">Simon, J. E. Basil. Purdue University, 1995.</span></li>
+<li id="cite_note-2"><span class="mw-cite-backlink"><b><a href="#cite_ref-2">^</a></b></span> <span class="reference-text">Growing herbs at home. Garden Press, 2010.</span></li>
+</ol>
+</div></body></html>"##;
+
+        let parse = |remove: bool| {
+            let options = ReadabilityOptions::builder()
+                .char_threshold(100)
+                .remove_footnote_backrefs(remove)
+                .build();
+            parse_with(html, options)
+        };
+
+        let kept = parse(false);
+        assert!(kept.content.unwrap().contains("mw-cite-backlink"));
+        assert!(kept.text_content.unwrap().contains("^ Simon"));
+
+        let article = parse(true);
+        let content = article.content.unwrap();
+        assert!(!content.contains("mw-cite-backlink"));
+        assert!(content.contains("[1]"));
+        let text = article.text_content.unwrap();
+        assert!(!text.contains('^'));
+        assert!(text.contains("Simon, J. E. Basil."));
+        assert!(text.contains("Growing herbs at home."));
+    }
+
+    #[test]
+    fn test_strip_image_credits() {
+        let html = r#"<html><head><title>Herons</title></head><body><article>
+<p>Grey herons hunt the estuary at first light, standing motionless in the shallows until a fish comes close enough to strike.</p>
+<figure><img src="https://example.com/heron.jpg"><figcaption>A heron at dawn. Photo: Getty Images / Jane Doe</figcaption></figure>
+<p>They nest in colonies high in the trees along the river, returning to the same heronry year after year to rebuild old nests.</p>
+</article></body></html>"#;
+
+        let parse = |strip: bool| {
+            let options = ReadabilityOptions::builder()
+                .char_threshold(100)
+                .strip_image_credits(strip)
+                .build();
+            let article = parse_with(html, options);
+            article.content.unwrap()
+        };
+
+        let credit = "<figcaption>A heron at dawn. Photo: Getty Images / Jane Doe</figcaption>";
+        assert!(parse(false).contains(credit));
+        assert!(parse(true).contains("<figcaption>A heron at dawn.</figcaption>"));
+    }
+
+    #[test]
+    fn test_flatten_wrappers() {
+        let html = r#"<html><head><title>Herons</title></head><body><article><div class="story"><div><div><div><div class="story-body">
+<p>Grey herons hunt the estuary at first light, standing motionless in the shallows until a fish comes close enough to strike.</p>
+<p>They nest in colonies high in the trees along the river, returning to the same heronry year after year to rebuild old nests.</p>
+</div></div></div></div></div></article></body></html>"#;
+
+        let parse = |flatten: bool| {
+            let options = ReadabilityOptions::builder()
+                .char_threshold(100)
+                .flatten_wrappers(flatten)
+                .build();
+            let article = parse_with(html, options);
+            article.content.unwrap()
+        };
+
+        assert!(
+            parse(false).contains(r#"<div class="story"><div><div><div><div class="story-body">"#)
+        );
+        let flattened = parse(true);
+        assert!(flattened.contains(r#"<article><div class="story story-body">"#));
+        assert!(flattened.contains("</div></article>"));
+    }
+
+    #[test]
+    fn test_embedded_html_fallback() {
+        let html = r#"<html><head><title>Herons</title></head><body><div id="__next"></div>
+<script id="__NEXT_DATA__" type="application/json">{"props":{"pageProps":{"article":{"body":"<h2>At the estuary<\/h2><p>Grey herons hunt the estuary at first light, standing motionless in the shallows until a fish comes close enough to strike.<\/p><p>They nest in colonies high in the trees along the river, returning to the \"same heronry\" year after year to rebuild old nests.<\/p>"}}}}</script>
+</body></html>"#;
+
+        let parse = |min_length: usize| {
+            let options = ReadabilityOptions::builder()
+                .char_threshold(100)
+                .embedded_html_min_length(min_length)
+                .build();
+            Readability::new(html, None, Some(options))
+                .unwrap()
+                .parse()
+                .and_then(|article| article.text_content)
+                .unwrap_or_default()
+        };
+
+        assert!(!parse(0).contains("Grey herons"));
+        let text = parse(200);
+        assert!(text.contains("Grey herons hunt the estuary"));
+        assert!(text.contains(r#"returning to the "same heronry" year"#));
+        assert!(!parse(10_000).contains("Grey herons"));
+
+        let script_only = html.replace(r#"<div id="__next"></div>"#, "");
+        let options = ReadabilityOptions::builder()
+            .char_threshold(100)
+            .embedded_html_min_length(200)
+            .build();
+        let article = parse_with(&script_only, options);
+        assert!(article.content.unwrap().contains("Grey herons hunt"));
+        // The path would point into the embedded document, not the page.
+        assert_eq!(article.candidate_path, None);
+    }
+
+    #[test]
+    fn test_iframe_srcdoc_fallback() {
+        let html = r#"<html><head><title>Herons</title></head><body><div class="preview">
+<iframe width="800" height="1200" srcdoc="&lt;html&gt;&lt;body&gt;&lt;article&gt;&lt;h2&gt;At the estuary&lt;/h2&gt;&lt;p&gt;Grey herons hunt the estuary at first light, standing motionless in the shallows until a fish comes close enough to strike.&lt;/p&gt;&lt;p&gt;They nest in colonies high in the trees along the river, returning to the &amp;quot;same heronry&amp;quot; year after year.&lt;/p&gt;&lt;/article&gt;&lt;/body&gt;&lt;/html&gt;"></iframe>
+</div></body></html>"#;
+
+        let parse = |min_length: usize| {
+            let options = ReadabilityOptions::builder()
+                .char_threshold(100)
+                .iframe_srcdoc_min_length(min_length)
+                .build();
+            Readability::new(html, None, Some(options))
+                .unwrap()
+                .parse()
+                .and_then(|article| article.text_content)
+                .unwrap_or_default()
+        };
+
+        assert!(!parse(0).contains("Grey herons"));
+        let text = parse(200);
+        assert!(text.contains("Grey herons hunt the estuary"));
+        assert!(text.contains(r#"returning to the "same heronry" year"#));
+        assert!(!parse(10_000).contains("Grey herons"));
+    }
+
+    #[test]
+    fn test_figure_captions_without_images() {
+        let html = r#"<html><head><title>Herons</title></head><body><article>
+<p>Grey herons hunt the estuary at first light, standing motionless in the shallows until a fish comes close enough to strike.</p>
+<figure><blockquote><p>The heron is the patient hunter of the marsh, and it rarely misses.</p></blockquote><figcaption>John Smith, Birds of the Estuary</figcaption></figure>
+<p>They nest in colonies high in the trees along the river, returning to the same heronry year after year to rebuild old nests.</p>
+<figure><figcaption>Map of the heronry</figcaption></figure>
+</article></body></html>"#;
+
+        let parse = |keep: bool| {
+            let options = ReadabilityOptions::builder()
+                .char_threshold(100)
+                .output_markdown(true)
+                .keep_standalone_captions(keep)
+                .build();
+            parse_with(html, options)
+        };
+
+        let quote_caption =
+            "</blockquote><figcaption>John Smith, Birds of the Estuary</figcaption></figure>";
+        let kept = parse(true);
+        let content = kept.content.unwrap();
+        assert!(content.contains(quote_caption));
+        assert!(content.contains("<figcaption>Map of the heronry</figcaption>"));
+        let markdown = kept.markdown_content.unwrap();
+        assert!(markdown.contains("rarely misses.\n\nJohn Smith, Birds of the Estuary"));
+
+        let removed = parse(false).content.unwrap();
+        assert!(removed.contains(quote_caption));
+        assert!(!removed.contains("Map of the heronry"));
+    }
+
+    #[test]
+    fn test_remove_empty_sections() {
+        let html = r#"<html><head><title>Herons</title></head><body><article>
+<p>Grey herons hunt the estuary at first light, standing motionless in the shallows until a fish comes close enough to strike.</p>
+<h2>Watch them hunt</h2>
+<div><iframe src="https://ads.example.com/player"></iframe></div>
+<h2>Nesting</h2>
+<p>They nest in colonies high in the trees along the river, returning to the same heronry year after year to rebuild old nests.</p>
+</article></body></html>"#;
+
+        let parse = |remove: bool| {
+            let options = ReadabilityOptions::builder()
+                .char_threshold(100)
+                .remove_empty_sections(remove)
+                .build();
+            let article = parse_with(html, options);
+            article.content.unwrap()
+        };
+
+        let kept = parse(false);
+        assert!(kept.contains("<h2>Watch them hunt</h2>"));
+        assert!(!kept.contains("<iframe"));
+
+        let removed = parse(true);
+        assert!(!removed.contains("Watch them hunt"));
+        assert!(removed.contains("<h2>Nesting</h2>"));
+    }
+
+    #[test]
+    fn test_clean_fragment_fast_path() {
+        let fragment = r#"<p>Grey herons hunt by standing still for long stretches, waiting for fish to come within reach.</p>
+<div class="gallery"><img alt="Heron wading" src="/a.jpg"><img alt="Heron in flight" src="/b.jpg"><img alt="Heron nest" src="/c.jpg"></div>
+<p>They nest in colonies high in the trees along the river, returning to the same heronry each year.</p>"#;
+
+        let parse = |html: &str, max_elements: usize| {
+            let options = ReadabilityOptions::builder()
+                .char_threshold(100)
+                .clean_fragment_max_elements(max_elements)
+                .build();
+            parse_with(html, options)
+        };
+
+        // Scoring drops the image-only gallery as boilerplate.
+        assert!(!parse(fragment, 0).content.unwrap().contains("gallery"));
+
+        let article = parse(fragment, 50);
+        assert_eq!(article.content.as_deref(), Some(fragment));
+        // The whole input was taken, so there is no candidate to exclude.
+        assert_eq!(article.candidate_path, None);
+
+        // Too many elements, page layout or a full document: scored as usual.
+        assert!(!parse(fragment, 5).content.unwrap().contains("gallery"));
+        let with_nav = format!(r#"<nav><a href="/">Home</a></nav>{fragment}"#);
+        assert!(!parse(&with_nav, 50).content.unwrap().contains("gallery"));
+        let document = format!("<html><body>{fragment}</body></html>");
+        assert!(!parse(&document, 50).content.unwrap().contains("gallery"));
+    }
+
+    #[test]
+    fn test_convert_brs_disabled_keeps_line_breaks() {
+        let html = r#"<html><head><title>The Heron</title></head><body><article>
+<p>This short poem was written on the estuary path at dawn, while the tide was still running out past the heronry.</p>
+<p>Grey on the grey of the morning,<br>
+still as the post he stands on,<br>
+he waits for the river to bring him.<br>
+<br>
+Then the beak, the flash, the lifting,<br>and the tide goes out without him.</p>
+</article></body></html>"#;
+
+        let parse = |convert: bool| {
+            let options = ReadabilityOptions::builder()
+                .char_threshold(100)
+                .convert_brs(convert)
+                .build();
+            parse_with(html, options)
+        };
+
+        let converted = parse(true).content.unwrap();
+        assert!(converted.contains("bring him.</p>\n <p>Then the beak"));
+
+        let kept = parse(false);
+        let content = kept.content.unwrap();
+        assert!(content.contains("<p>Grey on the grey of the morning,<br>"));
+        assert!(
+            content.contains("bring him.<br>\n<br>\nThen the beak, the flash, the lifting,<br>and")
+        );
+        let text = kept.text_content.unwrap();
+        assert!(text.contains(
+            "Grey on the grey of the morning,\nstill as the post he stands on,\n\
+             he waits for the river to bring him.\n\nThen the beak, the flash, the lifting,\n\
+             and the tide goes out without him."
+        ));
+    }
+
+    #[test]
+    fn test_paragraphs_wrapped_in_card_links() {
+        let html = r#"<html><head><title>Herons of the estuary</title></head><body>
+<nav><ul><li><a href="/">Home</a></li><li><a href="/birds">Birds</a></li><li><a href="/about">About</a></li></ul></nav>
+<div class="main">
+<a href="/story/herons"><p>Grey herons have returned to the estuary in numbers not seen for a decade, according to a survey published by the county wildlife trust on Tuesday.</p></a>
+<a href="/story/herons"><p>Volunteers counted forty-two nesting pairs in the pines above the river, up from just nine pairs when the trust began its annual count in 2016.</p></a>
+<a href="/story/herons"><p>The recovery follows a decade of work to clean up the river, which was once among the most polluted in the region, and to stop boats disturbing the heronry.</p></a>
+</div>
+</body></html>"#;
+
+        let options = ReadabilityOptions::builder().char_threshold(100).build();
+        let article = parse_with(html, options);
+        let content = article.content.unwrap();
+        assert!(content.contains("Grey herons have returned to the estuary"));
+        assert!(content.contains("forty-two nesting pairs"));
+        assert!(content.contains("stop boats disturbing the heronry"));
+        assert!(!content.contains("About"));
+    }
+
+    #[test]
+    fn test_custom_preserve_whitespace_tag() {
+        let html = r#"<html><head><title>Drawing herons</title></head><body><article>
+<p>Grey herons are easy to draw in plain text, as long as the spacing of every line is kept exactly as it was typed.</p>
+<p><samp>   __
+  (o &gt;
+   \\  <br><br>    ||</samp></p>
+<pre>legs    long
+neck    longer</pre>
+<p>They nest in colonies high in the trees along the river, returning to the same heronry year after year.</p>
+</article></body></html>"#;
+
+        let parse = |tags: &[&str]| {
+            let options = ReadabilityOptions::builder()
+                .char_threshold(100)
+                .preserve_whitespace_tags(tags.iter().map(|tag| tag.to_string()).collect())
+                .build();
+            parse_with(html, options)
+        };
+
+        let default = parse(&["pre", "textarea"]);
+        let content = default.content.unwrap();
+        assert!(content.contains("<pre>legs    long\nneck    longer</pre>"));
+        assert!(!content.contains("<samp>   __"));
+
+        let article = parse(&["pre", "textarea", "samp"]);
+        let content = article.content.unwrap();
+        assert!(content.contains("<samp>   __\n  (o &gt;\n   \\\\  <br><br>    ||</samp>"));
+        let text = article.text_content.unwrap();
+        assert!(text.contains("   __\n  (o >\n   \\\\  \n\n    ||"));
+        assert!(text.contains("legs    long\nneck    longer"));
+    }
+
+    #[test]
+    fn test_scene_breaks_and_emoji_paragraphs() {
+        let html = r#"<html><head><title>The Heron</title></head><body><article>
+<p>The heron stood on the post at the edge of the estuary for most of the morning, watching the water run out over the mud.</p>
+<p>* * *</p>
+<p>By evening the tide had turned, and the heron was gone from the post, back to the heronry in the pines across the river.</p>
+<p>👏👏 🔥</p>
+</article></body></html>"#;
+
+        let parse = |enabled: bool| {
+            let options = ReadabilityOptions::builder()
+                .char_threshold(100)
+                .convert_scene_breaks(enabled)
+                .remove_emoji_paragraphs(enabled)
+                .build();
+            parse_with(html, options).content.unwrap()
+        };
+
+        let kept = parse(false);
+        assert!(kept.contains("<p>* * *</p>"));
+        assert!(kept.contains("<p>👏👏 🔥</p>"));
+
+        let content = parse(true);
+        assert!(content.contains("over the mud.</p>\n<hr>\n<p>By evening"));
+        assert!(!content.contains("👏"));
+    }
+
+    #[test]
+    fn test_custom_text_normalizer() {
+        let html = r#"<html><head><title>Estuary notes</title></head><body><article>
+<p>The herons ﬁsh the estuary at dawn, standing still in the shallows until the tide brings the ﬁrst small ﬁsh within reach.</p>
+<p>We counted ４２ birds on the mudﬂats this morning, the highest count since the survey began.</p>
+</article></body></html>"#;
+
+        // NFKC-style folding of ligatures and full-width digits.
+        let fold = |text: &str| {
+            text.chars()
+                .flat_map(|c| match c {
+                    'ﬁ' => vec!['f', 'i'],
+                    'ﬂ' => vec!['f', 'l'],
+                    '０'..='９' => vec![char::from_u32(c as u32 - 0xFEE0).unwrap()],
+                    _ => vec![c],
+                })
+                .collect::<String>()
+        };
+        let parse = |options: ReadabilityOptions| parse_with(html, options);
+
+        let default = parse(ReadabilityOptions::builder().char_threshold(100).build());
+        assert!(default.text_content.unwrap().contains("ﬁsh the estuary"));
+
+        let article = parse(
+            ReadabilityOptions::builder()
+                .char_threshold(100)
+                .text_normalizer(fold)
+                .build(),
+        );
+        let text = article.text_content.unwrap();
+        assert!(text.contains("The herons fish the estuary"));
+        assert!(text.contains("We counted 42 birds on the mudflats"));
+    }
+
+    #[test]
+    fn test_documentation_profile() {
+        // Docusaurus layout: sidebar, content column and "on this page" rail.
+        let html = r##"<html><head><title>Configuration | Heron Docs</title></head><body>
+<nav class="navbar"><a href="/">Heron</a><a href="/docs">Docs</a><a href="/blog">Blog</a></nav>
+<div class="main-wrapper"><div class="docPage">
+<aside class="theme-doc-sidebar-container"><div class="sidebar_menu"><ul class="menu__list">
+<li><a href="/docs/intro">Introduction to Heron and why you might want to use it</a></li>
+<li><a href="/docs/install">Installing Heron on Linux, macOS and Windows machines</a></li>
+<li><a href="/docs/config">Configuration reference for every option Heron supports</a></li>
+<li><a href="/docs/cli">Command line interface and the flags each command accepts</a></li>
+<li><a href="/docs/plugins">Writing plugins that extend the build with custom steps</a></li>
+</ul></div></aside>
+<main class="docMainContainer"><div class="container"><div class="row">
+<div class="col docItemCol"><article>
+<nav class="theme-doc-breadcrumbs"><a href="/docs">Docs</a> › Configuration</nav>
+<div class="theme-doc-toc-mobile tocCollapsible"><button>On this page</button><ul class="table-of-contents"><li><a href="#file">Config file</a></li><li><a href="#options">Options</a></li></ul></div>
+<div class="theme-doc-markdown markdown"><header><h1>Configuration</h1></header>
+<p>Heron reads its settings from a file at the root of your project. Settings from the command line override the file.</p>
+<h2 id="file">Config file</h2>
+<div class="codeBlockContainer"><div class="codeBlockContent"><pre class="prism-code language-toml"><code>[build]
+out_dir = "dist"
+jobs = 4</code></pre><button class="copyButton">Copy</button></div></div>
+<h2 id="options">Options</h2>
+<table><thead><tr><th>Option</th><th>Default</th></tr></thead><tbody><tr><td><code>out_dir</code></td><td><a href="#file">dist</a></td></tr><tr><td><code>jobs</code></td><td><a href="#file">4</a></td></tr></tbody></table>
+<dl><dt><code>out_dir</code></dt><dd>Where builds go.</dd><dt><code>jobs</code></dt><dd>Parallel jobs.</dd></dl>
+</div>
+<div class="theme-doc-footer"><a class="theme-edit-this-page" href="https://github.com/heron/edit/config.md">Edit this page</a></div>
+</article>
+<nav class="pagination-nav"><a href="/docs/install">Previous: Installing</a><a href="/docs/cli">Next: CLI</a></nav>
+</div>
+<div class="col col--3"><div class="tableOfContents"><ul class="table-of-contents"><li><a href="#file">Config file</a></li><li><a href="#options">Options</a></li></ul></div></div>
+</div></div></main></div></div>
+<footer class="footer">Copyright Heron</footer>
+</body></html>"##;
+
+        let parse = |profile: ContentProfile| {
+            let options = ReadabilityOptions::builder()
+                .char_threshold(100)
+                .content_profile(profile)
+                .build();
+            parse_with(html, options)
+        };
+
+        let general = parse(ContentProfile::General).content.unwrap();
+        assert!(general.contains("Edit this page"));
+
+        let article = parse(ContentProfile::Documentation);
+        assert!(article.candidate_path.unwrap().ends_with("/article"));
+        let content = article.content.unwrap();
+        assert!(content.contains("<h1>Configuration</h1>"));
+        assert!(content.contains(r#"<h2 id="options">Options</h2>"#));
+        assert!(content.contains("<pre class=\"prism-code language-toml\"><code>[build]"));
+        assert!(content.contains("<table><thead><tr><th>Option</th>"));
+        assert!(content.contains("<dl><dt><code>out_dir</code></dt><dd>Where builds go.</dd>"));
+        assert!(!content.contains("Introduction to Heron"));
+        assert!(!content.contains("On this page"));
+        assert!(!content.contains("Edit this page"));
+        assert!(!content.contains("Next: CLI"));
+    }
+
+    #[test]
+    fn test_news_profile() {
+        let html = r#"<html><head><title>River herons return to the estuary | The Daily Tide</title>
+<meta property="og:image" content="https://tide.example.com/img/herons.jpg">
+<meta name="author" content="Jane Marsh">
+<meta property="article:published_time" content="2026-03-04T08:00:00Z">
+</head><body>
+<header class="site-header"><a href="/">The Daily Tide</a><ul><li><a href="/news">News</a></li><li><a href="/sport">Sport</a></li></ul></header>
+<main><article class="story">
+<h1>River herons return to the estuary</h1>
+<p class="byline">By Jane Marsh</p>
+<figure class="lead-image"><img src="https://tide.example.com/img/herons.jpg" alt="Herons on the mudflats"><figcaption>Herons on the mudflats at dawn.</figcaption></figure>
+<div class="story-body">
+<p>Grey herons have returned to the estuary in numbers not seen for a decade, according to a survey published by the county wildlife trust on Tuesday morning.</p>
+<p>Volunteers counted forty-two nesting pairs in the pines above the river, up from just nine pairs when the trust began its annual count in 2016.</p>
+</div>
+<div class="ad-slot" id="div-gpt-ad-1"><p>Advertisement</p></div>
+<div class="story-body">
+<p>The recovery follows a decade of work to clean up the river, which was once among the most polluted in the region, and to stop boats disturbing the heronry.</p>
+<aside class="related-links"><h3>Related</h3><ul><li><a href="/news/otters">Otters spotted upstream for the first time in years</a></li><li><a href="/news/flood">Flood defences approved for the lower town</a></li></ul></aside>
+<p><strong>Read more:</strong> <a href="/news/kingfishers">Kingfishers nest on the canal</a></p>
+<p>"It is the best news we have had in years," said the trust's director, who added that the count would continue every spring.</p>
+</div>
+<div class="newsletter-signup"><h3>Get the Tide in your inbox</h3><p>Sign up for our free daily newsletter with the top stories from the coast.</p><form><input type="email"><button>Sign up</button></form></div>
+<div class="story-body">
+<p>Local schools will take part in next year's count, and the trust hopes to fit cameras to two of the nests so that pupils can watch the chicks hatch in real time.</p>
+</div>
+</article>
+<section class="recirculation most-read"><h2>Most read</h2><ol><li><a href="/a">Council approves new harbour plan after long debate</a></li><li><a href="/b">Storm warning issued for the weekend across the coast</a></li><li><a href="/c">Ferry timetable changes from Monday for winter</a></li></ol></section>
+</main>
+<footer>© The Daily Tide</footer>
+</body></html>"#;
+
+        let parse = |profile: ContentProfile| {
+            let options = ReadabilityOptions::builder()
+                .char_threshold(100)
+                .content_profile(profile)
+                .build();
+            parse_with(html, options)
+        };
+
+        let general = parse(ContentProfile::General).content.unwrap();
+        assert!(general.contains("Sign up for our free daily newsletter"));
+
+        let article = parse(ContentProfile::News);
+        let content = article.content.unwrap();
+        assert!(content.contains("<figcaption>Herons on the mudflats at dawn.</figcaption>"));
+        assert!(content.contains("Grey herons have returned to the estuary"));
+        assert!(content.contains("a decade of work to clean up the river"));
+        assert!(content.contains("fit cameras to two of the nests"));
+        assert!(!content.contains("Advertisement"));
+        assert!(!content.contains("Otters spotted upstream"));
+        assert!(!content.contains("Read more:"));
+        assert!(!content.contains("Sign up for our free daily newsletter"));
+        assert!(!content.contains("Most read"));
+        assert_eq!(article.byline.as_deref(), Some("Jane Marsh"));
+        assert!(article.published_time.is_some());
+        assert_eq!(
+            article.image.as_deref(),
+            Some("https://tide.example.com/img/herons.jpg")
+        );
+    }
+
+    #[test]
+    fn test_text_only_cookie_banner_is_removed() {
+        let html = r#"<html><head><title>Herons of the estuary</title></head><body><div>
+<div><p>We use cookies to personalise content and analyse our traffic. You can change your choice at any time.</p>
+<div><button>Accept all</button><button>Reject all</button><a href="/cookies">Manage preferences</a></div></div>
+<p>Grey herons have returned to the estuary in numbers not seen for a decade, according to a survey published by the county wildlife trust.</p>
+<p>Volunteers counted forty-two nesting pairs in the pines above the river, up from just nine pairs when the trust began its annual count.</p>
+<p>The recovery follows a decade of work to clean up the river, which was once among the most polluted in the region.</p>
+</div></body></html>"#;
+
+        let parse = |remove: bool| {
+            let options = ReadabilityOptions::builder()
+                .char_threshold(100)
+                .remove_consent_banners(remove)
+                .build();
+            parse_with(html, options).content.unwrap()
+        };
+
+        let content = parse(true);
+        assert!(!content.contains("We use cookies"));
+        assert!(!content.contains("Manage preferences"));
+        assert!(content.contains("Grey herons have returned to the estuary"));
+        assert!(content.contains("a decade of work to clean up the river"));
+
+        assert!(parse(false).contains("We use cookies"));
+    }
+
+    #[test]
+    fn test_title_falls_back_to_first_heading() {
+        let html = format!(
+            r#"<html><head></head><body><article>
+<h1>Spring planting notes</h1>
+{GARDEN_PARAGRAPHS}
+</article></body></html>"#
+        );
+
+        let options = ReadabilityOptions::builder().char_threshold(100).build();
+        let article = parse_with(&html, options);
+        assert_eq!(article.title.as_deref(), Some("Spring planting notes"));
+    }
+
+    #[test]
+    fn test_merge_split_lists() {
+        let html = format!(
+            r#"<html><body><article>
+{GARDEN_PARAGRAPHS}
+<ol><li>Harden off the seedlings for a week.</li><li>Water the bed the evening before.</li></ol>
+<div class="ad-slot"><p>Advertisement</p></div>
+<ol start="3"><li>Plant in the cool of the morning.</li></ol>
+</article></body></html>"#
+        );
+
+        let parse = |merge: bool| {
+            let options = ReadabilityOptions::builder()
+                .char_threshold(100)
+                .merge_split_lists(merge)
+                .build();
+            let article = parse_with(&html, options);
+            article.content.unwrap()
+        };
+
+        let content = parse(true);
+        assert!(!content.contains("Advertisement"));
+        assert_eq!(content.matches("<ol").count(), 1);
+        assert!(content.contains("<li>Plant in the cool of the morning.</li></ol>"));
+        assert_eq!(parse(false).matches("<ol").count(), 2);
+    }
+
+    #[test]
+    fn test_byline_is_removed_from_content() {
+        let html = format!(
+            r#"<html><head><title>Spring planting notes</title></head><body><article>
+<p class="byline">By <a href="/authors/jane" rel="author">Jane Marsh</a></p>
+{GARDEN_PARAGRAPHS}
+</article></body></html>"#
+        );
+
+        let options = ReadabilityOptions::builder().char_threshold(100).build();
+        let article = parse_with(&html, options);
+        assert_eq!(article.byline.as_deref(), Some("By Jane Marsh"));
+        let content = article.content.unwrap();
+        assert!(!content.contains("Jane Marsh"));
+        assert!(content.contains("first wave of seedlings"));
+    }
+
+    #[test]
+    fn test_relative_urls_are_resolved() {
+        let html = r#"<html><head><title>Herons</title></head><body><article>
+<p>Grey herons hunt the estuary at first light, standing motionless in the shallows until a fish comes close enough to strike. <a href="/about">About us</a>.</p>
+<figure><img src="images/x.png" alt="A heron"><figcaption>A heron at dawn.</figcaption></figure>
+<p>They nest in colonies high in the trees along the river, returning to the same heronry year after year to rebuild old nests.</p>
+</article></body></html>"#;
+
+        let options = ReadabilityOptions::builder().char_threshold(100).build();
+        let base = "https://example.com/articles/2024/post";
+        let content = Readability::new(html, Some(base), Some(options))
+            .unwrap()
+            .parse()
+            .unwrap()
+            .content
+            .unwrap();
+        assert!(content.contains(r#"href="https://example.com/about""#));
+        assert!(content.contains(r#"src="https://example.com/articles/2024/images/x.png""#));
+    }
+
+    #[test]
+    fn test_parse_within_selector() {
+        let html = r#"<html><head><title>Herons</title></head><body>
+<div class="intro"><p>Grey herons hunt the estuary at first light, standing motionless in the shallows until a fish comes close enough to strike, and so on.</p>
+<p>They nest in colonies high in the trees along the river, returning to the same heronry year after year to rebuild old nests.</p></div>
+<article class="post"><p>Herons are back. <img src="/img/heron.jpg" alt="A heron"> <a href="/count">See the count</a>.</p></article>
+</body></html>"#;
+        let readability = |fallback: bool| {
+            let options = ReadabilityOptions::builder()
+                .char_threshold(100)
+                .selector_fallback(fallback)
+                .build();
+            Readability::new(html, Some("https://example.com/news/"), Some(options)).unwrap()
+        };
+
+        let article = readability(true)
+            .parse_within("article.post")
+            .unwrap()
+            .unwrap();
+        let content = article.content.unwrap();
+        assert!(content.contains(r#"<p>Herons are back. <img alt="A heron""#));
+        assert!(!content.contains("Grey herons hunt"));
+        assert_eq!(article.title.as_deref(), Some("Herons"));
+
+        let fallback = readability(true).parse_within("main").unwrap().unwrap();
+        assert!(fallback.content.unwrap().contains("Grey herons hunt"));
+        assert!(matches!(
+            readability(false).parse_within("main"),
+            Err(ReadabilityError::SelectorNotFound(_))
+        ));
+        assert!(matches!(
+            readability(true).parse_within("article[["),
+            Err(ReadabilityError::InvalidSelector(_))
+        ));
+    }
+
+    #[test]
+    fn test_svg_policy() {
+        let html = r#"<html><head><title>Herons</title></head><body><article>
+<p>Grey herons hunt the estuary at first light, standing motionless in the shallows until a fish comes close enough to strike. <svg class="icon" viewBox="0 0 16 16" width="16" height="16"><path d="M0 0h16v16H0z"/></svg></p>
+<figure><svg viewBox="0 0 400 200" role="img"><title>Heron count by year</title><rect x="10" y="150" width="40" height="50"/><rect x="60" y="20" width="40" height="180"/></svg><figcaption>Nesting pairs by year.</figcaption></figure>
+<p>They nest in colonies high in the trees along the river, returning to the same heronry year after year to rebuild old nests.</p>
+</article></body></html>"#;
+
+        let parse = |policy: SvgPolicy| {
+            let options = ReadabilityOptions::builder()
+                .char_threshold(100)
+                .svg_policy(policy)
+                .build();
+            parse_with(html, options).content.unwrap()
+        };
+
+        let content = parse(SvgPolicy::StripDecorative);
+        assert!(!content.contains(r#"<svg class="icon""#));
+        assert!(content.contains("<title>Heron count by year</title>"));
+        assert!(parse(SvgPolicy::KeepAll).contains(r#"<svg class="icon""#));
+        assert!(!parse(SvgPolicy::StripAll).contains("<svg"));
+    }
+
+    #[test]
+    fn test_open_details_keeps_spoilers_closed() {
+        let html = r#"<html><head><title>Review: The Heron King</title></head><body><article>
+<p>The Heron King is the best novel of the season, a slow and patient story about a family of river keepers and the birds they watch over.</p>
+<details class="faq"><summary>How long is the book?</summary><p>Just over four hundred pages, in three parts.</p></details>
+<details open><summary>Spoiler: the ending</summary><p>The heron king is the keeper's missing brother.</p></details>
+<p>Whether or not you know how it ends, the last chapter is worth the whole journey, and the book earns a place on any shelf.</p>
+</article></body></html>"#;
+
+        let parse = |open: bool| {
+            let options = ReadabilityOptions::builder()
+                .char_threshold(100)
+                .open_details(open)
+                .build();
+            parse_with(html, options).content.unwrap()
+        };
+
+        let content = parse(true);
+        let faq = r#"<details class="faq" open><summary>How long is the book?</summary>"#;
+        assert!(content.contains(faq));
+        assert!(content.contains(
+            "<details data-spoiler><summary>Spoiler: the ending</summary>\
+             <p>The heron king is the keeper's missing brother.</p></details>"
+        ));
+
+        let untouched = parse(false);
+        assert!(untouched.contains(r#"<details class="faq"><summary>"#));
+        assert!(untouched.contains(r#"<details open=""><summary>Spoiler"#));
+    }
+
+    #[test]
+    fn test_block_output() {
+        let html = r#"<html><head><title>Herons</title></head><body><article>
+<p>Grey herons hunt the estuary at first light, standing motionless in the shallows until a fish comes close enough to strike.</p>
+<h2>Nesting</h2>
+<p>They nest in colonies high in the trees along the river, returning to the same heronry year after year to rebuild old nests.</p>
+<figure><img src="https://example.com/heron.jpg" alt="A heron"><figcaption>A heron at dawn.</figcaption></figure>
+<ul><li>Sticks</li><li>Reeds</li></ul>
+</article></body></html>"#;
+
+        let options = ReadabilityOptions::builder()
+            .char_threshold(100)
+            .output_blocks(true)
+            .build();
+        let article = parse_with(html, options);
+
+        let json = serde_json::to_value(article.blocks.unwrap()).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!([
+                {"type": "paragraph", "text": "Grey herons hunt the estuary at first light, standing motionless in the shallows until a fish comes close enough to strike."},
+                {"type": "heading", "level": 2, "text": "Nesting"},
+                {"type": "paragraph", "text": "They nest in colonies high in the trees along the river, returning to the same heronry year after year to rebuild old nests."},
+                {"type": "image", "src": "https://example.com/heron.jpg", "alt": "A heron", "caption": "A heron at dawn."},
+                {"type": "list", "ordered": false, "items": ["Sticks", "Reeds"]}
+            ])
+        );
+
+        let default = Readability::new(html, None, None).unwrap().parse().unwrap();
+        assert!(default.blocks.is_none());
+    }
+
+    #[test]
+    fn test_plain_text_output() {
+        let html = r#"<html><head><title>Herons</title></head><body><article>
+<p>Grey herons hunt the estuary at first light, standing motionless in the shallows until a fish comes close enough to strike.</p><h2>Nesting</h2><p>They nest in colonies high in the trees along the river, returning to the <em>same heronry</em> year after year.</p>
+</article></body></html>"#;
+
+        let options = ReadabilityOptions::builder()
+            .char_threshold(100)
+            .output_plain_text(true)
+            .build();
+        let article = parse_with(html, options);
+        assert_eq!(
+            article.plain_text.as_deref(),
+            Some("Grey herons hunt the estuary at first light, standing motionless in the shallows until a fish comes close enough to strike.\nNesting\nThey nest in colonies high in the trees along the river, returning to the same heronry year after year.")
+        );
+
+        let default = Readability::new(html, None, None).unwrap().parse().unwrap();
+        assert!(default.plain_text.is_none());
+    }
+
+    #[test]
+    fn test_amp_output() {
+        let html = r#"<html><head><title>Herons</title></head><body><article>
+<p>Grey herons hunt the estuary at first light, standing motionless in the shallows until a fish comes close enough to strike.</p>
+<figure><img src="/images/heron.jpg" alt="A heron" width="800" height="600" style="border: 0"><figcaption>A heron at dawn.</figcaption></figure>
+<p>They nest in colonies high in the trees along the river, returning to the same heronry year after year to rebuild old nests.</p>
+</article></body></html>"#;
+
+        let options = ReadabilityOptions::builder()
+            .char_threshold(100)
+            .output_amp(true)
+            .build();
+        let article = parse_with(html, options);
+
+        let amp = article.amp_content.unwrap();
+        assert!(amp.contains(
+            r#"<figure><amp-img src="/images/heron.jpg" alt="A heron" width="800" height="600" layout="responsive"></amp-img><figcaption>A heron at dawn.</figcaption></figure>"#
+        ));
+        assert!(!amp.contains("<img"));
+        assert!(amp.contains("<p>Grey herons hunt the estuary"));
+        assert!(article.content.unwrap().contains("<img"));
+    }
+
+    #[test]
+    fn test_clean_without_extraction() {
+        let html = r#"<div class="story">
+<p style="color: white">Spring has finally arrived, and with it the first wave of seedlings.</p>
+<p> </p>
+<div class="ad-banner"><a href="/buy">Buy seeds</a> <a href="/sale">Spring sale</a> <a href="/offers">Offers</a></div>
+<form><input type="email"><button>Subscribe</button></form>
+<p>Short note.</p>
+</div>"#;
+
+        let cleaned = clean(html, None, None);
+        assert!(cleaned.contains("<p>Spring has finally arrived"));
+        assert!(!cleaned.contains("color: white"));
+        assert!(!cleaned.contains("<p> </p>"));
+        assert!(!cleaned.contains("Buy seeds"));
+        assert!(!cleaned.contains("Subscribe"));
+        // No scoring: short paragraphs the extractor would skip are kept.
+        assert!(cleaned.contains("<p>Short note.</p>"));
+
+        let options = ReadabilityOptions::builder().clean_styles(false).build();
+        assert!(clean(html, None, Some(options)).contains(r#"style="color: white""#));
+    }
+
+    #[test]
+    fn test_include_light_content() {
+        let html = r#"<html><head><title>Garden notes</title></head><body><article>
+<nav><a href="/">Home</a> <a href="/garden">Garden</a></nav>
+<p style="font-size: 18px">Spring has finally arrived, and with it the first wave of seedlings. We spent the week transplanting tomatoes, peppers, and a few experimental squash varieties.</p>
+<ul class="promo"><li><a href="/buy">Buy seeds</a></li><li><a href="/sale">Spring sale</a></li><li><a href="/offers">Offers</a></li></ul>
+<p>The soil temperature matters more than the air temperature, so we waited until our thermometer read a steady fifteen degrees before moving anything outside.</p>
+</article></body></html>"#;
+
+        let parse = |include: bool| {
+            let options = ReadabilityOptions::builder()
+                .char_threshold(100)
+                .include_light_content(include)
+                .build();
+            parse_with(html, options)
+        };
+
+        assert_eq!(parse(false).light_content, None);
+
+        let article = parse(true);
+        let content = article.content.unwrap();
+        let light = article.light_content.unwrap();
+        for html in [&content, &light] {
+            assert!(!html.contains("<nav>"));
+            assert!(html.contains("first wave of seedlings"));
+        }
+        assert!(light.contains(r#"style="font-size: 18px""#));
+        assert!(light.contains("Buy seeds"));
+        assert!(!content.contains("font-size"));
+        assert!(!content.contains("Buy seeds"));
+    }
+
+    #[test]
+    fn test_strip_text_breadcrumbs() {
+        let html = format!(
+            r#"<html><head><title>Raised beds</title></head><body><div class="story">
+<p><a href="/">Home</a> › <a href="/garden">Garden</a> › Raised beds</p>
+<h2>Building the frame</h2>
+{GARDEN_PARAGRAPHS}
+</div></body></html>"#
+        );
+
+        let parse = |strip: bool| {
+            let options = ReadabilityOptions::builder()
+                .char_threshold(100)
+                .strip_text_breadcrumbs(strip)
+                .build();
+            parse_with(&html, options)
+        };
+
+        assert!(parse(false).text_content.unwrap().contains("Home › Garden"));
+
+        let article = parse(true);
+        let content = article.content.unwrap();
+        assert!(!content.contains("Home"));
+        assert!(content.contains("<h2>Building the frame</h2>"));
+        assert!(article
+            .text_content
+            .unwrap()
+            .trim_start()
+            .starts_with("Building the frame"));
+    }
+
+    #[test]
+    fn test_target_width_picks_responsive_source() {
+        let html = format!(
+            r#"<html><head><title>Garden notes</title></head><body><article>
+{GARDEN_PARAGRAPHS}
+<picture><source media="(max-width: 600px)" srcset="https://example.com/bed-480.jpg 480w, https://example.com/bed-960.jpg 960w"><img src="https://example.com/bed.jpg" srcset="https://example.com/bed-1600.jpg 1600w, https://example.com/bed-4000.jpg 4000w" alt="Raised bed"></picture>
+<p>The soil temperature matters more than the air temperature, so we waited until our thermometer read a steady fifteen degrees before moving anything outside.</p>
+</article></body></html>"#
+        );
+
+        let parse = |target_width: Option<u32>| {
+            let mut builder = ReadabilityOptions::builder()
+                .char_threshold(100)
+                .output_markdown(true);
+            if let Some(width) = target_width {
+                builder = builder.target_width(width);
+            }
+            let options = builder.build();
+            parse_with(&html, options)
+        };
+
+        let article = parse(Some(480));
+        let content = article.content.unwrap();
+        assert!(content.contains(r#"src="https://example.com/bed-480.jpg""#));
+        assert!(content.contains(r#"<source media="(max-width: 600px)""#));
+        assert!(article
+            .markdown_content
+            .unwrap()
+            .contains("](https://example.com/bed-480.jpg)"));
+
+        let article = parse(None);
+        assert!(article
+            .content
+            .unwrap()
+            .contains(r#"src="https://example.com/bed.jpg""#));
+        assert!(article
+            .markdown_content
+            .unwrap()
+            .contains("](https://example.com/bed-4000.jpg)"));
+    }
+
+    #[test]
+    fn test_strict_mode_skips_short_pages() {
+        let html = r#"<html><head><title>Garden notes</title></head><body><article>
+<p>Spring has finally arrived, and with it the first wave of seedlings in the raised beds.</p>
+<p>More notes on transplanting will follow next week, once the weather settles down.</p>
+</article></body></html>"#;
+
+        let parse = |strict: bool| {
+            let options = ReadabilityOptions::builder().strict(strict).build();
+            Readability::new(html, None, Some(options)).unwrap().parse()
+        };
+
+        let article = parse(false).unwrap();
+        assert!(article.length < 500);
+        assert!(article.content.unwrap().contains("first wave of seedlings"));
+
+        assert!(parse(true).is_none());
+    }
+
+    #[test]
+    fn test_mixed_br_spellings() {
+        let html = format!(
+            r#"<html><head><title>Garden notes</title></head><body><article>
+{GARDEN_PARAGRAPHS}
+<BR CLEAR="all"><br />
+<p>The soil temperature matters more than the air temperature, so we waited until our thermometer read a steady fifteen degrees before moving anything outside.</p>
+<p class="note"><span><Br class="spacer"></span></p>
+<div>Tomatoes went in first.<BR/><br clear=left>Peppers followed a week later, once the nights stayed warm enough for them.</div>
+</article></body></html>"#
+        );
+
+        let options = ReadabilityOptions::builder().char_threshold(100).build();
+        let content = parse_with(&html, options).content.unwrap();
+
+        assert!(!content.to_lowercase().contains("<br"));
+        assert!(!content.contains(r#"class="note""#));
+        assert!(content.contains("<p>Tomatoes went in first.</p>"));
+    }
+
+    #[test]
+    fn test_malformed_html_is_repaired() {
+        let body = format!(
+            r#"<article>
+{GARDEN_PARAGRAPHS}
+</article>"#
+        );
+        let options = || ReadabilityOptions::builder().char_threshold(100).build();
+
+        for html in [
+            format!("<html><head><title>Garden notes</head><body>{body}</body></html>"),
+            format!("<html><head></head><body><!-- sidebar {body}</body></html>"),
+            format!("<html><head><style>p {{ color: red }}</head><body><textarea>{body}"),
+        ] {
+            let article = parse_with(&html, options());
+            assert!(article.content.unwrap().contains("first wave of seedlings"));
+        }
+
+        let frameset = format!("<html><frameset><frame src=\"a.html\"></frameset>{body}</html>");
+        assert!(Readability::new(&frameset, None, Some(options())).is_ok());
+
+        for valid in [
+            "<html></html>",
+            "<html><head><title>Garden notes</title></head><body></body></html>",
+            r#"<html><body><script type="application/json">{"page":1}</script></body></html>"#,
+        ] {
+            assert!(Readability::new(valid, None, None).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_infer_site_name() {
+        let page = |title: &str| {
+            format!(
+                r#"<html><head><title>{title}</title>
+<meta property="og:title" content="Spring planting notes | Garden Weekly"></head><body><article>
+{GARDEN_PARAGRAPHS}
+</article></body></html>"#
+            )
+        };
+        let parse = |html: &str, url: Option<&str>, infer: bool| {
+            let options = ReadabilityOptions::builder()
+                .char_threshold(100)
+                .infer_site_name(infer)
+                .build();
+            Readability::new(html, url, Some(options))
+                .unwrap()
+                .parse()
+                .unwrap()
+        };
+        let url = Some("https://www.gardenweekly.example/notes");
+
+        let article = parse(&page("Garden notes"), url, false);
+        assert_eq!(article.site_name, None);
+
+        // The hostname is the last resort.
+        let article = parse(&page("Garden notes"), url, true);
+        assert_eq!(article.site_name.as_deref(), Some("gardenweekly.example"));
+        assert_eq!(
+            article.title.as_deref(),
+            Some("Spring planting notes | Garden Weekly")
+        );
+
+        // A site name from the title is also stripped from the og:title.
+        let article = parse(&page("Spring planting notes | Garden Weekly"), url, true);
+        assert_eq!(article.site_name.as_deref(), Some("Garden Weekly"));
+        assert_eq!(article.title.as_deref(), Some("Spring planting notes"));
+    }
+
+    #[test]
+    fn test_write_content_matches_content() {
+        let html = format!(
+            r#"<html><head><title>Garden notes</title></head><body><article>
+{GARDEN_PARAGRAPHS}
+</article></body></html>"#
+        );
+
+        let options = ReadabilityOptions::builder().char_threshold(100).build();
+        let article = parse_with(&html, options);
+
+        let mut written = Vec::new();
+        article.write_content(&mut written).unwrap();
+        assert_eq!(Some(String::from_utf8(written).unwrap()), article.content);
+
+        let mut written = Vec::new();
+        Article::new().write_content(&mut written).unwrap();
+        assert!(written.is_empty());
+    }
+
+    #[test]
+    fn test_image_loading_attributes() {
+        let html = format!(
+            r#"<html><head><title>Garden notes</title></head><body><article>
+{GARDEN_PARAGRAPHS}
+<img src="https://example.com/a.jpg" loading="lazy" decoding="async" sizes="(max-width: 600px) 100vw, 50vw" srcset="https://example.com/a-480.jpg 480w, https://example.com/a-960.jpg 960w" alt="Raised bed">
+<picture><source srcset="https://example.com/b.webp" type="image/webp" sizes="100vw"><img src="https://example.com/b.jpg" loading="lazy" decoding="async" alt="Seedlings"></picture>
+<p>The soil temperature matters more than the air temperature, so we waited until our thermometer read a steady fifteen degrees before moving anything outside.</p>
+<figure><img src="https://example.com/c.jpg" loading="lazy" alt="Thermometer"><figcaption>Fifteen degrees</figcaption></figure>
+</article></body></html>"#
+        );
+
+        let parse = |eager_images: usize| {
+            let options = ReadabilityOptions::builder()
+                .char_threshold(100)
+                .eager_images(eager_images)
+                .build();
+            Readability::new(&html, Some("https://example.com/notes"), Some(options))
+                .unwrap()
+                .parse()
+                .unwrap()
+                .content
+                .unwrap()
+        };
+
+        let content = parse(0);
+        assert_eq!(content.matches(r#"loading="lazy""#).count(), 3);
+        assert_eq!(content.matches(r#"decoding="async""#).count(), 2);
+        assert!(content.contains(r#"sizes="(max-width: 600px) 100vw, 50vw""#));
+        assert!(content.contains(r#"<source sizes="100vw""#));
+
+        let content = parse(2);
+        assert_eq!(content.matches(r#"loading="eager""#).count(), 2);
+        assert!(!content.contains(r#"decoding="async""#));
+        assert!(content.contains(r#"loading="lazy" src="https://example.com/c.jpg""#));
+        assert!(content.contains(r#"sizes="(max-width: 600px) 100vw, 50vw""#));
+    }
+
+    #[test]
+    fn test_media_manifest() {
+        let html = format!(
+            r#"<html><head><title>Garden notes</title></head><body><article>
+{GARDEN_PARAGRAPHS}
+<figure><img src="/images/bed.jpg" alt="Raised bed" width="800" height="600"><figcaption>The new raised bed, before planting.</figcaption></figure>
+<p>The soil temperature matters more than the air temperature, so we waited until our thermometer read a steady fifteen degrees before moving anything outside.</p>
+<p><img src="thermometer.jpg" alt="Soil thermometer"> Fifteen degrees, at last, after a week of checking every morning before work.</p>
+</article></body></html>"#
+        );
+
+        let parse = |extract_media: bool| {
+            let options = ReadabilityOptions::builder()
+                .char_threshold(100)
+                .extract_media(extract_media)
+                .build();
+            Readability::new(&html, Some("https://example.com/notes/"), Some(options))
+                .unwrap()
+                .parse()
+                .unwrap()
+        };
+
+        assert!(parse(false).media.is_empty());
+
+        let media = parse(true).media;
+        assert_eq!(media.len(), 2);
+        assert_eq!(media[0].src, "https://example.com/images/bed.jpg");
+        assert_eq!(media[0].alt.as_deref(), Some("Raised bed"));
+        assert_eq!(
+            media[0].caption.as_deref(),
+            Some("The new raised bed, before planting.")
+        );
+        assert_eq!((media[0].width, media[0].height), (Some(800), Some(600)));
+        assert_eq!(media[1].src, "https://example.com/notes/thermometer.jpg");
+        assert_eq!(media[1].caption, None);
+    }
+
+    #[test]
+    fn test_iframe_placeholders() {
+        let html = format!(
+            r#"<html><head><title>Garden notes</title></head><body><article>
+{GARDEN_PARAGRAPHS}
+<iframe height="300" src="https://codepen.io/garden/embed/qBxyz?default-tab=result&amp;theme-id=dark" title="Watering schedule"></iframe>
+<p>The soil temperature matters more than the air temperature, so we waited until our thermometer read a steady fifteen degrees before moving anything outside.</p>
+<iframe src="/maps/embed?plot=7"></iframe>
+<iframe src="https://www.youtube.com/embed/abc"></iframe>
+</article></body></html>"#
+        );
+
+        let parse = |placeholders: bool| {
+            let options = ReadabilityOptions::builder()
+                .char_threshold(100)
+                .iframe_placeholders(placeholders)
+                .build();
+            Readability::new(&html, Some("https://example.com/notes/"), Some(options))
+                .unwrap()
+                .parse()
+                .unwrap()
+                .content
+                .unwrap()
+        };
+
+        let content = parse(false);
+        assert!(!content.contains("codepen.io"));
+        assert!(!content.contains("Embedded content"));
+
+        let content = parse(true);
+        assert!(content.contains(
+            r#"<p><a href="https://codepen.io/garden/embed/qBxyz?default-tab=result&amp;theme-id=dark">Embedded content</a></p>"#
+        ));
+        assert!(content.contains(
+            r#"<p><a href="https://example.com/maps/embed?plot=7">Embedded content</a></p>"#
+        ));
+        assert!(content.contains(r#"<iframe src="https://www.youtube.com/embed/abc">"#));
+    }
+
+    #[test]
+    fn test_footnote_targets_survive_cleaning() {
+        let html = r##"<html><head><title>Garden notes</title></head><body><article>
+<p>Spring has finally arrived, and with it the first wave of seedlings.<sup><a href="#fn1">1</a></sup> We spent the week transplanting tomatoes, peppers, and a few experimental squash varieties.</p>
+<p>The soil temperature matters more than the air temperature, so we waited until our thermometer read a steady fifteen degrees before moving anything outside.</p>
+<div><span id="fn1"></span></div>
+<p>1. Seedlings were started indoors in March, under lights, on a heat mat in the basement.</p>
+<div><span id="unused"></span></div>
+</article></body></html>"##;
+
+        let options = ReadabilityOptions::builder().char_threshold(100).build();
+        let content = parse_with(html, options).content.unwrap();
+
+        assert!(content.contains(r##"<a href="#fn1">1</a>"##));
+        assert!(content.contains(r#"<span id="fn1"></span>"#));
+        assert!(!content.contains("unused"));
+    }
+
+    #[test]
+    fn test_content_containers_bypass_conditional_cleaning() {
+        let html = format!(
+            r#"<html><head><title>Garden notes</title></head><body><article>
+{GARDEN_PARAGRAPHS}
+<div class="widget harvest-box"><p>Harvest so far: <a href="/tomatoes">tomatoes</a>, <a href="/beans">beans</a>.</p></div>
+<p>The soil temperature matters more than the air temperature, so we waited until our thermometer read a steady fifteen degrees before moving anything outside.</p>
+</article></body></html>"#
+        );
+
+        let parse = |containers: Vec<String>| {
+            let options = ReadabilityOptions::builder()
+                .char_threshold(100)
+                .content_containers(containers)
+                .build();
+            parse_with(&html, options).content.unwrap()
+        };
+
+        assert!(!parse(Vec::new()).contains("Harvest so far"));
+        assert!(parse(vec![".harvest-box".to_string()]).contains("Harvest so far"));
+    }
+
+    #[test]
+    fn test_soft_break_hints_in_text_output() {
+        let html = format!(
+            r#"<html><head><title>Garden notes</title></head><body><article>
+{GARDEN_PARAGRAPHS}
+<p>The seed catalogue calls it super<wbr>cali<WBR>fragilistic, which is a lot to write on a plant label, so we settled for anti&shy;dis&#8203;establishment.</p>
+</article></body></html>"#
+        );
+
+        let options = ReadabilityOptions::builder()
+            .char_threshold(100)
+            .output_markdown(true)
+            .build();
+        let article = parse_with(&html, options);
+
+        let text = article.text_content.unwrap();
+        assert!(text.contains("calls it supercalifragilistic, which"));
+        assert!(text.contains("for antidisestablishment."));
+        let markdown = article.markdown_content.unwrap();
+        assert!(markdown.contains("calls it supercalifragilistic, which"));
+        assert!(markdown.contains("for antidisestablishment."));
+        assert!(article
+            .content
+            .unwrap()
+            .contains("super<wbr>cali<wbr>fragilistic"));
+    }
+
+    #[test]
+    fn test_edit_markup_survives() {
+        let html = r#"<html><head><title>Changelog</title></head><body><div class="content">
+<h2>Release 2.4</h2>
+<p>Spring has finally arrived, and with it a new release. We spent the week fixing bugs in the scheduler, the importer, and a few experimental exporters.</p>
+<p>The default timeout is now <del datetime="2024-03-01" cite="https://example.com/issues/12">30 seconds</del> <ins datetime="2024-03-02">60 seconds</ins>, which matches what most users configured by hand anyway.</p>
+<del datetime="2024-03-01"><p>Exporting to the old format is deprecated and will be removed in the next release.</p><p>Please migrate.</p></del>
+<ul><li><ins>New: parallel imports</ins></li><li><del>Removed: serial mode</del></li></ul>
+</div></body></html>"#;
+
+        let options = ReadabilityOptions::builder()
+            .char_threshold(100)
+            .output_markdown(true)
+            .build();
+        let article = parse_with(html, options);
+
+        let content = article.content.unwrap();
+        assert!(content.contains(
+            r#"<del cite="https://example.com/issues/12" datetime="2024-03-01">30 seconds</del> <ins datetime="2024-03-02">60 seconds</ins>"#
+        ));
+        assert!(content.contains(r#"<del datetime="2024-03-01"><p>Exporting"#));
+        assert!(content.contains("<li><ins>New: parallel imports</ins></li>"));
+
+        let markdown = article.markdown_content.unwrap();
+        assert!(markdown.contains("now ~~30 seconds~~ 60 seconds, which"));
+        assert!(markdown.contains("~~Exporting to the old format is deprecated and will be removed in the next release.~~\n\n~~Please migrate.~~"));
+        assert!(markdown.contains("- New: parallel imports\n- ~~Removed: serial mode~~"));
+    }
+
+    #[test]
+    fn test_mark_highlights_survive() {
+        let html = r#"<html><head><title>Search results</title></head><body><div class="results">
+<p>Spring has finally arrived, and with it the first wave of <mark class="search-hit">seedlings</mark>. We spent the week transplanting tomatoes, peppers, and a few experimental squash varieties.</p>
+<p>The soil temperature matters more than the air temperature, so we waited until our <mark class="annotation comment">thermometer</mark> read a steady fifteen degrees before moving anything outside.</p>
+</div></body></html>"#;
+
+        let markdown_options = MarkdownOptions {
+            highlight_style: HighlightStyle::Strong,
+            ..MarkdownOptions::default()
+        };
+        let options = ReadabilityOptions::builder()
+            .char_threshold(100)
+            .output_markdown(true)
+            .markdown_options(markdown_options)
+            .build();
+        let article = parse_with(html, options);
+
+        let content = article.content.unwrap();
+        assert!(content.contains(r#"<mark class="search-hit">seedlings</mark>"#));
+        assert!(content.contains(r#"<mark class="annotation comment">thermometer</mark>"#));
+
+        let markdown = article.markdown_content.unwrap();
+        assert!(markdown.contains("first wave of **seedlings**."));
+        assert!(markdown.contains("until our **thermometer** read"));
+    }
+
+    #[test]
+    fn test_parse_excluding_returns_next_candidate() {
+        let paragraph = |topic: &str| {
+            format!("<p>{topic} Spring has finally arrived, and with it the first wave of seedlings. We spent the week transplanting tomatoes, peppers, and a few squash varieties.</p>")
+        };
+        let html = format!(
+            r#"<html><head><title>Garden notes</title></head><body>
+<section><div class="post">{}</div></section>
+<section><div class="post">{}</div></section>
+</body></html>"#,
+            paragraph("Main story.").repeat(6),
+            paragraph("Second story.").repeat(3),
+        );
+
+        let first = Readability::new(&html, None, None)
+            .unwrap()
+            .parse()
+            .unwrap();
+        let first_path = first.candidate_path.unwrap();
+        assert_eq!(first_path, "/html/body/section[1]");
+        assert!(first.content.unwrap().contains("Main story."));
+
+        let next = Readability::new(&html, None, None)
+            .unwrap()
+            .parse_excluding(&[first_path.as_str()])
+            .unwrap();
+        let next_content = next.content.unwrap();
+        assert_eq!(
+            next.candidate_path.as_deref(),
+            Some("/html/body/section[2]")
+        );
+        assert!(next_content.contains("Second story."));
+        assert!(!next_content.contains("Main story."));
+
+        // Unknown paths are ignored.
+        let same = Readability::new(&html, None, None)
+            .unwrap()
+            .parse_excluding(&["/html/body/table"])
+            .unwrap();
+        assert_eq!(
+            same.candidate_path.as_deref(),
+            Some("/html/body/section[1]")
+        );
+    }
+
+    #[test]
+    fn test_extraction_confidence_score() {
+        let paragraph = "<p>Spring has finally arrived, and with it the first wave of seedlings. We spent the week transplanting tomatoes, peppers, and a few experimental squash varieties, and the soil, finally, is warm enough.</p>";
+        let article_html = format!(
+            "<html><head><title>Garden notes</title></head><body><article>{}</article></body></html>",
+            paragraph.repeat(12)
+        );
+        let thin_html = r#"<html><head><title>Links</title></head><body><div>
+<p>Short note about the garden, with <a href="/a">one link to the seed catalogue page</a>.</p>
+<ul><li><a href="/b">Tomatoes</a></li><li><a href="/c">Peppers</a></li><li><a href="/d">Squash</a></li></ul>
+</div></body></html>"#;
+
+        let parse = |html: &str| {
+            let options = ReadabilityOptions::builder().char_threshold(20).build();
+            parse_with(html, options)
+        };
+
+        let article = parse(&article_html);
+        let thin = parse(thin_html);
+        assert!(article.score > 0.7, "{}", article.score);
+        assert!(thin.score < 0.3, "{}", thin.score);
+    }
+
+    #[test]
+    fn test_soft_hyphen_options() {
+        let html = format!(
+            r#"<html><head><title>Garden notes</title></head><body><article>
+{GARDEN_PARAGRAPHS}
+<p>Our neighbour's Kirsch&shy;blüten&shy;fest drew a crowd, and the hand&#173;written signs pointed every visitor towards the green&shy;houses at the back of the lot.</p>
+</article></body></html>"#
+        );
+
+        let parse = |options: ReadabilityOptions| parse_with(&html, options);
+        let builder = || ReadabilityOptions::builder().char_threshold(100);
+
+        let article = parse(builder().build());
+        let text = article.text_content.unwrap();
+        assert!(text.contains("Kirschblütenfest drew a crowd, and the handwritten signs"));
+        assert!(text.contains("the greenhouses at"));
+        assert!(article
+            .content
+            .unwrap()
+            .contains("Kirsch\u{ad}blüten\u{ad}fest"));
+
+        let kept = parse(builder().strip_soft_hyphens(false).build());
+        assert!(kept.text_content.unwrap().contains("hand\u{ad}written"));
+
+        let stripped = parse(builder().strip_soft_hyphens_html(true).build());
+        let content = stripped.content.unwrap();
+        assert!(!content.contains('\u{ad}'));
+        assert!(content.contains("Kirschblütenfest drew a crowd, and the handwritten signs"));
+    }
+
+    #[test]
+    fn test_extraction_hints() {
+        let html = format!(
+            r#"<html><head><title>Hints</title></head><body>
+<div class="story">
+{GARDEN_PARAGRAPHS}
+<p data-readability="remove">Sponsored: the best raised bed kits of the year, reviewed by our partners, with discount codes for every reader of this newsletter.</p>
+</div>
+<div class="letters" data-readability="content"><p>A short reader letter the site wants shown as the article.</p></div>
+</body></html>"#
+        );
+
+        let options = ReadabilityOptions::builder().char_threshold(100).build();
+        let article = Readability::new(&html, None, Some(options.clone()))
+            .unwrap()
+            .parse()
+            .unwrap();
+        let content = article.content.unwrap();
+        assert!(content.contains("A short reader letter"));
+        assert!(!content.contains("first wave of seedlings"));
+        assert!(!content.contains("data-readability"));
+
+        let without_content_hint = html.replace(r#" data-readability="content""#, "");
+        let article = parse_with(&without_content_hint, options);
+        let content = article.content.unwrap();
+        assert!(content.contains("first wave of seedlings"));
+        assert!(!content.contains("Sponsored"));
+    }
+
+    #[test]
+    fn test_datatable_marker_never_in_content() {
+        let html = r#"<html><head><title>Harvest report</title></head><body><article>
+<h1>Harvest report</h1>
+<p>Every autumn we weigh what came out of the garden and compare it with previous years, which tells us which varieties earned their space in the beds.</p>
+<table data-readability-datatable="true" summary="Harvest by year">
+  <caption>Harvest by year</caption>
+  <thead><tr><th>Year</th><th>Tomatoes</th><th>Squash</th></tr></thead>
+  <tbody data-readability-datatable="false"><tr><td>2022</td><td>41 kg</td><td>18 kg</td></tr>
+  <tr><td>2023</td><td>56 kg</td><td>23 kg</td></tr></tbody>
+</table>
+<p>Tomatoes did well thanks to the new drip irrigation, while the squash suffered from powdery mildew during the wet weeks of August and September.</p>
+</article></body></html>"#;
+
+        for keep_classes in [false, true] {
+            let options = ReadabilityOptions::builder()
+                .char_threshold(100)
+                .keep_classes(keep_classes)
+                .build();
+            let article = parse_with(html, options);
+            let content = article.content.unwrap();
+            assert!(content.contains("<table"));
+            assert!(content.contains("56 kg"));
+            assert!(!content.contains("data-readability-datatable"));
+        }
+    }
+
+    #[test]
+    fn test_newsletter_layout_tables_are_unwrapped() {
+        let html = r#"<html><head><title>Weekly Digest</title></head><body>
+<table width="100%"><tr><td align="center">
+  <table width="600">
+    <tr><td><a href="https://example.com/view">View this email in your browser</a></td></tr>
+    <tr><td>
+      <table><tr><td>
+        <h1>This week in gardening</h1>
+        <p>Spring has finally arrived, and with it the first wave of seedlings. We spent the week transplanting tomatoes, peppers, and a few experimental squash varieties into the raised beds.</p>
+        <p>The soil temperature matters more than the air temperature, so we waited until our thermometer read a steady fifteen degrees before moving anything outside.</p>
+      </td></tr></table>
+      <table><tr><td>
+        <h2>Reader question</h2>
+        <p>Water new transplants daily for the first week, then every other day.</p>
+      </td></tr></table>
+    </td></tr>
+    <tr><td><em>All rights reserved.</em> <a href="https://example.com/unsub">Unsubscribe</a></td></tr>
+  </table>
+</td></tr></table>
+</body></html>"#;
+
+        let options = ReadabilityOptions::builder().char_threshold(100).build();
+        let article = parse_with(html, options);
+        let content = article.content.unwrap();
+        assert!(content.contains("first wave of seedlings"));
+        assert!(content.contains("Water new transplants daily"));
+        assert!(!content.contains("Unsubscribe"));
+        assert!(!content.contains("<table"));
+    }
+
+    #[test]
+    fn excerpt_skips_hatnote_paragraphs() {
+        let html = r#"
+        <p class="hatnote" role="note">See also: Something else entirely.</p>
+        <p>This is the first real paragraph with sufficient length to act as an excerpt. It should be returned.</p>
+        "#;
+        let reader = Readability::new(html, None, None).unwrap();
+        let excerpt = reader.generate_excerpt_from_html(html);
+        assert_eq!(
+            excerpt,
+            Some(
+                "This is the first real paragraph with sufficient length to act as an excerpt. It should be returned."
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_br_paragraphs_keep_words_apart_in_text_content() {
+        let html = r#"<html><body><article><h1>Lorem</h1><div>
+Lorem ipsum<br/>dolor sit<br/> <br/><br/>amet, consectetur adipisicing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua ut enim ad minim veniam.<br/><br/>Quis nostrud exercitation ullamco laboris nisi ut aliquip ex ea commodo<br/> <br/>consequat, duis aute irure dolor in reprehenderit in voluptate velit esse.
+</div></article></body></html>"#;
+        let options = ReadabilityOptions::builder().char_threshold(100).build();
+        let text = parse_with(html, options).text_content.unwrap();
+
+        assert!(!text.contains("sitamet"));
+        assert!(!text.contains("commodoconsequat"));
+        let words: Vec<&str> = text.split_whitespace().collect();
+        assert!(words.windows(2).any(|pair| pair == ["sit", "amet,"]));
+        assert!(words
+            .windows(2)
+            .any(|pair| pair == ["commodo", "consequat,"]));
     }
 }
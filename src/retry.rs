@@ -0,0 +1,120 @@
+//! Retry strategies for content extraction.
+//!
+//! When an extraction attempt yields less text than `char_threshold`, the
+//! extractor retries with a different set of [`ParseFlags`]. A [`RetryStrategy`]
+//! decides which flags to try next based on the previous attempt.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use readabilityrs::{AttemptStats, ParseFlags, ReadabilityOptions, RetryStrategy};
+//! use std::sync::Arc;
+//!
+//! /// Never stops weighting classes, but relaxes conditional cleaning first.
+//! #[derive(Debug)]
+//! struct KeepClassWeights;
+//!
+//! impl RetryStrategy for KeepClassWeights {
+//!     fn next_flags(&self, stats: &AttemptStats) -> Option<ParseFlags> {
+//!         if stats.flags.contains(ParseFlags::CLEAN_CONDITIONALLY) {
+//!             Some(stats.flags - ParseFlags::CLEAN_CONDITIONALLY)
+//!         } else if stats.flags.contains(ParseFlags::STRIP_UNLIKELYS) {
+//!             Some(stats.flags - ParseFlags::STRIP_UNLIKELYS)
+//!         } else {
+//!             None
+//!         }
+//!     }
+//! }
+//!
+//! let options = ReadabilityOptions::builder()
+//!     .retry_strategy(Arc::new(KeepClassWeights))
+//!     .build();
+//! ```
+
+use crate::constants::ParseFlags;
+use std::fmt::Debug;
+
+/// Statistics about a finished extraction attempt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AttemptStats {
+    /// Zero-based index of the attempt.
+    pub attempt: usize,
+    /// Flags the attempt ran with.
+    pub flags: ParseFlags,
+    /// Length of the extracted text, or `0` when nothing was extracted.
+    pub text_length: usize,
+    /// Number of scoring candidates found with these flags.
+    pub candidate_count: usize,
+}
+
+/// Decides which [`ParseFlags`] to use for each extraction attempt.
+///
+/// The extractor starts with [`initial_flags`](RetryStrategy::initial_flags)
+/// and, as long as an attempt falls short of `char_threshold`, asks
+/// [`next_flags`](RetryStrategy::next_flags) for the flags to retry with.
+/// Extraction stops when the strategy returns `None` or returns flags that were
+/// already tried, and the longest attempt is used.
+pub trait RetryStrategy: Debug + Send + Sync {
+    /// Flags for the first attempt.
+    ///
+    /// Default: all flags enabled.
+    fn initial_flags(&self) -> ParseFlags {
+        ParseFlags::all()
+    }
+
+    /// Flags for the next attempt, or `None` to stop retrying.
+    fn next_flags(&self, stats: &AttemptStats) -> Option<ParseFlags>;
+}
+
+/// Mozilla's retry order: drop `STRIP_UNLIKELYS`, then `WEIGHT_CLASSES`, then
+/// `CLEAN_CONDITIONALLY`, one flag per attempt.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultRetryStrategy;
+
+impl RetryStrategy for DefaultRetryStrategy {
+    fn next_flags(&self, stats: &AttemptStats) -> Option<ParseFlags> {
+        [
+            ParseFlags::STRIP_UNLIKELYS,
+            ParseFlags::WEIGHT_CLASSES,
+            ParseFlags::CLEAN_CONDITIONALLY,
+        ]
+        .into_iter()
+        .find(|flag| stats.flags.contains(*flag))
+        .map(|flag| stats.flags - flag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(flags: ParseFlags) -> AttemptStats {
+        AttemptStats {
+            attempt: 0,
+            flags,
+            text_length: 0,
+            candidate_count: 0,
+        }
+    }
+
+    #[test]
+    fn test_default_strategy_order() {
+        let strategy = DefaultRetryStrategy;
+        let mut flags = strategy.initial_flags();
+        let mut sequence = vec![flags];
+        while let Some(next) = strategy.next_flags(&stats(flags)) {
+            flags = next;
+            sequence.push(flags);
+        }
+
+        assert_eq!(
+            sequence,
+            vec![
+                ParseFlags::all(),
+                ParseFlags::WEIGHT_CLASSES | ParseFlags::CLEAN_CONDITIONALLY,
+                ParseFlags::CLEAN_CONDITIONALLY,
+                ParseFlags::empty(),
+            ]
+        );
+    }
+}
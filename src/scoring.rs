@@ -3,6 +3,7 @@
 use crate::constants::{ParseFlags, REGEXPS};
 use crate::dom_utils;
 use scraper::ElementRef;
+use std::collections::HashMap;
 
 /// Get an element's class/ID weight using regular expressions.
 /// Uses positive/negative patterns to determine if an element looks good or bad.
@@ -56,46 +57,66 @@ pub fn get_class_weight(element: ElementRef, flags: ParseFlags) -> i32 {
 /// # Arguments
 /// * `element` - The element to initialize scoring for
 /// * `flags` - Current parsing flags
+/// * `base_score_overrides` - Base scores by lowercase tag name, replacing the
+///   built-in value for that tag (for `div`, both the paragraph and container value)
 ///
 /// # Returns
 /// Initial content score as a float
-pub fn initialize_node_score(element: ElementRef, flags: ParseFlags) -> f64 {
-    let mut score = 0.0;
+pub fn initialize_node_score(
+    element: ElementRef,
+    flags: ParseFlags,
+    base_score_overrides: &HashMap<String, f64>,
+) -> f64 {
+    let base_score = base_score_overrides
+        .get(element.value().name())
+        .copied()
+        .unwrap_or_else(|| default_base_score(element));
+
+    base_score + get_class_weight(element, flags) as f64
+}
 
+/// Built-in base score for an element's tag.
+fn default_base_score(element: ElementRef) -> f64 {
     let tag_name = element.value().name().to_uppercase();
 
     match tag_name.as_str() {
         // P tags get the highest base score (they're what we're looking for)
-        "P" => score += 5.0,
+        "P" => 5.0,
 
         // SECTION and ARTICLE are good semantic containers
-        "SECTION" | "ARTICLE" => score += 8.0,
+        "SECTION" | "ARTICLE" => 8.0,
 
         // DIV gets special handling: if it has no block children, treat like P
         "DIV" => {
             if !dom_utils::has_child_block_element(element) {
                 // DIV acting as paragraph - give it P tag score
-                score += 5.0;
+                5.0
             } else {
                 // DIV as container - lower score
-                score += 2.0;
+                2.0
             }
         }
 
         // These tags are good content containers
-        "PRE" | "TD" | "BLOCKQUOTE" => score += 3.0,
+        "PRE" | "TD" | "BLOCKQUOTE" => 3.0,
 
         // These tags are typically not article content
-        "ADDRESS" | "OL" | "UL" | "DL" | "DD" | "DT" | "LI" | "FORM" => score -= 3.0,
+        "ADDRESS" | "OL" | "UL" | "DL" | "DD" | "DT" | "LI" | "FORM" => -3.0,
 
         // Headers are typically not body content
-        "H1" | "H2" | "H3" | "H4" | "H5" | "H6" | "TH" => score -= 5.0,
+        "H1" | "H2" | "H3" | "H4" | "H5" | "H6" | "TH" => -5.0,
 
-        _ => {}
-    }
+        // Custom elements are treated like whatever they are used as.
+        _ if dom_utils::is_custom_element(element) => {
+            if !dom_utils::has_child_block_element(element) {
+                5.0
+            } else {
+                2.0
+            }
+        }
 
-    score += get_class_weight(element, flags) as f64;
-    score
+        _ => 0.0,
+    }
 }
 
 /// Calculate content score for a paragraph or other scoreable element.
@@ -131,6 +152,33 @@ pub fn calculate_content_score(element: ElementRef, link_density_modifier: f64)
     score
 }
 
+/// Candidate score at which the score half of the confidence reaches ~63%.
+const CONFIDENCE_SCORE_SCALE: f64 = 40.0;
+/// Text length in characters at which the length half reaches ~63%.
+const CONFIDENCE_LENGTH_SCALE: f64 = 1500.0;
+
+/// Estimate how likely an extraction is to be the real article.
+///
+/// Combines the selected candidate's final content score, the length of the
+/// cleaned text and its link density into a value between `0.0` and `1.0`.
+/// Score and length each saturate towards `1.0` and are averaged; the result
+/// is then scaled down by the share of text inside links. A `None` score
+/// means the page marked its content root itself and counts as certain.
+///
+/// Roughly: above `0.7` is a full article, `0.4` to `0.7` a short article or
+/// a partial extraction, and below `0.3` a thin or link-heavy page.
+pub fn extraction_confidence(
+    candidate_score: Option<f64>,
+    text_length: usize,
+    link_density: f64,
+) -> f64 {
+    let saturate = |value: f64, scale: f64| 1.0 - (-value.max(0.0) / scale).exp();
+    let score = candidate_score.map_or(1.0, |score| saturate(score, CONFIDENCE_SCORE_SCALE));
+    let length = saturate(text_length as f64, CONFIDENCE_LENGTH_SCALE);
+
+    ((score + length) / 2.0 * (1.0 - link_density.clamp(0.0, 1.0))).clamp(0.0, 1.0)
+}
+
 /// Check if an element is a valid byline.
 ///
 /// A valid byline should:
@@ -187,28 +235,29 @@ mod tests {
 
     #[test]
     fn test_initialize_node_score() {
+        let no_overrides = HashMap::new();
         let p_html = Html::parse_fragment("<p>Content</p>");
         let p_sel = Selector::parse("p").unwrap();
         let p = p_html.select(&p_sel).next().unwrap();
-        assert_eq!(initialize_node_score(p, ParseFlags::WEIGHT_CLASSES), 5.0);
+        assert_eq!(initialize_node_score(p, ParseFlags::WEIGHT_CLASSES, &no_overrides), 5.0);
 
         let h1_html = Html::parse_fragment("<h1>Title</h1>");
         let h1_sel = Selector::parse("h1").unwrap();
         let h1 = h1_html.select(&h1_sel).next().unwrap();
-        assert_eq!(initialize_node_score(h1, ParseFlags::WEIGHT_CLASSES), -5.0);
+        assert_eq!(initialize_node_score(h1, ParseFlags::WEIGHT_CLASSES, &no_overrides), -5.0);
 
         let div_p_html = Html::parse_fragment("<div>Text content only</div>");
         let div_sel = Selector::parse("div").unwrap();
         let div_as_p = div_p_html.select(&div_sel).next().unwrap();
         assert_eq!(
-            initialize_node_score(div_as_p, ParseFlags::WEIGHT_CLASSES),
+            initialize_node_score(div_as_p, ParseFlags::WEIGHT_CLASSES, &no_overrides),
             5.0
         );
 
         let div_container_html = Html::parse_fragment("<div><p>Nested paragraph</p></div>");
         let div_container = div_container_html.select(&div_sel).next().unwrap();
         assert_eq!(
-            initialize_node_score(div_container, ParseFlags::WEIGHT_CLASSES),
+            initialize_node_score(div_container, ParseFlags::WEIGHT_CLASSES, &no_overrides),
             2.0
         );
 
@@ -216,11 +265,30 @@ mod tests {
         let article_sel = Selector::parse("article").unwrap();
         let article = article_html.select(&article_sel).next().unwrap();
         assert_eq!(
-            initialize_node_score(article, ParseFlags::WEIGHT_CLASSES),
+            initialize_node_score(article, ParseFlags::WEIGHT_CLASSES, &no_overrides),
             8.0
         );
     }
 
+    #[test]
+    fn test_initialize_node_score_overrides() {
+        let html = Html::parse_fragment(
+            r#"<blockquote class="post">Answer</blockquote><li>Post</li><p>Text</p>"#,
+        );
+        let get = |tag: &str| {
+            let sel = Selector::parse(tag).unwrap();
+            html.select(&sel).next().unwrap()
+        };
+        let overrides = HashMap::from([("blockquote".to_string(), 20.0), ("li".to_string(), 5.0)]);
+        let flags = ParseFlags::WEIGHT_CLASSES;
+
+        assert_eq!(initialize_node_score(get("blockquote"), flags, &HashMap::new()), 28.0);
+        // Class weight still applies on top of the overridden base score.
+        assert_eq!(initialize_node_score(get("blockquote"), flags, &overrides), 45.0);
+        assert_eq!(initialize_node_score(get("li"), flags, &overrides), 5.0);
+        assert_eq!(initialize_node_score(get("p"), flags, &overrides), 5.0);
+    }
+
     #[test]
     fn test_calculate_content_score() {
         let html = Html::parse_fragment(
@@ -242,4 +310,18 @@ mod tests {
         let score = calculate_content_score(elem, 0.0);
         assert_eq!(score, 0.0);
     }
+
+    #[test]
+    fn test_extraction_confidence() {
+        let full = extraction_confidence(Some(120.0), 6000, 0.05);
+        let thin = extraction_confidence(Some(8.0), 300, 0.1);
+        let link_heavy = extraction_confidence(Some(120.0), 6000, 0.9);
+        assert!(full > 0.7, "{full}");
+        assert!(thin < 0.3, "{thin}");
+        assert!(link_heavy < 0.3, "{link_heavy}");
+
+        assert_eq!(extraction_confidence(Some(-20.0), 0, 0.0), 0.0);
+        assert!(extraction_confidence(Some(1e9), usize::MAX, 0.0) <= 1.0);
+        assert!(extraction_confidence(None, 300, 0.0) > thin);
+    }
 }
@@ -0,0 +1,145 @@
+//! Helpers for evaluating heuristic changes against fixtures.
+//!
+//! This module is only compiled with the `testutil` feature (and in the crate's
+//! own tests). It compares two extraction outputs at the block level, so a
+//! scoring or cleaning change can be reviewed as a list of paragraphs that
+//! appeared or disappeared instead of a raw HTML diff.
+//!
+//! ```rust
+//! use readabilityrs::testutil::diff_extraction;
+//!
+//! let before = "<p>Intro.</p><p>Sign up for our newsletter</p>";
+//! let after = "<p>Intro.</p><p>Closing thoughts.</p>";
+//!
+//! let report = diff_extraction(before, after);
+//! assert_eq!(report.removed, vec!["Sign up for our newsletter"]);
+//! assert_eq!(report.added, vec!["Closing thoughts."]);
+//! ```
+
+use crate::dom_utils::get_inner_text;
+use once_cell::sync::Lazy;
+use scraper::{ElementRef, Html, Selector};
+use std::collections::HashMap;
+
+static BLOCK_SELECTOR: Lazy<Selector> = Lazy::new(|| {
+    Selector::parse(
+        "p, h1, h2, h3, h4, h5, h6, li, pre, blockquote, td, th, dt, dd, figcaption, caption",
+    )
+    .unwrap()
+});
+
+/// Block-level differences between two extraction outputs.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DiffReport {
+    /// Text blocks present in the new output but not in the old one, in document order.
+    pub added: Vec<String>,
+    /// Text blocks present in the old output but not in the new one, in document order.
+    pub removed: Vec<String>,
+    /// Number of blocks found in both outputs.
+    pub unchanged: usize,
+}
+
+impl DiffReport {
+    /// Returns `true` when both outputs contain the same blocks.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// Compare two extracted article HTML strings block by block.
+///
+/// Each output is split into its innermost text blocks (paragraphs, headings,
+/// list items, table cells, ...) with whitespace normalized. Blocks are matched
+/// as a multiset, so moving a paragraph does not count as a change, but
+/// duplicating or dropping one does.
+///
+/// # Arguments
+/// * `old_html` - Content produced before the change
+/// * `new_html` - Content produced after the change
+pub fn diff_extraction(old_html: &str, new_html: &str) -> DiffReport {
+    let old_blocks = collect_blocks(old_html);
+    let new_blocks = collect_blocks(new_html);
+
+    let mut remaining: HashMap<&str, usize> = HashMap::new();
+    for block in &new_blocks {
+        *remaining.entry(block.as_str()).or_insert(0) += 1;
+    }
+
+    let mut report = DiffReport::default();
+    for block in &old_blocks {
+        match remaining.get_mut(block.as_str()) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                report.unchanged += 1;
+            }
+            _ => report.removed.push(block.clone()),
+        }
+    }
+
+    // Walk the new blocks backwards so the last occurrences are the ones
+    // reported as added, then restore document order.
+    for block in new_blocks.iter().rev() {
+        if let Some(count) = remaining.get_mut(block.as_str()) {
+            if *count > 0 {
+                *count -= 1;
+                report.added.push(block.clone());
+            }
+        }
+    }
+    report.added.reverse();
+
+    report
+}
+
+fn collect_blocks(html: &str) -> Vec<String> {
+    let fragment = Html::parse_fragment(html);
+    fragment
+        .select(&BLOCK_SELECTOR)
+        .filter(|el| !contains_block(*el))
+        .map(|el| get_inner_text(el, true))
+        .filter(|text| !text.is_empty())
+        .collect()
+}
+
+fn contains_block(element: ElementRef) -> bool {
+    element.select(&BLOCK_SELECTOR).any(|el| el.id() != element.id())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identical_outputs_have_no_diff() {
+        let html = "<div><h2>Title</h2><p>First paragraph.</p><p>Second   paragraph.</p></div>";
+        let report = diff_extraction(html, html);
+        assert!(report.is_empty());
+        assert_eq!(report.unchanged, 3);
+    }
+
+    #[test]
+    fn test_reports_added_and_removed_blocks() {
+        let old = "<p>Intro.</p><p>Sign up for our newsletter</p><p>Body text.</p>";
+        let new = "<p>Intro.</p><p>Body   text.</p><ul><li>Recovered item</li></ul>";
+        let report = diff_extraction(old, new);
+        assert_eq!(report.removed, vec!["Sign up for our newsletter"]);
+        assert_eq!(report.added, vec!["Recovered item"]);
+        assert_eq!(report.unchanged, 2);
+    }
+
+    #[test]
+    fn test_nested_blocks_compare_innermost_text() {
+        let old = "<blockquote><p>Quoted.</p></blockquote>";
+        let new = "<p>Quoted.</p>";
+        assert!(diff_extraction(old, new).is_empty());
+    }
+
+    #[test]
+    fn test_duplicated_block_is_reported() {
+        let old = "<p>Once.</p>";
+        let new = "<p>Once.</p><p>Once.</p>";
+        let report = diff_extraction(old, new);
+        assert_eq!(report.added, vec!["Once."]);
+        assert!(report.removed.is_empty());
+    }
+}
@@ -60,11 +60,258 @@ fn decode_html_entity(entity: &str) -> Option<String> {
     }
 }
 
+/// Replace non-breaking spaces (U+00A0) with regular spaces.
+///
+/// `&nbsp;` decodes to U+00A0, which takes two bytes in UTF-8 and would
+/// otherwise inflate length checks and leak into plain-text output.
+pub fn replace_nbsp(text: &str) -> String {
+    text.replace('\u{a0}', " ")
+}
+
+/// Remove soft line-break hints: zero-width spaces (U+200B) and, when
+/// `soft_hyphens` is set, soft hyphens (U+00AD, `&shy;`).
+///
+/// Like `<wbr>`, these only mark where a long word or URL may wrap. They are
+/// invisible in rendered HTML but would split or pad words in plain text.
+pub fn remove_soft_breaks(text: &str, soft_hyphens: bool) -> String {
+    if soft_hyphens {
+        text.replace(['\u{200b}', '\u{ad}'], "")
+    } else {
+        text.replace('\u{200b}', "")
+    }
+}
+
+/// Whether a character is written without spaces between words: CJK
+/// ideographs, kana, and the Thai, Lao, Khmer and Burmese scripts.
+fn is_unspaced_script(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{30FF}'   // Hiragana, Katakana
+        | '\u{3400}'..='\u{4DBF}' // CJK Extension A
+        | '\u{4E00}'..='\u{9FFF}' // CJK Unified Ideographs
+        | '\u{F900}'..='\u{FAFF}' // CJK Compatibility Ideographs
+        | '\u{FF66}'..='\u{FF9F}' // Half-width Katakana
+        | '\u{20000}'..='\u{2FA1F}'
+    ) || is_southeast_asian_script(c)
+}
+
+/// Count the words in plain text
+///
+/// Space-delimited scripts count runs of letters and digits, keeping
+/// apostrophes and hyphens inside words ("don't", "well-known") and decimal
+/// points inside numbers ("3.14") as one word. Scripts written without spaces
+/// have no word boundaries to find without a dictionary, so each CJK, Thai,
+/// Lao, Khmer or Burmese character counts as one word. Mixed-script text
+/// applies the matching rule to each run.
+pub fn count_words(text: &str) -> usize {
+    let chars: Vec<char> = text.chars().collect();
+    let mut count = 0;
+    let mut in_word = false;
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_whitespace() {
+            in_word = false;
+        } else if is_unspaced_script(c) {
+            // Combining vowel and tone marks belong to the previous letter.
+            if !is_combining_mark(c) {
+                count += 1;
+            }
+            in_word = false;
+        } else if c.is_alphanumeric() {
+            if !in_word {
+                count += 1;
+                in_word = true;
+            }
+        } else if in_word {
+            let prev = chars[i - 1];
+            let next = chars.get(i + 1).copied();
+            let joins = match c {
+                '\'' | '’' | '-' => next.is_some_and(char::is_alphanumeric),
+                '.' | ',' => prev.is_ascii_digit() && next.is_some_and(|n| n.is_ascii_digit()),
+                _ => false,
+            };
+            in_word = joins;
+        }
+    }
+
+    count
+}
+
+/// Thai, Lao, Khmer and Burmese vowel signs and tone marks that combine with
+/// the preceding consonant.
+fn is_combining_mark(c: char) -> bool {
+    matches!(c,
+        '\u{0E31}' | '\u{0E34}'..='\u{0E3A}' | '\u{0E47}'..='\u{0E4E}'
+        | '\u{0EB1}' | '\u{0EB4}'..='\u{0EBC}' | '\u{0EC8}'..='\u{0ECD}'
+        | '\u{17B4}'..='\u{17D3}'
+        | '\u{102B}'..='\u{103E}'
+    )
+}
+
 /// Normalize whitespace in a string
 pub fn normalize_whitespace(text: &str) -> String {
     REGEXPS.normalize.replace_all(text, " ").to_string()
 }
 
+/// How sentences are delimited in a given language
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SentenceStyle {
+    /// A period followed by whitespace or the end of the text.
+    Western,
+    /// Full-width terminators (`。`, `！`, `？`) that need no following space (Japanese, Chinese).
+    FullWidth,
+    /// No sentence punctuation; a space between words of the script marks a break (Thai, Lao, Khmer, Burmese).
+    SpaceDelimited,
+}
+
+impl SentenceStyle {
+    /// Pick the segmentation rules for a BCP 47 language tag such as `ja` or `th-TH`.
+    pub fn for_lang(lang: Option<&str>) -> Self {
+        let primary = lang
+            .and_then(|l| l.trim().split(['-', '_']).next())
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        match primary.as_str() {
+            "ja" | "zh" | "yue" | "wuu" => SentenceStyle::FullWidth,
+            "th" | "lo" | "km" | "my" => SentenceStyle::SpaceDelimited,
+            _ => SentenceStyle::Western,
+        }
+    }
+}
+
+const FULL_WIDTH_TERMINATORS: &[char] = &['。', '！', '？', '．'];
+const SOUTHEAST_ASIAN_TERMINATORS: &[char] = &['๚', '๛', '។', '៕', '။'];
+
+fn is_southeast_asian_script(c: char) -> bool {
+    matches!(c, '\u{0E00}'..='\u{0EFF}' | '\u{1780}'..='\u{17FF}' | '\u{1000}'..='\u{109F}')
+}
+
+/// Detect whether text contains a sentence boundary under the language's rules
+///
+/// Western punctuation is accepted for every language since mixed-language
+/// paragraphs are common.
+pub fn has_sentence_boundary(text: &str, lang: Option<&str>) -> bool {
+    let mut chars = text.chars().peekable();
+    let mut prev: Option<char> = None;
+    let style = SentenceStyle::for_lang(lang);
+
+    while let Some(ch) = chars.next() {
+        let next = chars.peek().copied();
+        if ch == '.' && next.is_none_or(char::is_whitespace) {
+            return true;
+        }
+        match style {
+            SentenceStyle::Western => {}
+            SentenceStyle::FullWidth => {
+                if FULL_WIDTH_TERMINATORS.contains(&ch) {
+                    return true;
+                }
+            }
+            SentenceStyle::SpaceDelimited => {
+                if SOUTHEAST_ASIAN_TERMINATORS.contains(&ch) {
+                    return true;
+                }
+                if ch.is_whitespace()
+                    && prev.is_some_and(is_southeast_asian_script)
+                    && next.is_some_and(is_southeast_asian_script)
+                {
+                    return true;
+                }
+            }
+        }
+        prev = Some(ch);
+    }
+    false
+}
+
+/// Byte offset just past the last sentence terminator in `text`, if any
+///
+/// Only meaningful for [`SentenceStyle::FullWidth`] languages, where text has no
+/// spaces to break at; other styles return `None`.
+pub fn last_sentence_end(text: &str, lang: Option<&str>) -> Option<usize> {
+    if SentenceStyle::for_lang(lang) != SentenceStyle::FullWidth {
+        return None;
+    }
+    text.char_indices()
+        .rev()
+        .find(|(_, c)| FULL_WIDTH_TERMINATORS.contains(c))
+        .map(|(i, c)| i + c.len_utf8())
+}
+
+/// Quotation marks for a `<q>` nested `depth` quotes deep in text of language `lang`
+///
+/// Outer quotes use the language's primary marks (`“…”` in English, `«…»`
+/// in French, `„…“` in German, `「…」` in Japanese and Chinese) and quotes
+/// inside them the secondary marks, alternating with depth. Unknown or
+/// missing languages use English marks.
+pub fn quote_marks(lang: Option<&str>, depth: usize) -> (&'static str, &'static str) {
+    let primary = lang
+        .and_then(|l| l.trim().split(['-', '_']).next())
+        .unwrap_or("")
+        .to_ascii_lowercase();
+    let (outer, inner) = match primary.as_str() {
+        "fr" => (("«\u{a0}", "\u{a0}»"), ("“", "”")),
+        "es" | "it" | "ca" | "ru" | "uk" | "el" => (("«", "»"), ("“", "”")),
+        "de" | "cs" | "sk" | "pl" | "bg" => (("„", "“"), ("‚", "‘")),
+        "ja" | "zh" => (("「", "」"), ("『", "』")),
+        _ => (("“", "”"), ("‘", "’")),
+    };
+    if depth.is_multiple_of(2) {
+        outer
+    } else {
+        inner
+    }
+}
+
+/// Render text with Unicode superscript characters
+///
+/// Returns `None` when any character has no superscript form, so callers can
+/// fall back to another representation instead of a half-converted string.
+pub fn to_unicode_superscript(text: &str) -> Option<String> {
+    map_script_chars(text, |c| {
+        Some(match c {
+            '0' => '⁰', '1' => '¹', '2' => '²', '3' => '³', '4' => '⁴',
+            '5' => '⁵', '6' => '⁶', '7' => '⁷', '8' => '⁸', '9' => '⁹',
+            '+' => '⁺', '-' | '−' => '⁻', '=' => '⁼', '(' => '⁽', ')' => '⁾',
+            'i' => 'ⁱ', 'n' => 'ⁿ',
+            _ => return None,
+        })
+    })
+}
+
+/// Render text with Unicode subscript characters
+///
+/// Returns `None` when any character has no subscript form.
+pub fn to_unicode_subscript(text: &str) -> Option<String> {
+    map_script_chars(text, |c| {
+        Some(match c {
+            '0' => '₀', '1' => '₁', '2' => '₂', '3' => '₃', '4' => '₄',
+            '5' => '₅', '6' => '₆', '7' => '₇', '8' => '₈', '9' => '₉',
+            '+' => '₊', '-' | '−' => '₋', '=' => '₌', '(' => '₍', ')' => '₎',
+            'a' => 'ₐ', 'e' => 'ₑ', 'o' => 'ₒ', 'x' => 'ₓ', 'h' => 'ₕ',
+            'k' => 'ₖ', 'l' => 'ₗ', 'm' => 'ₘ', 'n' => 'ₙ', 'p' => 'ₚ',
+            's' => 'ₛ', 't' => 'ₜ', 'i' => 'ᵢ', 'j' => 'ⱼ', 'r' => 'ᵣ',
+            'u' => 'ᵤ', 'v' => 'ᵥ',
+            _ => return None,
+        })
+    })
+}
+
+fn map_script_chars(text: &str, map: impl Fn(char) -> Option<char>) -> Option<String> {
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+    trimmed.chars().map(map).collect()
+}
+
+/// Normalize a class attribute for equality checks
+///
+/// Collapses any run of whitespace (spaces, tabs, newlines) between class names
+/// into a single space and trims the ends, so `"  post\tentry "` equals `"post entry"`.
+pub fn normalize_class_list(class: &str) -> String {
+    class.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
 /// Check if a string is a valid URL
 pub fn is_url(s: &str) -> bool {
     url::Url::parse(s).is_ok()
@@ -731,6 +978,41 @@ fn remove_social_handle_lines(text: &str) -> Option<String> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_count_words_english() {
+        assert_eq!(count_words(""), 0);
+        assert_eq!(count_words("  Hello,   world!  "), 2);
+        assert_eq!(
+            count_words("Don't panic: it's a well-known fact that π ≈ 3.14 — mostly."),
+            10
+        );
+    }
+
+    #[test]
+    fn test_count_words_chinese() {
+        assert_eq!(count_words("我们今天去公园。"), 7);
+        assert_eq!(count_words("我用 Rust 写了 2 个程序"), 9);
+    }
+
+    #[test]
+    fn test_count_words_thai() {
+        // Base letters count; combining vowel and tone marks do not.
+        assert_eq!(count_words("สวัสดี"), 4);
+    }
+
+    #[test]
+    fn test_remove_soft_breaks() {
+        assert_eq!(
+            remove_soft_breaks("super\u{ad}cali\u{200b}fragilistic", true),
+            "supercalifragilistic"
+        );
+        assert_eq!(
+            remove_soft_breaks("super\u{ad}cali\u{200b}fragilistic", false),
+            "super\u{ad}califragilistic"
+        );
+        assert_eq!(remove_soft_breaks("two words", true), "two words");
+    }
+
     #[test]
     fn test_unescape_html_entities() {
         assert_eq!(unescape_html_entities("&lt;div&gt;"), "<div>");
@@ -743,6 +1025,61 @@ mod tests {
         assert_eq!(normalize_whitespace("a  b  c"), "a b c");
     }
 
+    #[test]
+    fn test_sentence_boundary_western() {
+        assert!(has_sentence_boundary("One sentence. Another", None));
+        assert!(has_sentence_boundary("Ends here.", Some("en")));
+        assert!(!has_sentence_boundary("Version 1.2 notes", None));
+    }
+
+    #[test]
+    fn test_sentence_boundary_japanese() {
+        let text = "今日は晴れです。明日は雨";
+        assert!(has_sentence_boundary(text, Some("ja")));
+        assert!(has_sentence_boundary("本当ですか？", Some("ja-JP")));
+        assert!(!has_sentence_boundary("今日は晴れです", Some("ja")));
+        assert!(!has_sentence_boundary(text, Some("en")));
+        assert_eq!(last_sentence_end(text, Some("ja")), Some("今日は晴れです。".len()));
+        assert_eq!(last_sentence_end(text, Some("en")), None);
+    }
+
+    #[test]
+    fn test_sentence_boundary_thai() {
+        let text = "วันนี้อากาศดีมาก พรุ่งนี้ฝนจะตก";
+        assert!(has_sentence_boundary(text, Some("th")));
+        assert!(!has_sentence_boundary("วันนี้อากาศดีมาก", Some("th")));
+        assert!(!has_sentence_boundary(text, None));
+        assert_eq!(SentenceStyle::for_lang(Some("th_TH")), SentenceStyle::SpaceDelimited);
+    }
+
+    #[test]
+    fn test_quote_marks() {
+        assert_eq!(quote_marks(None, 0), ("“", "”"));
+        assert_eq!(quote_marks(Some("en-GB"), 1), ("‘", "’"));
+        assert_eq!(quote_marks(Some("fr_CA"), 0), ("«\u{a0}", "\u{a0}»"));
+        assert_eq!(quote_marks(Some("de"), 2), ("„", "“"));
+        assert_eq!(quote_marks(Some("ja"), 1), ("『", "』"));
+    }
+
+    #[test]
+    fn test_unicode_scripts() {
+        assert_eq!(to_unicode_superscript("2").as_deref(), Some("²"));
+        assert_eq!(to_unicode_superscript("-1").as_deref(), Some("⁻¹"));
+        assert_eq!(to_unicode_subscript(" 2 ").as_deref(), Some("₂"));
+        assert_eq!(to_unicode_subscript("n+1").as_deref(), Some("ₙ₊₁"));
+        assert_eq!(to_unicode_superscript("[1]"), None);
+        assert_eq!(to_unicode_subscript("max"), Some("ₘₐₓ".to_string()));
+        assert_eq!(to_unicode_subscript("Q"), None);
+        assert_eq!(to_unicode_subscript(""), None);
+    }
+
+    #[test]
+    fn test_normalize_class_list() {
+        assert_eq!(normalize_class_list("  post   entry-content  "), "post entry-content");
+        assert_eq!(normalize_class_list("post\n\tentry"), "post entry");
+        assert_eq!(normalize_class_list("   "), "");
+    }
+
     #[test]
     fn test_looks_like_byline() {
         assert!(looks_like_byline("By Alice Smith"));
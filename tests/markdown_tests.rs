@@ -28,6 +28,14 @@ fn test_inline_code() {
     assert!(md.contains("`println!`"));
 }
 
+#[test]
+fn test_kbd_and_samp_as_inline_code() {
+    let md = html_to_md(
+        "<p>Press <kbd>Ctrl+C</kbd>, or <kbd><kbd>Ctrl</kbd>+<kbd>Insert</kbd></kbd>, and look for <samp>Copied 12 bytes</samp>.</p>",
+    );
+    assert!(md.contains("Press `Ctrl+C`, or `Ctrl+Insert`, and look for `Copied 12 bytes`."));
+}
+
 #[test]
 fn test_strikethrough() {
     let md = html_to_md("<p><del>removed</del></p>");
@@ -73,6 +81,29 @@ fn test_figure_with_caption() {
     assert!(md.contains("![My caption](photo.jpg)"));
 }
 
+#[test]
+fn test_figure_caption_without_image() {
+    let md = html_to_md(
+        "<figure><table><tr><th>Year</th></tr><tr><td>2021</td></tr></table>\
+         <figcaption>Source: survey</figcaption></figure>",
+    );
+    assert!(
+        md.contains("| 2021 |\n\nSource: survey"),
+        "caption not separated: {}",
+        md
+    );
+
+    let md = html_to_md(
+        "<figure><blockquote><p>Quoted words.</p></blockquote>\
+         <figcaption>A. Author</figcaption></figure>",
+    );
+    assert!(
+        md.contains("> Quoted words.\n\nA. Author"),
+        "caption not separated: {}",
+        md
+    );
+}
+
 // ── Lists ───────────────────────────────────────────────────────────
 
 #[test]
@@ -314,6 +345,44 @@ fn test_markdown_not_generated_by_default() {
     }
 }
 
+#[test]
+fn test_article_to_markdown() {
+    let html = r#"
+    <html><head><title>Herons</title></head><body><article>
+        <h2>Hunting</h2>
+        <p>Grey herons hunt <strong>at dawn</strong> and <em>at dusk</em>, see <a href="/herons/hunting">the guide</a>.</p>
+        <p><img src="/img/heron.jpg" alt="A heron"></p>
+        <ul><li>Wait</li><li>Strike</li></ul>
+        <blockquote><p>Patience is the heron's only trick.</p></blockquote>
+        <pre><code class="language-rust">let heron = Heron::new();</code></pre>
+        <p>Prices rose 5*3 times, from #1 to [unknown] levels, for the heron-watching tours.</p>
+    </article></body></html>
+    "#;
+
+    let options = ReadabilityOptions::builder().char_threshold(50).build();
+    let article = Readability::new(html, Some("https://example.com/birds/"), Some(options))
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!(article.markdown_content.is_none());
+
+    let md = article.to_markdown();
+    assert!(md.contains("## Hunting"));
+    assert!(md.contains("**at dawn**"));
+    assert!(md.contains("_at dusk_"));
+    assert!(md.contains("[the guide](https://example.com/herons/hunting)"));
+    assert!(md.contains("![A heron](https://example.com/img/heron.jpg)"));
+    assert!(md.contains("- Wait\n- Strike"));
+    assert!(md.contains("> Patience is the heron's only trick."));
+    assert!(md.contains("```rust\nlet heron = Heron::new();\n```"));
+    assert!(md.contains("5\\*3"));
+    // Brackets that cannot start a link are left as written.
+    assert!(md.contains("from #1 to [unknown] levels"));
+
+    let md_opts = MarkdownOptions::default();
+    assert!(article.to_markdown_with(&md_opts).contains("*at dusk*"));
+}
+
 // ── Definition lists ────────────────────────────────────────────────
 
 #[test]
@@ -492,6 +561,13 @@ fn test_nbsp_handling() {
     assert!(md.contains("hello") && md.contains("world"));
 }
 
+#[test]
+fn test_soft_break_hints_join_words() {
+    let md =
+        html_to_md("<p>super<wbr>cali<wbr/>fragilistic, anti\u{00ad}dis\u{200b}establishment</p>");
+    assert!(md.contains("supercalifragilistic, antidisestablishment"));
+}
+
 #[test]
 fn test_crlf_in_text() {
     let md = html_to_md("<p>line1\r\nline2</p>");
@@ -1181,6 +1257,39 @@ fn test_subscript() {
     assert!(md.contains("~2~"), "subscript missing: {}", md);
 }
 
+#[test]
+fn test_unicode_sub_sup_option() {
+    let md_opts = MarkdownOptions {
+        unicode_sub_sup: true,
+        ..MarkdownOptions::default()
+    };
+    let md = readabilityrs::markdown::html_to_markdown(
+        "<p>H<sub>2</sub>O and x<sup>2</sup> but not x<sup>y</sup></p>",
+        &md_opts,
+    );
+    assert!(md.contains("H₂O"), "unicode subscript missing: {}", md);
+    assert!(md.contains("x²"), "unicode superscript missing: {}", md);
+    assert!(md.contains("x^y^"), "unmappable superscript should fall back: {}", md);
+}
+
+// ── Inline quotes ───────────────────────────────────────────────────
+
+#[test]
+fn test_nested_inline_quotes() {
+    let md = html_to_md("<p>She said <q>he called it <q>home</q></q>.</p>");
+    assert!(md.contains("She said “he called it ‘home’”."), "{}", md);
+
+    let md_opts = MarkdownOptions {
+        lang: Some("de".to_string()),
+        ..MarkdownOptions::default()
+    };
+    let md = readabilityrs::markdown::html_to_markdown(
+        r#"<p><q>Guten Tag</q> and <span lang="en"><q>hello</q></span></p>"#,
+        &md_opts,
+    );
+    assert!(md.contains("„Guten Tag“ and “hello”"), "{}", md);
+}
+
 // ── Video/audio with <source> children ──────────────────────────────
 
 #[test]